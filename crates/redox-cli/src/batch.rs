@@ -0,0 +1,166 @@
+use std::{
+	fs,
+	panic::{self, AssertUnwindSafe},
+	path::{Path, PathBuf},
+	time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+use clap::Args;
+use redox_core::{Vec2, config::Config, pathfinder::Pathfinder, state::Action};
+use serde::Serialize;
+use tracing::{info, warn};
+
+#[derive(Args, Debug)]
+pub struct BatchArgs {
+	/// Directory containing `.txt` / `.gmd` level files
+	#[arg(short, long)]
+	dir: PathBuf,
+
+	/// Output path for the JSON results
+	#[arg(short, long, default_value = "results.json")]
+	out: PathBuf,
+
+	/// Maximum nodes to expand per level before giving up
+	#[arg(long, default_value_t = 5_000_000)]
+	max_nodes: usize,
+
+	/// Maximum seconds to spend on each level
+	#[arg(long)]
+	time_limit: Option<f64>,
+
+	/// TOML file with extra settings such as `[id_overrides]`
+	#[arg(long)]
+	config: Option<PathBuf>,
+}
+
+#[derive(Serialize, Debug)]
+struct BatchResult {
+	file: String,
+	solved: bool,
+	nodes_expanded: usize,
+	best_x: f32,
+	goal_x: f32,
+	presses: usize,
+	elapsed_ms: u128,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	stop_reason: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	error: Option<String>,
+}
+
+impl BatchResult {
+	fn failed(file: String, error: String, elapsed_ms: u128) -> Self {
+		Self {
+			file,
+			solved: false,
+			nodes_expanded: 0,
+			best_x: 0.0,
+			goal_x: 0.0,
+			presses: 0,
+			elapsed_ms,
+			stop_reason: None,
+			error: Some(error),
+		}
+	}
+}
+
+pub fn run(args: BatchArgs) -> Result<()> {
+	let mut files: Vec<PathBuf> = fs::read_dir(&args.dir)
+		.with_context(|| format!("Failed to read directory: {:?}", args.dir))?
+		.filter_map(|entry| entry.ok().map(|e| e.path()))
+		.filter(|path| {
+			path.extension().is_some_and(|ext| {
+				ext.eq_ignore_ascii_case("txt") || ext.eq_ignore_ascii_case("gmd")
+			})
+		})
+		.collect();
+	files.sort();
+
+	// Shared by every level, so a broken config fails the batch once up front
+	let mut config = crate::config_file::load_config(args.config.as_deref())?;
+	config.search.max_nodes = Some(args.max_nodes);
+	config.search.time_budget = args.time_limit.map(Duration::from_secs_f64);
+
+	info!("Solving {} levels from {:?}", files.len(), args.dir);
+
+	let mut results = Vec::with_capacity(files.len());
+	for path in &files {
+		let file = path.display().to_string();
+		let start = Instant::now();
+
+		let result =
+			match panic::catch_unwind(AssertUnwindSafe(|| solve_level(path, config.clone()))) {
+				Ok(Ok(mut result)) => {
+					result.elapsed_ms = start.elapsed().as_millis();
+					result
+				}
+				Ok(Err(e)) => {
+					BatchResult::failed(file.clone(), e.to_string(), start.elapsed().as_millis())
+				}
+				Err(payload) => {
+					let message = payload
+						.downcast_ref::<&str>()
+						.map(|s| s.to_string())
+						.or_else(|| payload.downcast_ref::<String>().cloned())
+						.unwrap_or_else(|| "unknown panic".to_string());
+					BatchResult::failed(
+						file.clone(),
+						format!("panic: {}", message),
+						start.elapsed().as_millis(),
+					)
+				}
+			};
+
+		if result.solved {
+			info!("{}: solved in {} ms", file, result.elapsed_ms);
+		} else {
+			warn!("{}: not solved (best x={:.1})", file, result.best_x);
+		}
+
+		results.push(result);
+	}
+
+	let json = serde_json::to_string_pretty(&results)?;
+	fs::write(&args.out, json).with_context(|| format!("Failed to write {:?}", args.out))?;
+
+	let solved = results.iter().filter(|r| r.solved).count();
+	info!(
+		"Solved {}/{} levels, results written to {}",
+		solved,
+		results.len(),
+		args.out.display()
+	);
+
+	Ok(())
+}
+
+fn solve_level(path: &Path, config: Config) -> Result<BatchResult> {
+	let crate::LoadedLevel {
+		header,
+		objects: game_objects,
+	} = crate::load_level(path, &config.id_overrides)?;
+	let goal_x = crate::default_goal_x(&game_objects);
+	let start_pos = Vec2::new(0.0, 15.0);
+
+	config.physics.validate_speed(header.speed)?;
+	let pathfinder = Pathfinder::try_with_config(game_objects, config)?;
+	let start_state = pathfinder.initial_state(start_pos, &header);
+	let result = pathfinder.solve_from(start_state, goal_x);
+
+	Ok(BatchResult {
+		file: path.display().to_string(),
+		solved: result.reached_goal,
+		nodes_expanded: result.nodes_expanded,
+		best_x: result.best_x,
+		goal_x,
+		presses: result
+			.actions
+			.iter()
+			.filter(|(a, _)| *a == Action::Press)
+			.count(),
+		elapsed_ms: 0,
+		stop_reason: Some(result.stop_reason.to_string()),
+		error: None,
+	})
+}
@@ -0,0 +1,104 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use anyhow::{Context, Result};
+use redox_core::{
+	config::{Config, IdOverride},
+	game_object::{GameObjectType, HitboxShape},
+};
+use serde::Deserialize;
+
+/// Settings read from a `--config` TOML file
+///
+/// ```toml
+/// [id_overrides]
+/// 1234 = { type = "hazard", width = 30, height = 30 }
+/// 1235 = { type = "sawblade", shape = "circle", width = 24, height = 24 }
+/// 1236 = { type = "force_hold", width = 300, height = 300 }
+/// ```
+#[derive(Deserialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+	/// Keyed by object id, TOML keys are always strings
+	id_overrides: HashMap<String, IdOverrideEntry>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct IdOverrideEntry {
+	#[serde(rename = "type")]
+	kind: ObjectKind,
+	#[serde(default)]
+	shape: ShapeKind,
+	width: f32,
+	height: f32,
+}
+
+/// The object types worth overriding, everything else needs dedicated handling anyway
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+enum ObjectKind {
+	Solid,
+	Hazard,
+	Sawblade,
+	Breakable,
+	Decoration,
+	/// Holds the input down while the player is inside, cutting the search's branching
+	ForceHold,
+	/// Keeps the input released while the player is inside
+	ForceRelease,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+enum ShapeKind {
+	#[default]
+	Rectangle,
+	Circle,
+	/// Rounded along its width, `height` is the diameter
+	Capsule,
+}
+
+impl IdOverrideEntry {
+	fn to_override(&self) -> IdOverride {
+		let object_type = match self.kind {
+			ObjectKind::Solid => GameObjectType::Solid,
+			ObjectKind::Hazard => GameObjectType::Hazard,
+			ObjectKind::Sawblade => GameObjectType::Sawblade,
+			ObjectKind::Breakable => GameObjectType::Breakable,
+			ObjectKind::Decoration => GameObjectType::Decoration,
+			ObjectKind::ForceHold => GameObjectType::ForceHold,
+			ObjectKind::ForceRelease => GameObjectType::ForceRelease,
+		};
+
+		let shape = match self.shape {
+			ShapeKind::Rectangle => HitboxShape::Rectangle,
+			ShapeKind::Circle => HitboxShape::Circle,
+			ShapeKind::Capsule => HitboxShape::Capsule,
+		};
+
+		(object_type, shape, self.width, self.height)
+	}
+}
+
+/// Builds the default config with the settings from `path` applied, if one is given
+pub fn load_config(path: Option<&Path>) -> Result<Config> {
+	let mut config = Config::default();
+
+	let Some(path) = path else {
+		return Ok(config);
+	};
+
+	let content = fs::read_to_string(path)
+		.with_context(|| format!("Failed to read config file: {:?}", path))?;
+	let file: ConfigFile =
+		toml::from_str(&content).with_context(|| format!("Invalid config file: {:?}", path))?;
+
+	for (id, entry) in &file.id_overrides {
+		let id: i32 = id
+			.parse()
+			.with_context(|| format!("Override key {:?} is not an object id", id))?;
+		config.id_overrides.insert(id, entry.to_override());
+	}
+
+	Ok(config)
+}
@@ -0,0 +1,59 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use redox_core::{Vec2, pathfinder::Pathfinder, replay};
+
+#[derive(Args, Debug)]
+pub struct DiffArgs {
+	/// Path to the level file
+	#[arg(short, long)]
+	level: PathBuf,
+
+	/// The replay to compare against, usually the older solution
+	#[arg(long)]
+	a: PathBuf,
+
+	/// The replay to compare
+	#[arg(long)]
+	b: PathBuf,
+
+	/// How far apart in units the players may get before the runs count as diverged
+	#[arg(long, default_value_t = 1.0)]
+	threshold: f32,
+
+	/// TOML file with extra settings such as `[id_overrides]`
+	#[arg(long)]
+	config: Option<PathBuf>,
+}
+
+pub fn run(args: DiffArgs) -> Result<()> {
+	let mut config = crate::config_file::load_config(args.config.as_deref())?;
+	let crate::LoadedLevel { header, objects } =
+		crate::load_level(&args.level, &config.id_overrides)?;
+
+	let load = |path: &PathBuf| -> Result<replay::Replay> {
+		let data = fs::read(path).with_context(|| format!("Failed to read replay: {:?}", path))?;
+		Ok(replay::Replay::deserialize(&data)?)
+	};
+	let (a, b) = (load(&args.a)?, load(&args.b)?);
+	if a.framerate != b.framerate {
+		bail!(
+			"The replays were recorded at different tick rates ({} and {} TPS)",
+			a.framerate,
+			b.framerate
+		);
+	}
+
+	// Simulate at the replays' own tick rate so frame timings line up
+	config.physics.tps = a.framerate as f32;
+
+	config.physics.validate_speed(header.speed)?;
+	let pathfinder = Pathfinder::try_with_config(objects, config)?;
+	let start = pathfinder.initial_state(Vec2::new(0.0, 15.0), &header);
+
+	let diff = pathfinder.compare_paths(start, &a.to_actions(), &b.to_actions(), args.threshold);
+	println!("{diff}");
+
+	Ok(())
+}
@@ -1,93 +1,513 @@
+mod batch;
+mod config_file;
+mod diff;
+mod render;
+mod stats;
+mod verify;
 mod visualizer;
 
-use std::{fs, path::PathBuf};
+use std::{
+	collections::{BTreeSet, HashMap},
+	fs,
+	io::{self, Read, Write},
+	path::{Path, PathBuf},
+	time::{Duration, Instant},
+};
 
-use anyhow::{Context, Result};
-use clap::Parser;
-use glam::Vec2;
-use redox_core::{formats::level, game_object::GameObject, gdr, pathfinder::Pathfinder, state};
-use tracing::{error, info, warn};
+use anyhow::{Context, Result, bail};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+use redox_core::{
+	Vec2,
+	config::{IdOverride, ProgressWindow, SearchStrategy},
+	formats::level::{self, LevelHeader},
+	game_object::{GameObject, GameObjectType},
+	pathfinder::{
+		Pathfinder,
+		search::{PhaseTimings, StopReason},
+	},
+	replay, state,
+};
+use tracing::{error, info, trace, warn};
 use tracing_subscriber::{EnvFilter, prelude::*};
 
+/// Expansions the `--probe` pass gets before giving up
+const PROBE_NODES: usize = 20_000;
+
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-	/// Path to the level file
-	#[arg(short, long)]
-	level: PathBuf,
+#[command(
+	author,
+	version,
+	about,
+	long_about = None,
+	args_conflicts_with_subcommands = true,
+	subcommand_negates_reqs = true
+)]
+struct Cli {
+	#[command(subcommand)]
+	command: Option<Command>,
+
+	/// Only log warnings and errors
+	#[arg(short, long, global = true, conflicts_with = "verbose")]
+	quiet: bool,
+
+	/// Log more detail, once for debug and twice for trace. `-v` is taken by
+	/// `--visualize`, so this one is long only
+	#[arg(long, global = true, action = ArgAction::Count)]
+	verbose: u8,
+
+	#[command(flatten)]
+	solve: SolveArgs,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+	/// Solve every level in a directory and write a JSON summary
+	Batch(batch::BatchArgs),
+	/// Re-simulate a replay against a level and check that it reaches the goal
+	Verify(verify::VerifyArgs),
+	/// Draw the whole level's hitboxes and solved path to a PNG, without a window
+	Render(render::RenderArgs),
+	/// Re-simulate two replays of a level and report where they first diverge
+	Diff(diff::DiffArgs),
+}
+
+#[derive(Args, Debug)]
+struct SolveArgs {
+	/// Path to the level file, `-` reads the encoded level string from stdin
+	#[arg(short, long, required = true)]
+	level: Option<PathBuf>,
+
+	/// TOML file with extra settings such as `[id_overrides]`
+	#[arg(long)]
+	config: Option<PathBuf>,
 
-	/// Output path for the replay file
+	/// Output path for the replay file, `-` writes to stdout with GDR base64-encoded
 	#[arg(short, long, default_value = "replay.gdr")]
 	output: PathBuf,
 
+	/// Encoding of the output replay
+	#[arg(long, value_enum, default_value_t = ReplayFormatArg::Gdr)]
+	replay_format: ReplayFormatArg,
+
+	/// Merge presses and releases held for fewer than N ticks where the path still
+	/// reaches the goal without them
+	#[arg(long, value_name = "N")]
+	smooth: Option<usize>,
+
+	/// How far smoothing may move the player off the solved trajectory on any tick
+	#[arg(long, value_name = "UNITS", default_value_t = 15.0)]
+	smooth_tolerance: f32,
+
+	/// Run a quick greedy probe first and warn if it stalls short of the goal
+	#[arg(long)]
+	probe: bool,
+
+	/// Time parsing, setup and each phase of the search, and report where it went
+	#[arg(long)]
+	profile: bool,
+
+	/// Report the N objects the search died on most, for spotting the hardest obstacles
+	#[arg(long, value_name = "N")]
+	deadliest: Option<usize>,
+
+	/// Write a JSON summary of the search to this file, `-` writes it to stdout
+	#[arg(long)]
+	stats_json: Option<PathBuf>,
+
+	/// Write the per-frame state trajectory of the solution to this CSV file
+	#[arg(long)]
+	dump_trajectory: Option<PathBuf>,
+
 	/// Open the graphical visualizer
 	#[arg(short, long)]
 	visualize: bool,
+
+	/// Open the visualizer and play the level by hand instead of searching it
+	#[arg(long)]
+	manual: bool,
+
+	/// Stop the search after expanding this many nodes
+	#[arg(long)]
+	max_nodes: Option<usize>,
+
+	/// Stop the search after this many seconds
+	#[arg(long)]
+	time_limit: Option<f64>,
+
+	/// Start the player at this x instead of the level's origin
+	#[arg(long, default_value_t = 0.0)]
+	start_x: f32,
+
+	/// Start the player at this y, the center of the player
+	#[arg(long, default_value_t = 15.0)]
+	start_y: f32,
+
+	/// Start with gravity flipped, whatever the level header says
+	#[arg(long)]
+	flipped: bool,
+
+	/// Stop the search at this x instead of just past the last object
+	#[arg(long)]
+	goal_x: Option<f32>,
+
+	/// Use beam search, capping the open set at this many nodes
+	#[arg(long)]
+	beam_width: Option<usize>,
+
+	/// Lower the heuristic weight when progress stalls instead of giving up
+	#[arg(long)]
+	adaptive_weight: bool,
+
+	/// Find the fewest-frames path, much slower than the default search
+	#[arg(long)]
+	optimal: bool,
+
+	/// Let a held cube jump again as soon as it lands, like holding in game
+	#[arg(long)]
+	buffer_jumps: bool,
+
+	/// Leave out decoration and other objects the simulation never reads
+	#[arg(long)]
+	drop_decoration: bool,
+
+	/// With `--drop-decoration`, also drop blocks and hazards on this z layer or further
+	/// back, for levels whose background layers are pure scenery
+	#[arg(
+		long,
+		value_name = "Z",
+		requires = "drop_decoration",
+		allow_negative_numbers = true
+	)]
+	background_z_layer: Option<i32>,
+
+	/// Without a solution, replay the furthest run and report the inputs leading up to
+	/// where and how it dies
+	#[arg(long)]
+	frame_advance_on_death: bool,
+
+	/// Treat the search as stalled when best x barely moves over this many expansions
+	#[arg(long, value_name = "NODES")]
+	stagnation_window: Option<usize>,
+
+	/// Simulation ticks per second, lower values search faster with coarser inputs
+	#[arg(long, default_value_t = 240.0)]
+	tps: f32,
+
+	/// Continue a search from a checkpoint written by `--checkpoint-every`
+	#[arg(long)]
+	resume: Option<PathBuf>,
+
+	/// Save the search to PATH after every N expanded nodes
+	#[arg(long, num_args = 2, value_names = ["N", "PATH"])]
+	checkpoint_every: Vec<String>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum ReplayFormatArg {
+	/// GDReplayFormat binary
+	Gdr,
+	/// Plain `<frame> <1|0>` lines for click-based tools such as Mega Hack
+	Clicks,
+	/// JSON object with the tick rate, length and `{frame, down}` inputs
+	Json,
+}
+
+impl ReplayFormatArg {
+	fn encoder(self) -> Box<dyn replay::ReplayFormat> {
+		match self {
+			Self::Gdr => Box::new(replay::Gdr),
+			Self::Clicks => Box::new(replay::Clicks),
+			Self::Json => Box::new(replay::Json),
+		}
+	}
 }
 
 fn main() -> Result<()> {
-	let args = Args::parse();
+	let cli = Cli::parse();
 
+	let level = match (cli.quiet, cli.verbose) {
+		(true, _) => "warn",
+		(false, 0) => "info",
+		(false, 1) => "debug",
+		(false, _) => "trace",
+	};
+
+	// Logs go to stderr so stdout can carry the replay. RUST_LOG, when set, wins over
+	// the flags
 	tracing_subscriber::registry()
-		.with(tracing_subscriber::fmt::layer())
-		.with(
-			EnvFilter::try_from_default_env()
-				.unwrap_or_else(|_| EnvFilter::new(format!("{}=debug", env!("CARGO_CRATE_NAME")))),
-		)
+		.with(tracing_subscriber::fmt::layer().with_writer(io::stderr))
+		.with(EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+			EnvFilter::new(format!(
+				"{}={level},redox_core={level}",
+				env!("CARGO_CRATE_NAME")
+			))
+		}))
 		.init();
 
-	if args.visualize {
-		info!("Launching visualizer for level: {:?}", args.level);
+	match cli.command {
+		Some(Command::Batch(args)) => batch::run(args),
+		Some(Command::Verify(args)) => verify::run(args),
+		Some(Command::Render(args)) => render::run(args),
+		Some(Command::Diff(args)) => diff::run(args),
+		None => solve(cli.solve),
+	}
+}
+
+/// A decoded level file
+struct LoadedLevel {
+	header: LevelHeader,
+	objects: Vec<GameObject>,
+}
+
+/// Whether `path` is `-`, standing in for stdin or stdout
+fn is_stdio(path: &Path) -> bool {
+	path == Path::new("-")
+}
+
+/// Reads and decodes a level file into its header and game objects
+///
+/// A path of `-` reads an encoded level string from stdin instead.
+fn load_level(path: &Path, id_overrides: &HashMap<i32, IdOverride>) -> Result<LoadedLevel> {
+	let content = if is_stdio(path) {
+		let mut content = String::new();
+		io::stdin()
+			.read_to_string(&mut content)
+			.context("Failed to read level from stdin")?;
+		content
+	} else {
+		fs::read_to_string(path)
+			.with_context(|| format!("Failed to read level file: {:?}", path))?
+	};
+
+	let is_gmd = path
+		.extension()
+		.is_some_and(|ext| ext.eq_ignore_ascii_case("gmd"));
+
+	let decompressed = if is_gmd {
+		level::parse_gmd(&content)?
+	} else {
+		level::parse_level_data(&content)?
+	};
+
+	let header = level::parse_header(&decompressed);
+	let objects = level::par_parse_game_objects(&decompressed, id_overrides);
+
+	let unknown_ids: BTreeSet<i32> = objects
+		.iter()
+		.filter(|obj| obj.object_type == GameObjectType::Unknown)
+		.map(|obj| obj.id)
+		.collect();
+	if !unknown_ids.is_empty() {
+		warn!(
+			"{} object ids are unclassified and ignored by collision, add them to \
+			 [id_overrides] if they matter: {:?}",
+			unknown_ids.len(),
+			unknown_ids
+		);
+	}
+
+	Ok(LoadedLevel { header, objects })
+}
+
+/// Aim a bit past the last object
+fn default_goal_x(objects: &[GameObject]) -> f32 {
+	let mut max_x = 0.0;
+	for obj in objects {
+		if obj.position.x > max_x {
+			max_x = obj.position.x;
+		}
+	}
+
+	max_x + 200.0
+}
+
+fn solve(args: SolveArgs) -> Result<()> {
+	let level_path = args
+		.level
+		.expect("--level is required without a subcommand");
+
+	let mut config = config_file::load_config(args.config.as_deref())?;
+
+	if is_stdio(&args.output) && args.stats_json.as_deref().is_some_and(is_stdio) {
+		bail!("--output and --stats-json can't both write to stdout");
+	}
+
+	if args.visualize || args.manual {
+		info!("Launching visualizer for level: {:?}", level_path);
 		macroquad::Window::from_config(
 			visualizer::window_conf(),
-			visualizer::run_visualizer(args.level),
+			visualizer::run_visualizer(level_path, config.id_overrides, args.manual),
 		);
 		return Ok(());
 	}
 
-	if !args.level.exists() {
-		error!("Level file not found: {:?}", args.level);
+	if !is_stdio(&level_path) && !level_path.exists() {
+		error!("Level file not found: {:?}", level_path);
 		return Ok(());
 	}
 
-	let content = fs::read_to_string(&args.level)
-		.with_context(|| format!("Failed to read level file: {:?}", args.level))?;
-
 	info!("Parsing level data...");
-	let decompressed = level::parse_level_data(&content)?;
+	let parse_started = Instant::now();
+	let LoadedLevel {
+		mut header,
+		objects: game_objects,
+	} = load_level(&level_path, &config.id_overrides)?;
+	header.gravity_flipped |= args.flipped;
+	let parse_time = parse_started.elapsed();
 
-	let raw_objects = level::parse_objects(&decompressed);
-	let game_objects: Vec<GameObject> = raw_objects.iter().map(GameObject::from_raw).collect();
+	info!("{}", level::summarize(&game_objects));
 
-	info!("Converted {} game objects", game_objects.len());
+	let level_goal_x = default_goal_x(&game_objects);
+	info!("Level Max X: {}", level_goal_x - 200.0);
 
-	let mut max_x = 0.0;
-	for obj in &game_objects {
-		if obj.position.x > max_x {
-			max_x = obj.position.x;
-		}
+	let goal_x = args.goal_x.unwrap_or(level_goal_x);
+	if goal_x <= args.start_x {
+		bail!("Goal x {} is not past start x {}", goal_x, args.start_x);
 	}
 
-	info!("Level Max X: {}", max_x);
-	let goal_x = max_x + 200.0; // Aim a bit past the last object
-
 	info!("Initializing Pathfinder...");
 
-	let start_pos = Vec2::new(0.0, 15.0); // Start 15 units up (center of 30 unit cube)
+	let start_pos = Vec2::new(args.start_x, args.start_y);
+
+	config.search.max_nodes = args.max_nodes;
+	config.search.time_budget = args.time_limit.map(Duration::from_secs_f64);
+	config.physics.tps = args.tps;
+	config.search.adaptive_weight = args.adaptive_weight;
+	config.search.optimal = args.optimal;
+	config.search.allow_buffer_jump = args.buffer_jumps;
+	config.drop_decoration = args.drop_decoration;
+	config.background_z_layer = args.background_z_layer;
+	config.search.count_deaths = args.deadliest.is_some();
+	config.search.profile = args.profile;
+	if let Some(nodes) = args.stagnation_window {
+		config.search.progress_window = Some(ProgressWindow {
+			nodes,
+			..Default::default()
+		});
+	}
+	if let Some(width) = args.beam_width {
+		config.search.strategy = SearchStrategy::Beam { width };
+	}
+
+	let object_count = game_objects.len();
+	let build_started = Instant::now();
+	config.physics.validate_speed(header.speed)?;
+	let pathfinder = Pathfinder::try_with_config(game_objects, config)?;
+	let build_time = build_started.elapsed();
 
-	let pathfinder = Pathfinder::new(game_objects, goal_x);
+	let dropped = object_count - pathfinder.objects().len();
+	if dropped > 0 {
+		let kind = if args.drop_decoration {
+			"duplicate and decoration"
+		} else {
+			"duplicate"
+		};
+		info!(
+			"Dropped {} {} objects, {} left",
+			dropped,
+			kind,
+			pathfinder.objects().len()
+		);
+	}
 
 	info!("Starting search...");
 
-	let mut session = pathfinder.start_search(start_pos, goal_x);
+	let start_state = pathfinder.initial_state(start_pos, &header);
+	if let Some(id) = pathfinder.collides(&start_state) {
+		warn!(
+			"Start position ({}, {}) is already colliding with object {}",
+			start_pos.x, start_pos.y, id
+		);
+	}
+	info!(
+		"Start: {:?} at speed index {}{}{}",
+		start_state.mode,
+		start_state.speed,
+		if start_state.mini { ", mini" } else { "" },
+		if start_state.gravity_flipped {
+			", flipped gravity"
+		} else {
+			""
+		}
+	);
+
+	if args.probe {
+		let reached = pathfinder.reachable_x(start_state, PROBE_NODES);
+		if reached < goal_x {
+			warn!(
+				"Greedy probe stalled at x={:.1}, the level likely gets hard around there",
+				reached
+			);
+		} else {
+			info!("Greedy probe reached the goal");
+		}
+	}
+
+	let checkpoint = match args.checkpoint_every.as_slice() {
+		[every, path] => {
+			let every: usize = every
+				.parse()
+				.with_context(|| format!("--checkpoint-every count {:?} is not a number", every))?;
+			Some((every.max(1), PathBuf::from(path)))
+		}
+		_ => None,
+	};
+
+	let mut session = match &args.resume {
+		Some(path) => {
+			let session = pathfinder.resume_search(path)?;
+			info!(
+				"Resumed from {} at {} expanded nodes, best x={:.1}",
+				path.display(),
+				session.nodes_expanded,
+				session.best_x
+			);
+			session
+		}
+		None => pathfinder.start_search_from(start_state, goal_x),
+	};
+
+	let search_started = Instant::now();
+	let mut last_checkpoint = session.nodes_expanded;
 	loop {
-		let done = pathfinder.step(&mut session, goal_x);
-		if done {
+		let mut finished = true;
+		for progress in pathfinder.search_iter(&mut session, goal_x) {
+			trace!(
+				"Expanded {} nodes, best x={:.1}, open set {}",
+				progress.nodes_expanded, progress.best_x, progress.open_set_size
+			);
+
+			// Step out of the iterator to save, then pick the search back up
+			if let Some((every, _)) = &checkpoint
+				&& !progress.is_finished()
+				&& progress.nodes_expanded - last_checkpoint >= *every
+			{
+				finished = false;
+				break;
+			}
+		}
+
+		if finished {
 			break;
 		}
+
+		if let Some((_, path)) = &checkpoint {
+			session.save(path)?;
+			last_checkpoint = session.nodes_expanded;
+			info!(
+				"Checkpointed {} expanded nodes to {}",
+				session.nodes_expanded,
+				path.display()
+			);
+		}
 	}
 
-	let chosen_idx = if let Some(idx) = session.goal_reached_index {
+	let search_time = search_started.elapsed();
+
+	let chosen_idx = if let (Some(StopReason::Goal), Some(idx)) =
+		(session.stop_reason, session.goal_reached_index)
+	{
 		info!("Path found! Reached goal at node index {}", idx);
 		idx
 	} else {
@@ -108,16 +528,30 @@ fn main() -> Result<()> {
 		}
 
 		let best_node = &session.all_nodes[best_i];
-		warn!("No path to goal found.");
+		match session.stop_reason {
+			Some(reason) => warn!("No path to goal found (stopped: {}).", reason),
+			None => warn!("No path to goal found."),
+		}
 		warn!(
 			"Generating partial replay to furthest point: x={:.1} (node {})",
 			best_node.state.position.x, best_i
 		);
 
+		if args.frame_advance_on_death {
+			let furthest = pathfinder.reconstruct_path(&session.all_nodes, best_node);
+			report_furthest_run(&pathfinder, start_state, &furthest, goal_x);
+		}
+
 		best_i
 	};
 
-	let path = pathfinder.reconstruct_path(&session.all_nodes, &session.all_nodes[chosen_idx]);
+	let mut path = pathfinder.reconstruct_path(&session.all_nodes, &session.all_nodes[chosen_idx]);
+
+	if let Some(min_hold) = args.smooth {
+		let before = path.len();
+		path = pathfinder.smooth_path(start_state, &path, goal_x, min_hold, args.smooth_tolerance);
+		info!("Smoothed the path from {} to {} steps", before, path.len());
+	}
 
 	info!("Path Length: {} steps", path.len());
 	let presses = path
@@ -126,10 +560,150 @@ fn main() -> Result<()> {
 		.count();
 	info!("Total Presses: {}", presses);
 
-	match gdr::save_gdr(&path, args.output.to_str().unwrap(), 240.0) {
+	if let Some(n) = args.deadliest {
+		for (rank, (obj, deaths)) in pathfinder.deadliest(&session, n).iter().enumerate() {
+			info!(
+				"#{} object {} ({:?}) at ({:.1}, {:.1}): {} deaths",
+				rank + 1,
+				obj.id,
+				obj.object_type,
+				obj.position.x,
+				obj.position.y,
+				deaths
+			);
+		}
+	}
+
+	if args.profile {
+		log_profile(parse_time, build_time, search_time, &session.timings);
+	}
+
+	if let Some(stats_path) = &args.stats_json {
+		let stats = stats::SearchStats::new(
+			&session,
+			&path,
+			goal_x,
+			search_started.elapsed(),
+			pathfinder.dt(),
+		);
+		match stats.write(stats_path) {
+			Ok(()) if is_stdio(stats_path) => info!("Wrote search stats to stdout"),
+			Ok(()) => info!("Wrote search stats to {}", stats_path.display()),
+			Err(e) => error!("Failed to write search stats: {:?}", e),
+		}
+	}
+
+	if let Some(csv_path) = &args.dump_trajectory {
+		let states = pathfinder.simulate_trajectory(start_state, &path);
+		match write_trajectory_csv(&states, csv_path) {
+			Ok(()) => info!(
+				"Wrote {} trajectory frames to {}",
+				states.len(),
+				csv_path.display()
+			),
+			Err(e) => error!("Failed to write trajectory: {:?}", e),
+		}
+	}
+
+	match save_replay(&path, &args.output, args.replay_format, pathfinder.tps()) {
+		Ok(()) if is_stdio(&args.output) => info!("Wrote replay to stdout"),
 		Ok(()) => info!("Saved replay to {}", args.output.display()),
 		Err(e) => error!("Failed to save replay: {:?}", e),
 	}
 
 	Ok(())
 }
+
+/// Logs setup times and how the search time splits across expansion phases
+fn log_profile(parse: Duration, build: Duration, search: Duration, timings: &PhaseTimings) {
+	info!(
+		"Profile: parsing {:.1?}, pathfinder setup {:.1?}",
+		parse, build
+	);
+
+	let percent = |part: Duration| {
+		if search.is_zero() {
+			0.0
+		} else {
+			part.as_secs_f64() / search.as_secs_f64() * 100.0
+		}
+	};
+
+	for (name, time) in timings.phases() {
+		info!(
+			"Profile: {:<9} {:>10.1?} {:5.1}%",
+			name,
+			time,
+			percent(time)
+		);
+	}
+
+	let other = search.saturating_sub(timings.total());
+	info!(
+		"Profile: {:<9} {:>10.1?} {:5.1}%",
+		"other",
+		other,
+		percent(other)
+	);
+	info!("Profile: search    {:>10.1?}", search);
+}
+
+/// Logs the inputs of the furthest partial path and where idling on from its end dies
+fn report_furthest_run(
+	pathfinder: &Pathfinder, start: state::State, path: &[(state::Action, f32)], goal_x: f32,
+) {
+	info!("Furthest run inputs:");
+	for (frame, down) in replay::to_click_frames(path, pathfinder.tps()) {
+		info!(
+			"  frame {:>6}: {}",
+			frame,
+			if down { "press" } else { "release" }
+		);
+	}
+
+	warn!(
+		"Furthest run {}",
+		pathfinder.validate_path(start, path, goal_x)
+	);
+}
+
+fn save_replay(
+	path: &[(state::Action, f32)], out_path: &Path, format: ReplayFormatArg, tps: f32,
+) -> Result<()> {
+	let format = format.encoder();
+	let data = format.encode(path, tps)?;
+
+	if !is_stdio(out_path) {
+		fs::write(out_path, data)?;
+		return Ok(());
+	}
+
+	// Binary formats are base64-encoded to survive a text pipe
+	let text = if format.is_binary() {
+		STANDARD.encode(&data).into_bytes()
+	} else {
+		data
+	};
+
+	let mut stdout = io::stdout().lock();
+	stdout.write_all(&text)?;
+	if !text.ends_with(b"\n") {
+		stdout.write_all(b"\n")?;
+	}
+	stdout.flush()?;
+
+	Ok(())
+}
+
+fn write_trajectory_csv(states: &[state::State], out_path: &Path) -> Result<()> {
+	let mut csv = String::from("frame,x,y,vy,on_ground,mode,rotation\n");
+	for (frame, s) in states.iter().enumerate() {
+		csv.push_str(&format!(
+			"{},{},{},{},{},{:?},{}\n",
+			frame, s.position.x, s.position.y, s.vy, s.on_ground, s.mode, s.rotation
+		));
+	}
+
+	fs::write(out_path, csv)?;
+	Ok(())
+}
@@ -0,0 +1,280 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use redox_core::{
+	Vec2,
+	game_object::{GameObject, GameObjectType, RenderShape},
+	pathfinder::Pathfinder,
+};
+use tracing::{info, warn};
+
+/// Longest side of the image, past this the PNG gets unwieldy to open
+const MAX_SIDE: u32 = 32_768;
+/// World units left around the level's objects and the path
+const MARGIN: f32 = 30.0;
+/// Width of the path line in pixels
+const PATH_THICKNESS: f32 = 2.0;
+
+const BACKGROUND: [u8; 4] = [20, 20, 30, 255];
+const GROUND: [u8; 4] = [35, 35, 50, 255];
+const SOLID: [u8; 4] = [60, 100, 180, 255];
+const HAZARD: [u8; 4] = [220, 60, 60, 255];
+const PORTAL: [u8; 4] = [80, 200, 120, 255];
+const TRIGGER: [u8; 4] = [240, 200, 60, 255];
+const OTHER: [u8; 4] = [150, 150, 150, 255];
+const PATH: [u8; 4] = [255, 140, 0, 255];
+
+#[derive(Args, Debug)]
+pub struct RenderArgs {
+	/// Path to the level file, `-` reads the encoded level string from stdin
+	#[arg(short, long)]
+	level: PathBuf,
+
+	/// Output path for the PNG
+	#[arg(short, long, default_value = "level.png")]
+	out: PathBuf,
+
+	/// Image width in pixels, the height follows from the level's extent
+	#[arg(long, default_value_t = 4096)]
+	width: u32,
+
+	/// TOML file with extra settings such as `[id_overrides]`
+	#[arg(long)]
+	config: Option<PathBuf>,
+
+	/// Only draw the level, without searching for a path through it
+	#[arg(long)]
+	no_path: bool,
+
+	/// Stop the search after expanding this many nodes
+	#[arg(long, default_value_t = 5_000_000)]
+	max_nodes: usize,
+}
+
+pub fn run(args: RenderArgs) -> Result<()> {
+	if !(1..=MAX_SIDE).contains(&args.width) {
+		bail!("--width must be between 1 and {}", MAX_SIDE);
+	}
+
+	let mut config = crate::config_file::load_config(args.config.as_deref())?;
+	let crate::LoadedLevel { header, objects } =
+		crate::load_level(&args.level, &config.id_overrides)?;
+	let goal_x = crate::default_goal_x(&objects);
+
+	config.search.max_nodes = Some(args.max_nodes);
+	config.physics.validate_speed(header.speed)?;
+	let pathfinder = Pathfinder::try_with_config(objects, config)?;
+
+	let path = if args.no_path {
+		Vec::new()
+	} else {
+		let start = pathfinder.initial_state(Vec2::new(0.0, 15.0), &header);
+		let result = pathfinder.solve_from(start, goal_x);
+		if !result.reached_goal {
+			warn!(
+				"No path to goal found (stopped: {}), drawing the run to x={:.1}",
+				result.stop_reason, result.best_x
+			);
+		}
+
+		pathfinder
+			.simulate_trajectory(start, &result.actions)
+			.iter()
+			.map(|state| state.position)
+			.collect()
+	};
+
+	let mut canvas = Canvas::fit(pathfinder.objects(), &path, goal_x, args.width)?;
+	canvas.fill_ground();
+
+	for obj in pathfinder.objects() {
+		if let Some(color) = object_color(obj) {
+			canvas.fill_shape(obj.render_shape(), color);
+		}
+	}
+
+	for segment in path.windows(2) {
+		canvas.line(segment[0], segment[1], PATH_THICKNESS, PATH);
+	}
+
+	image::save_buffer(
+		&args.out,
+		&canvas.pixels.concat(),
+		canvas.width,
+		canvas.height,
+		image::ColorType::Rgba8,
+	)
+	.with_context(|| format!("Failed to write image: {:?}", args.out))?;
+
+	info!(
+		"Rendered {} objects to {} ({}x{})",
+		pathfinder.objects().len(),
+		args.out.display(),
+		canvas.width,
+		canvas.height
+	);
+
+	Ok(())
+}
+
+/// Fill color for an object, `None` for ones that aren't worth drawing
+fn object_color(obj: &GameObject) -> Option<[u8; 4]> {
+	let object_type = obj.object_type;
+	if object_type.is_decoration() {
+		return None;
+	}
+
+	Some(match object_type {
+		t if t.is_solid() || t == GameObjectType::Slope => SOLID,
+		t if t.is_hazard() => HAZARD,
+		t if t.is_portal() => PORTAL,
+		t if t.is_pad() || t.is_orb() => TRIGGER,
+		_ => OTHER,
+	})
+}
+
+/// RGBA pixels covering a rectangle of the world, y up
+struct Canvas {
+	width: u32,
+	height: u32,
+	pixels: Vec<[u8; 4]>,
+	/// World position of the top left corner
+	origin: Vec2,
+	pixels_per_unit: f32,
+}
+
+impl Canvas {
+	/// Sized to show every object, the path and the goal at `width` pixels across
+	fn fit(objects: &[GameObject], path: &[Vec2], goal_x: f32, width: u32) -> Result<Self> {
+		let mut min = Vec2::new(0.0, 0.0);
+		let mut max = Vec2::new(goal_x, 0.0);
+		for obj in objects.iter().filter(|obj| object_color(obj).is_some()) {
+			let (lo, hi) = obj.aabb();
+			min = min.min(lo);
+			max = max.max(hi);
+		}
+		for &point in path {
+			min = min.min(point);
+			max = max.max(point);
+		}
+		min -= Vec2::splat(MARGIN);
+		max += Vec2::splat(MARGIN);
+
+		let pixels_per_unit = width as f32 / (max.x - min.x);
+		let height = ((max.y - min.y) * pixels_per_unit).ceil().max(1.0) as u32;
+		if height > MAX_SIDE {
+			bail!(
+				"The level is {} pixels tall at this width, pick a smaller --width",
+				height
+			);
+		}
+
+		Ok(Self {
+			width,
+			height,
+			pixels: vec![BACKGROUND; width as usize * height as usize],
+			origin: Vec2::new(min.x, max.y),
+			pixels_per_unit,
+		})
+	}
+
+	fn to_pixel(&self, world: Vec2) -> Vec2 {
+		Vec2::new(world.x - self.origin.x, self.origin.y - world.y) * self.pixels_per_unit
+	}
+
+	/// Shades everything under y = 0
+	fn fill_ground(&mut self) {
+		let ground = self.to_pixel(Vec2::ZERO).y.max(0.0) as u32;
+		for y in ground.min(self.height)..self.height {
+			let row = y as usize * self.width as usize;
+			self.pixels[row..row + self.width as usize].fill(GROUND);
+		}
+	}
+
+	fn fill_shape(&mut self, shape: RenderShape, color: [u8; 4]) {
+		match shape {
+			RenderShape::Rect { corners } => {
+				let corners = corners.map(|corner| self.to_pixel(corner));
+				self.fill_polygon(&corners, color);
+			}
+			RenderShape::Circle { center, radius } => {
+				let center = self.to_pixel(center);
+				let radius = radius * self.pixels_per_unit;
+				self.fill(center - radius, center + radius, color, |p| {
+					p.distance_squared(center) <= radius * radius
+				});
+			}
+			RenderShape::Capsule { a, b, radius } => {
+				let (a, b) = (self.to_pixel(a), self.to_pixel(b));
+				self.capsule(a, b, radius * self.pixels_per_unit, color);
+			}
+		}
+	}
+
+	/// A line between two world points, `thickness` pixels wide
+	fn line(&mut self, a: Vec2, b: Vec2, thickness: f32, color: [u8; 4]) {
+		let (a, b) = (self.to_pixel(a), self.to_pixel(b));
+		self.capsule(a, b, thickness * 0.5, color);
+	}
+
+	/// Convex polygon in pixel space, corners in order around the edge either way round
+	fn fill_polygon(&mut self, corners: &[Vec2; 4], color: [u8; 4]) {
+		let min = corners.iter().copied().reduce(Vec2::min).unwrap();
+		let max = corners.iter().copied().reduce(Vec2::max).unwrap();
+
+		self.fill(min, max, color, |p| {
+			let mut sign = 0.0f32;
+			for i in 0..corners.len() {
+				let edge = corners[(i + 1) % corners.len()] - corners[i];
+				let cross = edge.perp_dot(p - corners[i]);
+				if cross != 0.0 {
+					if sign != 0.0 && cross.signum() != sign {
+						return false;
+					}
+					sign = cross.signum();
+				}
+			}
+			true
+		});
+	}
+
+	/// The segment `a`-`b` in pixel space swept by `radius`, at least a pixel wide
+	fn capsule(&mut self, a: Vec2, b: Vec2, radius: f32, color: [u8; 4]) {
+		let radius = radius.max(0.5);
+		let ab = b - a;
+		let length_squared = ab.length_squared();
+
+		self.fill(a.min(b) - radius, a.max(b) + radius, color, |p| {
+			let t = if length_squared > 0.0 {
+				((p - a).dot(ab) / length_squared).clamp(0.0, 1.0)
+			} else {
+				0.0
+			};
+			p.distance_squared(a + ab * t) <= radius * radius
+		});
+	}
+
+	/// Colors every pixel in the `min`-`max` box whose center passes `inside`
+	fn fill(&mut self, min: Vec2, max: Vec2, color: [u8; 4], inside: impl Fn(Vec2) -> bool) {
+		let x0 = min.x.floor().max(0.0) as u32;
+		let y0 = min.y.floor().max(0.0) as u32;
+		let x1 = (max.x.ceil().max(0.0) as u32).min(self.width);
+		let y1 = (max.y.ceil().max(0.0) as u32).min(self.height);
+
+		// Anything thinner than a pixel would otherwise miss every pixel center
+		let (x1, y1) = (
+			x1.max((x0 + 1).min(self.width)),
+			y1.max((y0 + 1).min(self.height)),
+		);
+		let tiny = max.x - min.x < 1.0 || max.y - min.y < 1.0;
+
+		for y in y0..y1 {
+			for x in x0..x1 {
+				if tiny || inside(Vec2::new(x as f32 + 0.5, y as f32 + 0.5)) {
+					self.pixels[y as usize * self.width as usize + x as usize] = color;
+				}
+			}
+		}
+	}
+}
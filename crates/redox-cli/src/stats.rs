@@ -0,0 +1,66 @@
+use std::{
+	fs,
+	io::{self, Write},
+	path::Path,
+	time::Duration,
+};
+
+use anyhow::Result;
+use redox_core::{
+	pathfinder::search::{SearchSession, StopReason},
+	state::Action,
+};
+use serde::Serialize;
+
+/// Machine-readable summary of a solve, for comparing runs across parameter changes
+#[derive(Serialize, Debug)]
+pub struct SearchStats {
+	solved: bool,
+	goal_x: f32,
+	best_x: f32,
+	nodes_expanded: usize,
+	/// Nodes ever pushed, expanded or not
+	nodes_generated: usize,
+	open_set_peak: usize,
+	elapsed_ms: u128,
+	presses: usize,
+	path_frames: u32,
+	stop_reason: Option<String>,
+}
+
+impl SearchStats {
+	pub fn new(
+		session: &SearchSession, path: &[(Action, f32)], goal_x: f32, elapsed: Duration, dt: f32,
+	) -> Self {
+		let duration: f32 = path.iter().map(|(_, duration)| duration).sum();
+
+		Self {
+			solved: session.stop_reason == Some(StopReason::Goal),
+			goal_x,
+			best_x: session.best_x,
+			nodes_expanded: session.nodes_expanded,
+			nodes_generated: session.all_nodes.len(),
+			open_set_peak: session.open_set_peak,
+			elapsed_ms: elapsed.as_millis(),
+			presses: path.iter().filter(|(a, _)| *a == Action::Press).count(),
+			path_frames: (duration / dt).round() as u32,
+			stop_reason: session.stop_reason.map(|r| r.to_string()),
+		}
+	}
+
+	/// Writes pretty-printed JSON to `path`, or to stdout for `-`
+	pub fn write(&self, path: &Path) -> Result<()> {
+		let mut json = serde_json::to_string_pretty(self)?;
+		json.push('\n');
+
+		if crate::is_stdio(path) {
+			let mut stdout = io::stdout().lock();
+			stdout.write_all(json.as_bytes())?;
+			stdout.flush()?;
+		} else {
+			fs::write(path, json)?;
+		}
+
+		Ok(())
+	}
+}
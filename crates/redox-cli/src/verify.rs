@@ -0,0 +1,69 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{Result, bail};
+use clap::Args;
+use redox_core::{
+	Vec2,
+	pathfinder::{Pathfinder, validate::PathValidation},
+	replay,
+};
+use tracing::info;
+
+#[derive(Args, Debug)]
+pub struct VerifyArgs {
+	/// Path to the level file
+	#[arg(short, long)]
+	level: PathBuf,
+
+	/// Path to the replay to check
+	#[arg(short, long)]
+	replay: PathBuf,
+
+	/// TOML file with extra settings such as `[id_overrides]`
+	#[arg(long)]
+	config: Option<PathBuf>,
+}
+
+pub fn run(args: VerifyArgs) -> Result<()> {
+	let mut config = crate::config_file::load_config(args.config.as_deref())?;
+	let crate::LoadedLevel { header, objects } =
+		crate::load_level(&args.level, &config.id_overrides)?;
+	let goal_x = crate::default_goal_x(&objects);
+
+	let replay = replay::Replay::deserialize(&fs::read(&args.replay)?)?;
+	let actions = replay.to_actions();
+	info!(
+		"Loaded {} actions from {} at {} TPS",
+		actions.len(),
+		args.replay.display(),
+		replay.framerate
+	);
+
+	// Simulate at the replay's own tick rate so frame timings line up
+	config.physics.tps = replay.framerate as f32;
+
+	config.physics.validate_speed(header.speed)?;
+	let pathfinder = Pathfinder::try_with_config(objects, config)?;
+	let start = pathfinder.initial_state(Vec2::new(0.0, 15.0), &header);
+
+	match pathfinder.validate_path(start, &actions, goal_x) {
+		PathValidation::Ok { final_x, frame } => {
+			info!(
+				"Replay reaches the goal (x={:.1}) at frame {}",
+				final_x, frame
+			);
+			Ok(())
+		}
+		PathValidation::Failed {
+			frame,
+			reason,
+			state,
+		} => bail!(
+			"Replay {} at frame {} (x={:.1}, y={:.1})",
+			reason,
+			frame,
+			state.position.x,
+			state.position.y
+		),
+	}
+}
@@ -1,72 +1,118 @@
-use std::sync::{
-	Arc,
-	atomic::{AtomicBool, Ordering},
-	mpsc,
+use std::{
+	sync::{
+		Arc,
+		atomic::{AtomicBool, Ordering},
+		mpsc,
+	},
+	time::Instant,
 };
 
 use macroquad::prelude::*;
 use redox_core::{
-	game_object::GameObject,
-	pathfinder::Pathfinder,
+	game_object::GameObjectType,
+	pathfinder::{Pathfinder, playback::Playhead, search::StopReason},
 	state::{Action, GameMode, State},
 };
 
 use super::{
+	eta::Eta,
+	manual::ManualPlay,
+	minimap::Minimap,
 	renderer::Renderer,
 	types::{SearchMessage, Vec2, VisualizerState},
 };
 
+/// Top edge and height of the playback progress bar
+const PROGRESS_BAR_Y: f32 = 76.0;
+const PROGRESS_BAR_HEIGHT: f32 = 4.0;
+/// Extra pixels above and below the bar that still grab it
+const PROGRESS_BAR_GRAB_MARGIN: f32 = 8.0;
+
 pub struct VisualizerApp {
-	pub game_objects: Vec<GameObject>,
+	pub pathfinder: Pathfinder,
 	pub goal_x: f32,
 	pub start_pos: Vec2,
+	pub start_state: State,
 	pub dt: f32,
 
 	pub viz_state: VisualizerState,
 	pub renderer: Renderer,
 	pub camera_lerp_alpha: f32,
+	/// Whether the camera tracks the player or frontier, cleared by manual panning
+	pub follow_camera: bool,
+	pub last_mouse_pos: (f32, f32),
 
 	pub current_best_path: Vec<Vec2>,
+	/// Sampled search nodes accumulated across progress updates
+	pub explored_nodes: Vec<(Vec2, f32)>,
+	pub show_heatmap: bool,
 	pub current_best_x: f32,
 	pub nodes_expanded: usize,
 	pub open_set_size: usize,
+	/// Best x over recent progress messages, for the time left shown while computing
+	pub eta: Eta,
+	pub stop_reason: Option<StopReason>,
 
 	pub states: Vec<State>,
 	pub path_points: Vec<Vec2>,
-	pub elapsed: f32,
-	pub total_time: f32,
+	pub playhead: Playhead,
 	pub paused: bool,
 	pub speed: f32,
+	/// Whether the progress bar is being dragged
+	pub scrubbing: bool,
 	pub final_actions: Option<Vec<(Action, f32)>>,
 
+	pub minimap: Minimap,
+	pub show_minimap: bool,
+
+	/// Index of the object clicked during playback, shown with its hitbox details
+	pub inspected: Option<usize>,
+
+	/// Set while in `VisualizerState::Manual`
+	pub manual: Option<ManualPlay>,
+
 	pub rx: mpsc::Receiver<SearchMessage>,
 	pub stop_flag: Arc<AtomicBool>,
 }
 
 impl VisualizerApp {
 	pub fn new(
-		game_objects: Vec<GameObject>, goal_x: f32, start_pos: Vec2, dt: f32,
+		pathfinder: Pathfinder, goal_x: f32, start_state: State, dt: f32,
 		rx: mpsc::Receiver<SearchMessage>, stop_flag: Arc<AtomicBool>,
 	) -> Self {
+		let start_pos = start_state.position;
+		let minimap = Minimap::new(&pathfinder, goal_x);
+
 		Self {
-			game_objects,
+			pathfinder,
 			goal_x,
 			start_pos,
+			start_state,
 			dt,
 			viz_state: VisualizerState::Computing,
 			renderer: Renderer::new(start_pos),
 			camera_lerp_alpha: 0.08,
+			follow_camera: true,
+			last_mouse_pos: mouse_position(),
 			current_best_path: Vec::new(),
+			explored_nodes: Vec::new(),
+			show_heatmap: false,
 			current_best_x: 0.0,
 			nodes_expanded: 0,
 			open_set_size: 0,
+			eta: Eta::default(),
+			stop_reason: None,
 			states: Vec::new(),
 			path_points: Vec::new(),
-			elapsed: 0.0,
-			total_time: 0.0,
+			playhead: Playhead::default(),
 			paused: true,
 			speed: 1.0,
+			scrubbing: false,
 			final_actions: None,
+			minimap,
+			show_minimap: true,
+			inspected: None,
+			manual: None,
 			rx,
 			stop_flag,
 		}
@@ -77,22 +123,27 @@ impl VisualizerApp {
 			match msg {
 				SearchMessage::Progress {
 					best_path,
+					sampled_nodes,
 					best_x,
 					nodes_expanded,
 					open_set_size,
 				} => {
 					self.current_best_path = best_path;
+					self.explored_nodes.extend(sampled_nodes);
 					self.current_best_x = best_x;
 					self.nodes_expanded = nodes_expanded;
 					self.open_set_size = open_set_size;
+					self.eta.record(Instant::now(), best_x);
 				}
 				SearchMessage::Done {
 					actions,
 					best_x,
 					nodes_expanded,
+					stop_reason,
 				} => {
 					self.current_best_x = best_x;
 					self.nodes_expanded = nodes_expanded;
+					self.stop_reason = Some(stop_reason);
 					self.final_actions = Some(actions);
 					self.viz_state = VisualizerState::Playback;
 				}
@@ -109,24 +160,47 @@ impl VisualizerApp {
 				SearchMessage::NoSolution {
 					best_x,
 					nodes_expanded,
+					stop_reason,
 				} => {
 					self.current_best_x = best_x;
 					self.nodes_expanded = nodes_expanded;
+					self.stop_reason = Some(stop_reason);
 					self.viz_state = VisualizerState::NoSolution;
 				}
 			}
 		}
 
+		if is_key_pressed(KeyCode::E) {
+			self.show_heatmap = !self.show_heatmap;
+		}
+
+		if is_key_pressed(KeyCode::H) {
+			self.renderer.show_hitboxes = !self.renderer.show_hitboxes;
+		}
+
+		// M already switches to manual play
+		if is_key_pressed(KeyCode::N) {
+			self.show_minimap = !self.show_minimap;
+		}
+
+		self.handle_camera_input();
+
 		if is_key_pressed(KeyCode::S) && self.viz_state == VisualizerState::Computing {
 			self.stop_flag.store(true, Ordering::Relaxed);
 		}
 
+		if is_key_pressed(KeyCode::M)
+			&& matches!(
+				self.viz_state,
+				VisualizerState::Playback | VisualizerState::NoSolution
+			) {
+			self.start_manual();
+		}
+
 		match self.viz_state {
 			VisualizerState::Computing => {
 				if let Some(last_pos) = self.current_best_path.last() {
-					let target_camera = *last_pos;
-					self.renderer.camera_pos +=
-						(target_camera - self.renderer.camera_pos) * self.camera_lerp_alpha;
+					self.follow(*last_pos);
 				}
 			}
 			VisualizerState::Playback => {
@@ -165,60 +239,175 @@ impl VisualizerApp {
 				}
 
 				if is_key_pressed(KeyCode::R) {
-					self.elapsed = 0.0;
+					self.playhead.seek(0);
 					self.paused = true;
 					self.renderer.camera_pos = self.start_pos;
+					self.follow_camera = true;
+				}
+
+				self.handle_scrubbing();
+				self.handle_minimap_seek();
+				self.handle_inspect();
+
+				if self.paused && !self.states.is_empty() {
+					let frame = self.playhead.frame();
+					if is_key_pressed(KeyCode::Comma) {
+						self.seek_frame(frame.saturating_sub(1));
+					}
+
+					if is_key_pressed(KeyCode::Period) {
+						self.seek_frame(frame + 1);
+					}
 				}
 
 				if !self.paused {
-					self.elapsed += frame_dt * self.speed;
+					self.playhead.advance(frame_dt * self.speed / self.dt);
 
-					if self.elapsed >= self.total_time {
-						self.elapsed = self.total_time;
+					if self.playhead.at_end() {
 						self.paused = true;
 					}
 				}
 
 				let player_pos = self.get_player_pos();
-				self.renderer.camera_pos +=
-					(player_pos - self.renderer.camera_pos) * self.camera_lerp_alpha;
+				self.follow(player_pos);
 			}
 			VisualizerState::NoSolution => {}
+			VisualizerState::Manual => {
+				let Some(manual) = self.manual.as_mut() else {
+					return;
+				};
+
+				if is_key_pressed(KeyCode::R) {
+					manual.restart();
+				}
+
+				let held = is_key_down(KeyCode::Space)
+					|| is_key_down(KeyCode::Up)
+					|| is_mouse_button_down(MouseButton::Left);
+				manual.update(&self.pathfinder, frame_dt, held);
+
+				let player_pos = manual.state.position;
+				self.follow(player_pos);
+			}
 		}
 	}
 
-	fn setup_playback(&mut self, actions: &[(Action, f32)]) {
-		let pf_playback = Pathfinder::new(self.game_objects.clone(), self.goal_x);
-
-		self.states = Vec::with_capacity(actions.len() + 1);
-
-		let mut curr_state = State {
-			position: self.start_pos,
-			vy: 0.0,
-			on_ground: true,
-			rotation: 0.0,
-			mode: GameMode::Cube,
-			gravity_flipped: false,
-			floor: 0.0,
-			ceiling: f32::MAX,
-			pressing: false,
-			speed: 1,
-		};
-		self.states.push(curr_state);
+	/// Switches to manual play from the level start
+	pub fn start_manual(&mut self) {
+		self.manual = Some(ManualPlay::new(self.start_state, self.goal_x));
+		self.renderer.camera_pos = self.start_pos;
+		self.follow_camera = true;
+		self.viz_state = VisualizerState::Manual;
+	}
+
+	/// Mouse wheel zooms around the cursor, right or middle drag pans the camera
+	fn handle_camera_input(&mut self) {
+		let (mouse_x, mouse_y) = mouse_position();
 
-		let dt = self.dt;
-		for (action, duration) in actions {
-			curr_state = pf_playback.simulate_step(&curr_state, *action);
-			self.states.push(curr_state);
+		let (_, wheel_y) = mouse_wheel();
+		if wheel_y != 0.0 {
+			self.renderer
+				.zoom_at(mouse_x, mouse_y, 1.1_f32.powf(wheel_y.signum()));
+		}
 
-			let remaining_frames = ((duration / dt).round() as usize).saturating_sub(1);
-			for _ in 0..remaining_frames {
-				curr_state = pf_playback.simulate_step(&curr_state, Action::None);
-				self.states.push(curr_state);
+		if is_mouse_button_down(MouseButton::Right) || is_mouse_button_down(MouseButton::Middle) {
+			let (last_x, last_y) = self.last_mouse_pos;
+			if (mouse_x, mouse_y) != (last_x, last_y) {
+				self.renderer.pan(mouse_x - last_x, mouse_y - last_y);
+				self.follow_camera = false;
 			}
 		}
 
-		self.total_time = (self.states.len() as f32 - 1.0) * dt;
+		self.last_mouse_pos = (mouse_x, mouse_y);
+	}
+
+	/// Eases the camera toward `target`, resuming auto-follow once it leaves the screen
+	fn follow(&mut self, target: Vec2) {
+		if !self.follow_camera && !self.renderer.is_on_screen(target) {
+			self.follow_camera = true;
+		}
+
+		if self.follow_camera {
+			self.renderer.camera_pos +=
+				(target - self.renderer.camera_pos) * self.camera_lerp_alpha;
+		}
+	}
+
+	/// Drags on the progress bar seek playback, pausing it while held
+	fn handle_scrubbing(&mut self) {
+		let (mouse_x, mouse_y) = mouse_position();
+		let grab_area = PROGRESS_BAR_Y - PROGRESS_BAR_GRAB_MARGIN
+			..=PROGRESS_BAR_Y + PROGRESS_BAR_HEIGHT + PROGRESS_BAR_GRAB_MARGIN;
+
+		if is_mouse_button_pressed(MouseButton::Left) && grab_area.contains(&mouse_y) {
+			self.scrubbing = true;
+			self.paused = true;
+		}
+
+		if !is_mouse_button_down(MouseButton::Left) {
+			self.scrubbing = false;
+		}
+
+		if self.scrubbing {
+			let t = (mouse_x / screen_width()).clamp(0.0, 1.0);
+			self.playhead.seek_progress(t);
+		}
+	}
+
+	/// Clicks on the minimap jump playback to the first frame the player reaches that x
+	fn handle_minimap_seek(&mut self) {
+		if !self.show_minimap || !is_mouse_button_pressed(MouseButton::Left) {
+			return;
+		}
+
+		let (mouse_x, mouse_y) = mouse_position();
+		let Some(x) = self.minimap.x_at(mouse_x, mouse_y) else {
+			return;
+		};
+
+		let frame = self
+			.states
+			.iter()
+			.position(|state| state.position.x >= x)
+			.unwrap_or(self.states.len().saturating_sub(1));
+		self.seek_frame(frame);
+		self.paused = true;
+		self.follow_camera = true;
+	}
+
+	/// Left clicks below the HUD select the object under the cursor, or
+	/// clear the selection on empty space
+	fn handle_inspect(&mut self) {
+		if self.scrubbing || !is_mouse_button_pressed(MouseButton::Left) {
+			return;
+		}
+
+		let (mouse_x, mouse_y) = mouse_position();
+		if mouse_y <= PROGRESS_BAR_Y + PROGRESS_BAR_HEIGHT + PROGRESS_BAR_GRAB_MARGIN {
+			return;
+		}
+		if self.show_minimap && self.minimap.x_at(mouse_x, mouse_y).is_some() {
+			return;
+		}
+
+		let point = self.renderer.screen_to_world(mouse_x, mouse_y);
+
+		// Later objects draw on top, so they win when hitboxes overlap
+		self.inspected = self.pathfinder.objects().iter().rposition(|obj| {
+			obj.object_type != GameObjectType::Unknown && obj.contains_point(point)
+		});
+	}
+
+	fn seek_frame(&mut self, frame: usize) {
+		self.playhead.seek(frame);
+	}
+
+	fn setup_playback(&mut self, actions: &[(Action, f32)]) {
+		self.states = self
+			.pathfinder
+			.simulate_trajectory(self.start_state, actions);
+
+		self.playhead = Playhead::new(self.states.len());
 
 		let sample_step = 4.max(self.states.len() / 2000);
 		self.path_points = self
@@ -229,7 +418,6 @@ impl VisualizerApp {
 			.collect();
 
 		self.renderer.camera_pos = self.start_pos;
-		self.elapsed = 0.0;
 		self.paused = true;
 	}
 
@@ -238,9 +426,8 @@ impl VisualizerApp {
 			return self.start_pos;
 		}
 
-		let idx_f = (self.elapsed / self.dt).clamp(0.0, (self.states.len() - 1) as f32);
-		let idx = idx_f.floor() as usize;
-		let t = idx_f - (idx as f32);
+		let idx = self.playhead.frame();
+		let t = self.playhead.ticks() - idx as f32;
 		let s0 = &self.states[idx];
 
 		let s1 = if idx + 1 < self.states.len() {
@@ -253,8 +440,7 @@ impl VisualizerApp {
 	}
 
 	pub fn get_current_state(&self) -> &State {
-		let idx_f = (self.elapsed / self.dt).clamp(0.0, (self.states.len() - 1) as f32);
-		&self.states[idx_f.floor() as usize]
+		&self.states[self.playhead.frame()]
 	}
 
 	pub fn draw(&self) {
@@ -263,8 +449,11 @@ impl VisualizerApp {
 				clear_background(Color::from_rgba(30, 30, 40, 255));
 
 				self.renderer
-					.draw_game_objects(&self.game_objects, self.viz_state);
+					.draw_game_objects(&self.pathfinder, self.viz_state);
 				self.renderer.draw_goal_line(self.goal_x);
+				if self.show_heatmap {
+					self.renderer.draw_explored_nodes(&self.explored_nodes);
+				}
 				self.renderer
 					.draw_path(&self.current_best_path, Color::from_rgba(50, 255, 50, 220));
 
@@ -273,13 +462,18 @@ impl VisualizerApp {
 					draw_circle(bx, by, 6.0, Color::from_rgba(255, 200, 50, 255));
 				}
 
+				if self.show_minimap {
+					self.minimap
+						.draw(self.current_best_x, Color::from_rgba(255, 200, 50, 255));
+				}
+
 				self.draw_hud_computing();
 			}
 			VisualizerState::Playback => {
 				clear_background(Color::from_rgba(240, 240, 240, 255));
 
 				self.renderer
-					.draw_game_objects(&self.game_objects, self.viz_state);
+					.draw_game_objects(&self.pathfinder, self.viz_state);
 				self.renderer.draw_goal_line(self.goal_x);
 				self.renderer
 					.draw_path(&self.path_points, Color::from_rgba(50, 200, 50, 180));
@@ -288,6 +482,18 @@ impl VisualizerApp {
 				let current_state = self.get_current_state();
 				self.renderer.draw_player(player_pos, current_state);
 
+				if let Some(obj) = self
+					.inspected
+					.and_then(|index| self.pathfinder.objects().get(index))
+				{
+					self.renderer.draw_object_info(obj);
+				}
+
+				if self.show_minimap {
+					self.minimap
+						.draw(player_pos.x, Color::from_rgba(255, 140, 0, 255));
+				}
+
 				self.draw_hud_playback();
 			}
 			VisualizerState::NoSolution => {
@@ -297,8 +503,10 @@ impl VisualizerApp {
 
 				draw_text(
 					&format!(
-						"Explored {} nodes, best X: {:.1}",
-						self.nodes_expanded, self.current_best_x
+						"Explored {} nodes, best X: {:.1} (stopped: {})",
+						self.nodes_expanded,
+						self.current_best_x,
+						self.stop_reason.unwrap_or(StopReason::Exhausted)
 					),
 					20.0,
 					80.0,
@@ -306,7 +514,40 @@ impl VisualizerApp {
 					WHITE,
 				);
 
-				draw_text("Press ESC to quit", 20.0, 120.0, 20.0, WHITE);
+				draw_text(
+					"Press M to play manually, ESC to quit",
+					20.0,
+					120.0,
+					20.0,
+					WHITE,
+				);
+			}
+			VisualizerState::Manual => {
+				let Some(manual) = &self.manual else {
+					return;
+				};
+
+				clear_background(Color::from_rgba(240, 240, 240, 255));
+
+				self.renderer
+					.draw_game_objects(&self.pathfinder, self.viz_state);
+				self.renderer.draw_goal_line(self.goal_x);
+
+				if let Some(death_pos) = manual.last_death {
+					let (dx, dy) = self.renderer.world_to_screen(death_pos);
+					draw_line(dx - 8.0, dy - 8.0, dx + 8.0, dy + 8.0, 3.0, RED);
+					draw_line(dx - 8.0, dy + 8.0, dx + 8.0, dy - 8.0, 3.0, RED);
+				}
+
+				self.renderer
+					.draw_player(manual.state.position, &manual.state);
+
+				if self.show_minimap {
+					self.minimap
+						.draw(manual.state.position.x, Color::from_rgba(255, 140, 0, 255));
+				}
+
+				self.draw_hud_manual(manual);
 			}
 		}
 	}
@@ -329,9 +570,10 @@ impl VisualizerApp {
 		let progress = (self.current_best_x / self.goal_x).clamp(0.0, 1.0);
 		draw_text(
 			&format!(
-				"Progress: {:.1}% | Open set: {}",
+				"Progress: {:.1}% | Open set: {} | {}",
 				progress * 100.0,
-				self.open_set_size
+				self.open_set_size,
+				self.eta.estimate(self.goal_x)
 			),
 			15.0,
 			48.0,
@@ -339,7 +581,7 @@ impl VisualizerApp {
 			Color::from_rgba(180, 180, 180, 255),
 		);
 		draw_text(
-			"Q: Quit | S: Stop & Play",
+			"Q: Quit | S: Stop & Play | H: Hitboxes | E: Explored nodes | N: Minimap",
 			15.0,
 			68.0,
 			16.0,
@@ -376,21 +618,98 @@ impl VisualizerApp {
 		);
 	}
 
+	fn draw_hud_manual(&self, manual: &ManualPlay) {
+		let screen_w = screen_width();
+		draw_rectangle(0.0, 0.0, screen_w, 80.0, Color::from_rgba(0, 0, 0, 180));
+
+		let status = if manual.finished {
+			"FINISHED"
+		} else {
+			"MANUAL"
+		};
+		let progress = (manual.state.position.x / self.goal_x).clamp(0.0, 1.0);
+
+		draw_text(
+			&format!(
+				"{} | Deaths: {} | X: {:.1} / {:.1} ({:.1}%)",
+				status,
+				manual.deaths,
+				manual.state.position.x,
+				self.goal_x,
+				progress * 100.0
+			),
+			15.0,
+			25.0,
+			22.0,
+			WHITE,
+		);
+
+		draw_text(
+			&format!(
+				"Input: {} | Frame: {}",
+				if manual.state.pressing {
+					"PRESS"
+				} else {
+					"---"
+				},
+				manual.frame
+			),
+			15.0,
+			48.0,
+			18.0,
+			Color::from_rgba(180, 180, 180, 255),
+		);
+
+		draw_text(
+			"Space/Up/Click: Jump | R: Restart | Wheel/RMB: Zoom/Pan | H: Hitboxes | N: Minimap | Q: Quit",
+			15.0,
+			68.0,
+			16.0,
+			Color::from_rgba(150, 150, 150, 255),
+		);
+
+		draw_rectangle(
+			0.0,
+			PROGRESS_BAR_Y,
+			screen_w,
+			PROGRESS_BAR_HEIGHT,
+			Color::from_rgba(60, 60, 60, 255),
+		);
+		draw_rectangle(
+			0.0,
+			PROGRESS_BAR_Y,
+			screen_w * progress,
+			PROGRESS_BAR_HEIGHT,
+			Color::from_rgba(50, 200, 50, 255),
+		);
+	}
+
 	fn draw_hud_playback(&self) {
 		let screen_w = screen_width();
 		draw_rectangle(0.0, 0.0, screen_w, 80.0, Color::from_rgba(0, 0, 0, 180));
 
 		let status = if self.paused { "PAUSED" } else { "PLAYING" };
+		let stop_str = match self.stop_reason {
+			Some(StopReason::Goal) | None => String::new(),
+			Some(reason) => format!(" | Partial ({})", reason),
+		};
 		let current_state = self.get_current_state();
 		let mode_str = match current_state.mode {
 			GameMode::Cube => "CUBE",
 			GameMode::Ship => "SHIP",
+			GameMode::Spider => "SPIDER",
+			GameMode::Robot => "ROBOT",
+			GameMode::Swing => "SWING",
 		};
 
 		draw_text(
 			&format!(
-				"{} | Speed: {:.2}x | Time: {:.2}s / {:.2}s",
-				status, self.speed, self.elapsed, self.total_time
+				"{} | Speed: {:.2}x | Time: {:.2}s / {:.2}s{}",
+				status,
+				self.speed,
+				self.playhead.ticks() * self.dt,
+				self.playhead.last_frame() as f32 * self.dt,
+				stop_str
 			),
 			15.0,
 			25.0,
@@ -407,8 +726,8 @@ impl VisualizerApp {
 				} else {
 					"---"
 				},
-				(self.elapsed / self.dt).floor() as usize,
-				self.states.len() - 1
+				self.playhead.frame(),
+				self.playhead.last_frame()
 			),
 			15.0,
 			48.0,
@@ -417,21 +736,43 @@ impl VisualizerApp {
 		);
 
 		draw_text(
-			"Space: Play/Pause | 1-4: Speed | R: Reset | Q: Quit",
+			"Space: Play/Pause | ,/.: Step | Drag bar: Seek | Wheel/RMB: Zoom/Pan | 1-4: Speed | Click: Inspect | R: Reset | M: Manual | H: Hitboxes | N: Minimap | Q: Quit",
 			15.0,
 			68.0,
 			16.0,
 			Color::from_rgba(150, 150, 150, 255),
 		);
 
+		let frame_text = format!("FRAME {}", self.playhead.frame());
+		let frame_size = measure_text(&frame_text, None, 36, 1.0);
+		draw_text(
+			&frame_text,
+			screen_w - frame_size.width - 15.0,
+			40.0,
+			36.0,
+			Color::from_rgba(255, 220, 80, 255),
+		);
+
 		// Progress bar
-		let progress = (self.elapsed / self.total_time).clamp(0.0, 1.0);
-		draw_rectangle(0.0, 76.0, screen_w, 4.0, Color::from_rgba(60, 60, 60, 255));
+		let progress = self.playhead.progress();
+		draw_rectangle(
+			0.0,
+			PROGRESS_BAR_Y,
+			screen_w,
+			PROGRESS_BAR_HEIGHT,
+			Color::from_rgba(60, 60, 60, 255),
+		);
 		draw_rectangle(
 			0.0,
-			76.0,
+			PROGRESS_BAR_Y,
 			screen_w * progress,
-			4.0,
+			PROGRESS_BAR_HEIGHT,
+			Color::from_rgba(50, 200, 50, 255),
+		);
+		draw_circle(
+			screen_w * progress,
+			PROGRESS_BAR_Y + PROGRESS_BAR_HEIGHT * 0.5,
+			if self.scrubbing { 7.0 } else { 5.0 },
 			Color::from_rgba(50, 200, 50, 255),
 		);
 	}
@@ -0,0 +1,88 @@
+use std::{
+	collections::VecDeque,
+	fmt,
+	time::{Duration, Instant},
+};
+
+/// Samples older than this are dropped, so the rate follows the section being searched
+const WINDOW: Duration = Duration::from_secs(10);
+/// Shortest span of samples an estimate is made from
+const MIN_SPAN: Duration = Duration::from_secs(1);
+/// Below this many units per second the search counts as stalled
+const STALLED_RATE: f32 = 0.5;
+/// Longest estimate shown as a number
+const MAX_ETA: Duration = Duration::from_secs(60 * 60);
+
+/// Estimated time until the search reaches the goal
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Estimate {
+	/// Not enough samples yet
+	Unknown,
+	/// Best x has barely moved across the window
+	Stalled,
+	Remaining(Duration),
+	/// Further out than `MAX_ETA`
+	Beyond,
+}
+
+impl fmt::Display for Estimate {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Estimate::Unknown => f.write_str("ETA ..."),
+			Estimate::Stalled => f.write_str("stalled"),
+			Estimate::Beyond => write!(f, "ETA > {}h", MAX_ETA.as_secs() / 3600),
+			Estimate::Remaining(remaining) => {
+				let secs = remaining.as_secs();
+				if secs < 60 {
+					write!(f, "ETA ~{}s", secs.max(1))
+				} else {
+					write!(f, "ETA ~{}m {:02}s", secs / 60, secs % 60)
+				}
+			}
+		}
+	}
+}
+
+/// Rate best x has been advancing at, from when progress messages arrived
+#[derive(Default)]
+pub struct Eta {
+	samples: VecDeque<(Instant, f32)>,
+}
+
+impl Eta {
+	pub fn record(&mut self, now: Instant, best_x: f32) {
+		self.samples.push_back((now, best_x));
+		while self
+			.samples
+			.front()
+			.is_some_and(|&(at, _)| now.duration_since(at) > WINDOW)
+		{
+			self.samples.pop_front();
+		}
+	}
+
+	pub fn estimate(&self, goal_x: f32) -> Estimate {
+		let (Some(&(first_at, first_x)), Some(&(last_at, last_x))) =
+			(self.samples.front(), self.samples.back())
+		else {
+			return Estimate::Unknown;
+		};
+
+		let span = last_at.duration_since(first_at);
+		if span < MIN_SPAN {
+			return Estimate::Unknown;
+		}
+
+		let rate = (last_x - first_x) / span.as_secs_f32();
+		if rate < STALLED_RATE {
+			return Estimate::Stalled;
+		}
+
+		let remaining = ((goal_x - last_x) / rate).max(0.0);
+		if remaining > MAX_ETA.as_secs_f32() {
+			return Estimate::Beyond;
+		}
+
+		Estimate::Remaining(Duration::from_secs_f32(remaining))
+	}
+}
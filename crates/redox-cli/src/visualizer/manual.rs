@@ -0,0 +1,81 @@
+use redox_core::{
+	pathfinder::Pathfinder,
+	state::{Action, State},
+};
+
+use super::types::Vec2;
+
+/// Most ticks simulated in one rendered frame, so a stall doesn't fast-forward the run
+const MAX_TICKS_PER_FRAME: usize = 16;
+
+/// A player-driven run, stepped at the simulation's own tick rate
+pub struct ManualPlay {
+	pub start_state: State,
+	pub state: State,
+	pub goal_x: f32,
+	/// Real time not yet simulated, less than one tick once `update` returns
+	pub accumulator: f32,
+	/// Ticks into the current attempt
+	pub frame: usize,
+	pub deaths: usize,
+	/// Where the last attempt ended, shown until the next death
+	pub last_death: Option<Vec2>,
+	pub finished: bool,
+}
+
+impl ManualPlay {
+	pub fn new(start_state: State, goal_x: f32) -> Self {
+		Self {
+			start_state,
+			state: start_state,
+			goal_x,
+			accumulator: 0.0,
+			frame: 0,
+			deaths: 0,
+			last_death: None,
+			finished: false,
+		}
+	}
+
+	/// Advances by `frame_dt` seconds of real time with the button held or not
+	pub fn update(&mut self, pathfinder: &Pathfinder, frame_dt: f32, held: bool) {
+		if self.finished {
+			return;
+		}
+
+		let dt = pathfinder.dt();
+		self.accumulator = (self.accumulator + frame_dt).min(dt * MAX_TICKS_PER_FRAME as f32);
+
+		while self.accumulator >= dt {
+			self.accumulator -= dt;
+
+			let action = match (held, self.state.pressing) {
+				(true, false) => Action::Press,
+				(false, true) => Action::Release,
+				_ => Action::None,
+			};
+			self.state = pathfinder.simulate_step(&self.state, action);
+			self.frame += 1;
+
+			if pathfinder.out_of_bounds(&self.state) || pathfinder.collides(&self.state).is_some() {
+				self.deaths += 1;
+				self.last_death = Some(self.state.position);
+				self.restart();
+				return;
+			}
+
+			if self.state.position.x >= self.goal_x {
+				self.finished = true;
+				return;
+			}
+		}
+	}
+
+	/// Back to the start, keeping the death count
+	pub fn restart(&mut self) {
+		self.state = self.start_state;
+		self.accumulator = 0.0;
+		self.frame = 0;
+		self.finished = false;
+	}
+}
@@ -0,0 +1,102 @@
+use macroquad::prelude::*;
+use redox_core::pathfinder::Pathfinder;
+
+/// Columns the level is split into for the hazard density shading
+const BINS: usize = 256;
+const HEIGHT: f32 = 14.0;
+const MARGIN: f32 = 15.0;
+
+/// Strip along the bottom of the screen showing the whole level's x range
+pub struct Minimap {
+	min_x: f32,
+	max_x: f32,
+	/// Hazards per column, scaled so the densest is 1
+	density: Vec<f32>,
+}
+
+impl Minimap {
+	pub fn new(pathfinder: &Pathfinder, goal_x: f32) -> Self {
+		let min_x = pathfinder
+			.objects()
+			.first()
+			.map_or(0.0, |obj| obj.aabb().0.x.min(0.0));
+		let max_x = goal_x.max(min_x + 1.0);
+
+		let mut counts = vec![0u32; BINS];
+		for obj in pathfinder.objects() {
+			if obj.object_type.is_hazard() {
+				let t = (obj.position.x - min_x) / (max_x - min_x);
+				counts[((t * BINS as f32) as usize).min(BINS - 1)] += 1;
+			}
+		}
+
+		let densest = counts.iter().copied().max().unwrap_or(0).max(1) as f32;
+
+		Self {
+			min_x,
+			max_x,
+			density: counts.iter().map(|&count| count as f32 / densest).collect(),
+		}
+	}
+
+	/// Screen-space `(x, y, width, height)` of the strip
+	fn rect() -> (f32, f32, f32, f32) {
+		let width = screen_width() - MARGIN * 2.0;
+		(MARGIN, screen_height() - HEIGHT - MARGIN, width, HEIGHT)
+	}
+
+	fn screen_x(&self, x: f32) -> f32 {
+		let (left, _, width, _) = Self::rect();
+		let t = ((x - self.min_x) / (self.max_x - self.min_x)).clamp(0.0, 1.0);
+		left + t * width
+	}
+
+	/// Level x under a screen point, `None` when it is outside the strip
+	pub fn x_at(&self, sx: f32, sy: f32) -> Option<f32> {
+		let (left, top, width, height) = Self::rect();
+		let inside = (left..=left + width).contains(&sx) && (top..=top + height).contains(&sy);
+
+		inside.then(|| self.min_x + (sx - left) / width * (self.max_x - self.min_x))
+	}
+
+	/// Draws the strip with the goal and a marker at `marker_x`
+	pub fn draw(&self, marker_x: f32, marker_color: Color) {
+		let (left, top, width, height) = Self::rect();
+		draw_rectangle(left, top, width, height, Color::from_rgba(0, 0, 0, 160));
+
+		let bin_width = width / BINS as f32;
+		for (i, &density) in self.density.iter().enumerate() {
+			if density > 0.0 {
+				draw_rectangle(
+					left + i as f32 * bin_width,
+					top,
+					bin_width.max(1.0),
+					height,
+					Color::new(0.9, 0.25, 0.25, 0.15 + 0.45 * density),
+				);
+			}
+		}
+
+		let goal = self.screen_x(self.max_x);
+		draw_line(
+			goal,
+			top - 2.0,
+			goal,
+			top + height + 2.0,
+			2.0,
+			Color::from_rgba(180, 50, 200, 255),
+		);
+
+		let marker = self.screen_x(marker_x);
+		draw_rectangle(marker - 2.0, top - 3.0, 4.0, height + 6.0, marker_color);
+
+		draw_rectangle_lines(
+			left,
+			top,
+			width,
+			height,
+			1.0,
+			Color::from_rgba(120, 120, 120, 200),
+		);
+	}
+}
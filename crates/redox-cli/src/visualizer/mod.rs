@@ -1,9 +1,12 @@
 mod app;
+mod eta;
+mod manual;
+mod minimap;
 mod renderer;
 mod types;
 
 use std::{
-	fs,
+	collections::HashMap,
 	sync::{
 		Arc,
 		atomic::{AtomicBool, Ordering},
@@ -14,10 +17,14 @@ use std::{
 
 use app::VisualizerApp;
 use macroquad::prelude::*;
-use redox_core::{formats::level, game_object::GameObject, pathfinder::Pathfinder};
+use redox_core::{
+	config::IdOverride,
+	pathfinder::{Pathfinder, search::StopReason},
+	state::State,
+};
 pub use types::SearchMessage;
 
-type Vec2 = ::glam::Vec2;
+type Vec2 = redox_core::Vec2;
 
 pub fn window_conf() -> Conf {
 	Conf {
@@ -28,7 +35,11 @@ pub fn window_conf() -> Conf {
 	}
 }
 
-pub async fn run_visualizer(level_path: std::path::PathBuf) {
+/// Opens the visualizer on a level, searching it in the background or, with `manual`,
+/// handing control straight to the keyboard
+pub async fn run_visualizer(
+	level_path: std::path::PathBuf, id_overrides: HashMap<i32, IdOverride>, manual: bool,
+) {
 	if !level_path.exists() {
 		loop {
 			clear_background(BLACK);
@@ -51,12 +62,15 @@ pub async fn run_visualizer(level_path: std::path::PathBuf) {
 		}
 	}
 
-	let content = match fs::read_to_string(&level_path) {
-		Ok(c) => c,
+	let crate::LoadedLevel {
+		header,
+		objects: game_objects,
+	} = match crate::load_level(&level_path, &id_overrides) {
+		Ok(level) => level,
 		Err(e) => loop {
 			clear_background(BLACK);
 			draw_text(
-				&format!("Error: Failed to read {:?}: {}", level_path, e),
+				&format!("Error: Failed to load {:?}: {}", level_path, e),
 				20.0,
 				40.0,
 				30.0,
@@ -70,115 +84,33 @@ pub async fn run_visualizer(level_path: std::path::PathBuf) {
 		},
 	};
 
-	let decompressed = match level::parse_level_data(&content) {
-		Ok(s) => s,
-		Err(e) => loop {
-			clear_background(BLACK);
-			draw_text(
-				&format!("Error: Failed to parse level data: {}", e),
-				20.0,
-				40.0,
-				30.0,
-				RED,
-			);
-			draw_text("Press ESC to quit", 20.0, 80.0, 20.0, WHITE);
-			if is_key_pressed(KeyCode::Escape) {
-				return;
-			}
-			next_frame().await;
-		},
-	};
-	let raw_objects = level::parse_objects(&decompressed);
-	let game_objects: Vec<GameObject> = raw_objects.iter().map(GameObject::from_raw).collect();
-
 	let mut max_x = 0.0f32;
 	for obj in &game_objects {
-		max_x = max_x.max(obj.position.x + obj.width * 0.5);
+		max_x = max_x.max(obj.aabb().1.x);
 	}
 
 	let goal_x = max_x + 200.0;
 	let start_pos = Vec2::new(0.0, 15.0);
 
-	let pf = Pathfinder::new(game_objects.clone(), goal_x);
+	let pf = Pathfinder::new(game_objects, goal_x);
 	let dt = pf.dt();
+	let start_state = pf.initial_state(start_pos, &header);
+
+	// Shares the search's objects rather than holding a second copy of the level
+	let viewer = Pathfinder::new(pf.shared_objects(), goal_x);
 
 	let (tx, rx) = mpsc::channel::<SearchMessage>();
 	let stop_flag = Arc::new(AtomicBool::new(false));
 	let stop_flag_thread = Arc::clone(&stop_flag);
 
-	thread::spawn(move || {
-		let mut session = pf.start_search(start_pos, goal_x);
-		let mut steps_since_update = 0;
-		let update_interval = 1000;
-
-		loop {
-			if stop_flag_thread.load(Ordering::Relaxed) {
-				let end_node = &session.all_nodes[session.best_x_index];
-				let actions = pf.reconstruct_path(&session.all_nodes, end_node);
-
-				let _ = tx.send(SearchMessage::Stopped {
-					actions,
-					best_x: session.best_x,
-					nodes_expanded: session.nodes_expanded,
-				});
-
-				break;
-			}
-
-			let mut done = false;
-			for _ in 0..100 {
-				done = pf.step_single(&mut session, goal_x);
-				steps_since_update += 1;
-
-				if done {
-					break;
-				}
-			}
-
-			if steps_since_update >= update_interval || done {
-				steps_since_update = 0;
-				if done {
-					if let Some(idx) = session.goal_reached_index {
-						let end_node = &session.all_nodes[idx];
-						let actions = pf.reconstruct_path(&session.all_nodes, end_node);
-						let _ = tx.send(SearchMessage::Done {
-							actions,
-							best_x: session.best_x,
-							nodes_expanded: session.nodes_expanded,
-						});
-					} else {
-						let _ = tx.send(SearchMessage::NoSolution {
-							best_x: session.best_x,
-							nodes_expanded: session.nodes_expanded,
-						});
-					}
-
-					break;
-				} else {
-					let mut best_path: Vec<Vec2> = Vec::new();
-					let mut idx = session.best_x_index;
-					while idx < session.all_nodes.len() {
-						best_path.push(session.all_nodes[idx].state.position);
-						if let Some(parent_idx) = session.all_nodes[idx].parent_index {
-							idx = parent_idx;
-						} else {
-							break;
-						}
-					}
-					best_path.reverse();
-
-					let _ = tx.send(SearchMessage::Progress {
-						best_path,
-						best_x: session.best_x,
-						nodes_expanded: session.nodes_expanded,
-						open_set_size: session.open_set.len(),
-					});
-				}
-			}
-		}
-	});
+	if !manual {
+		spawn_search(pf, start_state, goal_x, tx, stop_flag_thread);
+	}
 
-	let mut app = VisualizerApp::new(game_objects, goal_x, start_pos, dt, rx, stop_flag);
+	let mut app = VisualizerApp::new(viewer, goal_x, start_state, dt, rx, stop_flag);
+	if manual {
+		app.start_manual();
+	}
 
 	loop {
 		if is_key_pressed(KeyCode::Escape) || is_key_pressed(KeyCode::Q) {
@@ -191,3 +123,63 @@ pub async fn run_visualizer(level_path: std::path::PathBuf) {
 		next_frame().await;
 	}
 }
+
+fn spawn_search(
+	pf: Pathfinder, start_state: State, goal_x: f32, tx: mpsc::Sender<SearchMessage>,
+	stop_flag_thread: Arc<AtomicBool>,
+) {
+	thread::spawn(move || {
+		let mut session = pf.start_search_from(start_state, goal_x);
+		let mut final_progress = None;
+
+		for progress in pf.search_iter(&mut session, goal_x) {
+			if stop_flag_thread.load(Ordering::Relaxed) {
+				break;
+			}
+
+			if progress.is_finished() {
+				final_progress = Some(progress);
+				break;
+			}
+
+			let _ = tx.send(SearchMessage::Progress {
+				best_path: progress.best_path,
+				sampled_nodes: progress.sampled_nodes,
+				best_x: progress.best_x,
+				nodes_expanded: progress.nodes_expanded,
+				open_set_size: progress.open_set_size,
+			});
+		}
+
+		let Some(progress) = final_progress else {
+			let end_node = &session.all_nodes[session.best_x_index];
+			let actions = pf.reconstruct_path(&session.all_nodes, end_node);
+
+			let _ = tx.send(SearchMessage::Stopped {
+				actions,
+				best_x: session.best_x,
+				nodes_expanded: session.nodes_expanded,
+			});
+			return;
+		};
+
+		let stop_reason = progress.stop_reason.unwrap_or(StopReason::Exhausted);
+		// Budget and stagnation stops still play back the furthest run
+		if stop_reason != StopReason::Exhausted {
+			let end_node = &session.all_nodes[session.end_index()];
+			let actions = pf.reconstruct_path(&session.all_nodes, end_node);
+			let _ = tx.send(SearchMessage::Done {
+				actions,
+				best_x: progress.best_x,
+				nodes_expanded: progress.nodes_expanded,
+				stop_reason,
+			});
+		} else {
+			let _ = tx.send(SearchMessage::NoSolution {
+				best_x: progress.best_x,
+				nodes_expanded: progress.nodes_expanded,
+				stop_reason,
+			});
+		}
+	});
+}
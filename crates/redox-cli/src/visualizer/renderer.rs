@@ -1,14 +1,21 @@
 use macroquad::prelude::*;
 use redox_core::{
-	game_object::{GameObject, GameObjectType},
+	game_object::{GameObject, GameObjectType, RenderShape},
+	pathfinder::Pathfinder,
 	state::State,
 };
 
 use super::types::{Vec2, VisualizerState};
 
+/// Zoom limits, in screen pixels per world unit
+const MIN_PIXELS_PER_UNIT: f32 = 0.2;
+const MAX_PIXELS_PER_UNIT: f32 = 20.0;
+
 pub struct Renderer {
 	pub pixels_per_unit: f32,
 	pub camera_pos: Vec2,
+	/// Outline the exact collision shapes on top of the filled objects
+	pub show_hitboxes: bool,
 }
 
 impl Renderer {
@@ -16,6 +23,7 @@ impl Renderer {
 		Self {
 			pixels_per_unit: 3.0,
 			camera_pos: start_pos,
+			show_hitboxes: false,
 		}
 	}
 
@@ -27,43 +35,182 @@ impl Renderer {
 		(dx, dy)
 	}
 
-	pub fn draw_game_objects(&self, objects: &[GameObject], viz_state: VisualizerState) {
+	/// Inverse of `world_to_screen`
+	pub fn screen_to_world(&self, sx: f32, sy: f32) -> Vec2 {
+		let screen_center_x = screen_width() / 2.0;
+		let screen_center_y = screen_height() / 2.0;
+		Vec2::new(
+			(sx - screen_center_x) / self.pixels_per_unit + self.camera_pos.x,
+			(screen_center_y - sy) / self.pixels_per_unit + self.camera_pos.y,
+		)
+	}
+
+	/// Scales the zoom by `factor`, keeping the world point under the screen point fixed
+	pub fn zoom_at(&mut self, sx: f32, sy: f32, factor: f32) {
+		let anchor = self.screen_to_world(sx, sy);
+		self.pixels_per_unit =
+			(self.pixels_per_unit * factor).clamp(MIN_PIXELS_PER_UNIT, MAX_PIXELS_PER_UNIT);
+
+		let drifted = self.screen_to_world(sx, sy);
+		self.camera_pos += anchor - drifted;
+	}
+
+	/// Moves the camera so the world follows a drag of `(dx, dy)` screen pixels
+	pub fn pan(&mut self, dx: f32, dy: f32) {
+		self.camera_pos.x -= dx / self.pixels_per_unit;
+		self.camera_pos.y += dy / self.pixels_per_unit;
+	}
+
+	pub fn is_on_screen(&self, world: Vec2) -> bool {
+		let (sx, sy) = self.world_to_screen(world);
+		(0.0..=screen_width()).contains(&sx) && (0.0..=screen_height()).contains(&sy)
+	}
+
+	pub fn draw_game_objects(&self, pathfinder: &Pathfinder, viz_state: VisualizerState) {
 		let screen_w = screen_width();
 		let cull_distance = screen_w / self.pixels_per_unit * 0.7;
 
-		for obj in objects {
-			if (obj.position.x - self.camera_pos.x).abs() > cull_distance {
-				continue;
-			}
-
+		for obj in pathfinder.objects_in_range(
+			self.camera_pos.x - cull_distance,
+			self.camera_pos.x + cull_distance,
+		) {
 			let color = match viz_state {
 				VisualizerState::Computing => match obj.object_type {
-					GameObjectType::Solid => Color::from_rgba(40, 70, 120, 255),
-					GameObjectType::Hazard => Color::from_rgba(150, 40, 40, 255),
+					t if t.is_solid() => Color::from_rgba(40, 70, 120, 255),
+					t if t.is_hazard() => Color::from_rgba(150, 40, 40, 255),
 					GameObjectType::Unknown => continue,
 					_ => Color::from_rgba(100, 100, 100, 255),
 				},
-				VisualizerState::Playback => match obj.object_type {
-					GameObjectType::Solid => Color::from_rgba(60, 100, 180, 255),
-					GameObjectType::Hazard => Color::from_rgba(220, 60, 60, 255),
+				VisualizerState::Playback | VisualizerState::Manual => match obj.object_type {
+					t if t.is_solid() => Color::from_rgba(60, 100, 180, 255),
+					t if t.is_hazard() => Color::from_rgba(220, 60, 60, 255),
 					GameObjectType::Unknown => continue,
 					_ => Color::from_rgba(150, 150, 150, 255),
 				},
 				_ => continue,
 			};
 
-			let world_top_left = Vec2::new(
-				obj.position.x - obj.width * 0.5,
-				obj.position.y + obj.height * 0.5,
-			);
-			let (sx, sy) = self.world_to_screen(world_top_left);
-			let rect_w = obj.width * self.pixels_per_unit;
-			let rect_h = obj.height * self.pixels_per_unit;
+			self.draw_hitbox(obj, color);
 
-			draw_rectangle(sx, sy, rect_w, rect_h, color);
+			if self.show_hitboxes {
+				self.draw_hitbox_outline(obj, Color::from_rgba(255, 255, 0, 255));
+			}
 		}
 	}
 
+	/// Fills the object's collision shape, matching what `collides_info` tests
+	fn draw_hitbox(&self, obj: &GameObject, color: Color) {
+		match obj.render_shape() {
+			RenderShape::Circle { center, radius } => {
+				let (cx, cy) = self.world_to_screen(center);
+				draw_circle(cx, cy, radius * self.pixels_per_unit, color);
+			}
+			RenderShape::Rect { corners } => {
+				let [a, b, c, d] = corners.map(|corner| self.screen_vec(corner));
+				draw_triangle(a, b, c, color);
+				draw_triangle(a, c, d, color);
+			}
+			RenderShape::Capsule { a, b, radius } => {
+				let [a_screen, b_screen] = [a, b].map(|p| self.screen_vec(p));
+				let radius_px = radius * self.pixels_per_unit;
+
+				draw_circle(a_screen.x, a_screen.y, radius_px, color);
+				draw_circle(b_screen.x, b_screen.y, radius_px, color);
+				draw_line(
+					a_screen.x,
+					a_screen.y,
+					b_screen.x,
+					b_screen.y,
+					radius_px * 2.0,
+					color,
+				);
+			}
+		}
+	}
+
+	fn draw_hitbox_outline(&self, obj: &GameObject, color: Color) {
+		match obj.render_shape() {
+			RenderShape::Rect { corners } => {
+				let corners = corners.map(|corner| self.screen_vec(corner));
+				for i in 0..corners.len() {
+					let from = corners[i];
+					let to = corners[(i + 1) % corners.len()];
+					draw_line(from.x, from.y, to.x, to.y, 1.5, color);
+				}
+			}
+			RenderShape::Capsule { a, b, radius } => {
+				let radius_px = radius * self.pixels_per_unit;
+
+				// Offset the sides by the radius, perpendicular to the segment
+				let normal = (b - a).perp().normalize_or_zero() * radius;
+				for side in [normal, -normal] {
+					let from = self.screen_vec(a + side);
+					let to = self.screen_vec(b + side);
+					draw_line(from.x, from.y, to.x, to.y, 1.5, color);
+				}
+				for end in [a, b] {
+					let (cx, cy) = self.world_to_screen(end);
+					draw_circle_lines(cx, cy, radius_px, 1.5, color);
+				}
+			}
+			RenderShape::Circle { center, radius } => {
+				let (cx, cy) = self.world_to_screen(center);
+				draw_circle_lines(cx, cy, radius * self.pixels_per_unit, 1.5, color);
+			}
+		}
+	}
+
+	/// Outlines `obj` and labels it with its id, type and hitbox dimensions
+	pub fn draw_object_info(&self, obj: &GameObject) {
+		self.draw_hitbox_outline(obj, Color::from_rgba(0, 220, 255, 255));
+
+		let lines = [
+			format!("ID: {}", obj.id),
+			format!("Type: {:?}", obj.object_type),
+			format!("Size: {:.1} x {:.1}", obj.width, obj.height),
+			format!("Rotation: {:.1}", obj.rotation),
+		];
+
+		let font_size = 18.0;
+		let line_height = 20.0;
+		let padding = 8.0;
+		let box_w = lines
+			.iter()
+			.map(|line| measure_text(line, None, font_size as u16, 1.0).width)
+			.fold(0.0, f32::max)
+			+ padding * 2.0;
+		let box_h = line_height * lines.len() as f32 + padding;
+
+		// Beside the object's right edge, kept on screen
+		let (max_x, max_y) = self.world_to_screen(obj.aabb().1);
+		let bx = (max_x + 10.0).min(screen_width() - box_w).max(0.0);
+		let by = max_y.clamp(0.0, (screen_height() - box_h).max(0.0));
+
+		draw_rectangle(bx, by, box_w, box_h, Color::from_rgba(0, 0, 0, 210));
+		draw_rectangle_lines(
+			bx,
+			by,
+			box_w,
+			box_h,
+			1.5,
+			Color::from_rgba(0, 220, 255, 255),
+		);
+		for (i, line) in lines.iter().enumerate() {
+			draw_text(
+				line,
+				bx + padding,
+				by + line_height * (i + 1) as f32 - 2.0,
+				font_size,
+				WHITE,
+			);
+		}
+	}
+
+	fn screen_vec(&self, world: Vec2) -> macroquad::math::Vec2 {
+		let (x, y) = self.world_to_screen(world);
+		macroquad::math::Vec2::new(x, y)
+	}
+
 	pub fn draw_goal_line(&self, goal_x: f32) {
 		let (goal_screen_x, _) = self.world_to_screen(Vec2::new(goal_x, 0.0));
 		draw_line(
@@ -76,6 +223,21 @@ impl Renderer {
 		);
 	}
 
+	/// Faint dots for sampled search nodes, blue for low g-scores through red for high
+	pub fn draw_explored_nodes(&self, nodes: &[(Vec2, f32)]) {
+		let max_g = nodes.iter().map(|&(_, g)| g).fold(f32::EPSILON, f32::max);
+
+		for &(pos, g) in nodes {
+			if !self.is_on_screen(pos) {
+				continue;
+			}
+
+			let t = (g / max_g).clamp(0.0, 1.0);
+			let (sx, sy) = self.world_to_screen(pos);
+			draw_circle(sx, sy, 2.0, Color::new(t, 0.2, 1.0 - t, 0.35));
+		}
+	}
+
 	pub fn draw_path(&self, path: &[Vec2], color: Color) {
 		if path.len() > 1 {
 			for i in 0..path.len() - 1 {
@@ -107,6 +269,17 @@ impl Renderer {
 			Color::from_rgba(200, 100, 0, 255),
 		);
 
+		if let Some(second) = state.dual {
+			let (_, second_sy) = self.world_to_screen(Vec2::new(pos.x, second.y));
+			draw_rectangle(
+				player_sx - player_size * 0.5,
+				second_sy - player_size * 0.5,
+				player_size,
+				player_size,
+				Color::from_rgba(0, 180, 255, 255),
+			);
+		}
+
 		if state.pressing {
 			let indicator_x = player_sx + player_size * 0.5 + 10.0;
 			let indicator_y = player_sy;
@@ -1,12 +1,14 @@
-use redox_core::state::Action;
+use redox_core::{pathfinder::search::StopReason, state::Action};
 
-pub type Vec2 = ::glam::Vec2;
+pub type Vec2 = redox_core::Vec2;
 
 #[derive(PartialEq, Clone, Copy)]
 pub enum VisualizerState {
 	Computing,
 	Playback,
 	NoSolution,
+	/// The player steers with the keyboard instead of watching a solution
+	Manual,
 }
 
 /// Messages sent from the search thread to the main thread
@@ -14,15 +16,18 @@ pub enum SearchMessage {
 	/// Progress update with current best path and stats
 	Progress {
 		best_path: Vec<Vec2>,
+		/// Position and g-score of nodes generated since the last update, sparsely sampled
+		sampled_nodes: Vec<(Vec2, f32)>,
 		best_x: f32,
 		nodes_expanded: usize,
 		open_set_size: usize,
 	},
-	/// Search completed successfully
+	/// Search finished with a path, either at the goal or cut short by a limit
 	Done {
 		actions: Vec<(Action, f32)>,
 		best_x: f32,
 		nodes_expanded: usize,
+		stop_reason: StopReason,
 	},
 	/// Search stopped by user - returns partial path
 	Stopped {
@@ -31,5 +36,9 @@ pub enum SearchMessage {
 		nodes_expanded: usize,
 	},
 	/// No solution found
-	NoSolution { best_x: f32, nodes_expanded: usize },
+	NoSolution {
+		best_x: f32,
+		nodes_expanded: usize,
+		stop_reason: StopReason,
+	},
 }
@@ -0,0 +1,30 @@
+use std::{fs, process::Command};
+
+#[test]
+fn broken_config_aborts_the_batch_once() {
+	let dir = std::env::temp_dir().join(format!("redox-batch-{}", std::process::id()));
+	fs::create_dir_all(&dir).unwrap();
+	for name in ["a.txt", "b.txt"] {
+		fs::write(dir.join(name), "kA2,0;1,8,2,300,3,15;").unwrap();
+	}
+	let config = dir.join("broken.toml");
+	fs::write(&config, "[search\nmax_nodes = ").unwrap();
+	let out = dir.join("results.json");
+
+	let output = Command::new(env!("CARGO_BIN_EXE_redox-cli"))
+		.arg("batch")
+		.arg("--dir")
+		.arg(&dir)
+		.arg("--out")
+		.arg(&out)
+		.arg("--config")
+		.arg(&config)
+		.output()
+		.expect("failed to start redox-cli");
+
+	let results_written = out.exists();
+	let _ = fs::remove_dir_all(&dir);
+
+	assert!(!output.status.success());
+	assert!(!results_written, "levels were still solved");
+}
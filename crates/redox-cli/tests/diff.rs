@@ -0,0 +1,57 @@
+use std::{
+	io::Write,
+	process::{Command, Stdio},
+};
+
+use redox_core::{replay, state::Action};
+
+/// A flat level with no objects, `kA2,0;` gzipped and base64-encoded
+const FLAT_LEVEL: &str = "H4sIAAAAAAACA8t2NNIxsAYAun9aswYAAAA=";
+
+const TPS: f32 = 240.0;
+
+fn write_replay(name: &str, jump_tick: u32) -> std::path::PathBuf {
+	let dt = 1.0 / TPS;
+	let path = [
+		(Action::None, jump_tick as f32 * dt),
+		(Action::Press, dt),
+		(Action::Release, (110 - jump_tick) as f32 * dt),
+	];
+
+	let out = std::env::temp_dir().join(format!("redox-diff-{}-{name}.gdr", std::process::id()));
+	std::fs::write(&out, replay::encode_gdr(&path, TPS).unwrap()).unwrap();
+	out
+}
+
+#[test]
+fn diff_reports_where_two_replays_come_apart() {
+	let a = write_replay("a", 10);
+	let b = write_replay("b", 12);
+
+	let mut child = Command::new(env!("CARGO_BIN_EXE_redox-cli"))
+		.args(["diff", "--level", "-", "--a"])
+		.arg(&a)
+		.arg("--b")
+		.arg(&b)
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::null())
+		.spawn()
+		.expect("failed to start redox-cli");
+
+	child
+		.stdin
+		.take()
+		.unwrap()
+		.write_all(FLAT_LEVEL.as_bytes())
+		.unwrap();
+
+	let output = child.wait_with_output().unwrap();
+	let _ = std::fs::remove_file(&a);
+	let _ = std::fs::remove_file(&b);
+	assert!(output.status.success());
+
+	let stdout = String::from_utf8(output.stdout).unwrap();
+	assert!(stdout.starts_with("diverges on frame 11:"), "{stdout}");
+	assert!(stdout.contains("presses: 1 vs 1 (+0)"), "{stdout}");
+}
@@ -0,0 +1,63 @@
+use std::{
+	env, fs,
+	io::Write,
+	path::PathBuf,
+	process::{Command, Stdio},
+};
+
+/// A single spike, `kA2,0;1,8,2,300,3,15;` gzipped and base64-encoded
+const SPIKE_LEVEL: &str = "H4sIAAAAAAACA8t2NNIxsDbUsdAx0jE2MNAx1jE0tQYAj2HDORUAAAA=";
+
+const HAZARD: [u8; 4] = [220, 60, 60, 255];
+const PATH: [u8; 4] = [255, 140, 0, 255];
+
+/// Renders the spike level and loads the PNG back
+fn render(name: &str, extra_args: &[&str]) -> image::RgbaImage {
+	let out: PathBuf =
+		env::temp_dir().join(format!("redox-render-{}-{name}.png", std::process::id()));
+
+	let mut child = Command::new(env!("CARGO_BIN_EXE_redox-cli"))
+		.args(["render", "--level", "-", "--width", "600", "--out"])
+		.arg(&out)
+		.args(extra_args)
+		.stdin(Stdio::piped())
+		.stderr(Stdio::null())
+		.spawn()
+		.expect("failed to start redox-cli");
+
+	child
+		.stdin
+		.take()
+		.unwrap()
+		.write_all(SPIKE_LEVEL.as_bytes())
+		.unwrap();
+	assert!(child.wait().unwrap().success());
+
+	let image = image::open(&out)
+		.expect("output should be a PNG")
+		.to_rgba8();
+	fs::remove_file(&out).unwrap();
+	image
+}
+
+fn contains(image: &image::RgbaImage, color: [u8; 4]) -> bool {
+	image.pixels().any(|pixel| pixel.0 == color)
+}
+
+#[test]
+fn renders_hazards_and_the_path() {
+	let image = render("path", &[]);
+
+	assert_eq!(image.width(), 600);
+	assert!(image.height() > 1);
+	assert!(contains(&image, HAZARD));
+	assert!(contains(&image, PATH));
+}
+
+#[test]
+fn no_path_skips_the_search() {
+	let image = render("no-path", &["--no-path"]);
+
+	assert!(contains(&image, HAZARD));
+	assert!(!contains(&image, PATH));
+}
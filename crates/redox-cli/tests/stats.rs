@@ -0,0 +1,55 @@
+use std::{
+	io::Write,
+	process::{Command, Stdio},
+};
+
+use serde_json::Value;
+
+/// A single spike, `kA2,0;1,8,2,300,3,15;` gzipped and base64-encoded
+const SPIKE_LEVEL: &str = "H4sIAAAAAAACA8t2NNIxsDbUsdAx0jE2MNAx1jE0tQYAj2HDORUAAAA=";
+
+#[test]
+fn stats_json_describes_a_solved_level() {
+	let replay = std::env::temp_dir().join(format!("redox-stats-{}.gdr", std::process::id()));
+
+	let mut child = Command::new(env!("CARGO_BIN_EXE_redox-cli"))
+		.args(["--level", "-", "--stats-json", "-", "--output"])
+		.arg(&replay)
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::null())
+		.spawn()
+		.expect("failed to start redox-cli");
+
+	child
+		.stdin
+		.take()
+		.unwrap()
+		.write_all(SPIKE_LEVEL.as_bytes())
+		.unwrap();
+
+	let output = child.wait_with_output().unwrap();
+	let _ = std::fs::remove_file(&replay);
+	assert!(output.status.success());
+
+	let stats: Value = serde_json::from_slice(&output.stdout).expect("stdout should be JSON");
+
+	for key in [
+		"solved",
+		"goal_x",
+		"best_x",
+		"nodes_expanded",
+		"nodes_generated",
+		"open_set_peak",
+		"elapsed_ms",
+		"presses",
+		"path_frames",
+		"stop_reason",
+	] {
+		assert!(stats.get(key).is_some(), "missing {key}");
+	}
+	assert_eq!(stats["solved"], true);
+	assert_eq!(stats["stop_reason"], "goal");
+	assert_eq!(stats["presses"], 1);
+	assert!(stats["open_set_peak"].as_u64().unwrap() > 0);
+}
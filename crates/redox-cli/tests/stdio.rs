@@ -0,0 +1,39 @@
+use std::{
+	io::Write,
+	process::{Command, Stdio},
+};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use redox_core::replay::Replay;
+
+/// A single spike, `kA2,0;1,8,2,300,3,15;` gzipped and base64-encoded
+const SPIKE_LEVEL: &str = "H4sIAAAAAAACA8t2NNIxsDbUsdAx0jE2MNAx1jE0tQYAj2HDORUAAAA=";
+
+#[test]
+fn piped_level_produces_replay_on_stdout() {
+	let mut child = Command::new(env!("CARGO_BIN_EXE_redox-cli"))
+		.args(["--level", "-", "--output", "-"])
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.stderr(Stdio::null())
+		.spawn()
+		.expect("failed to start redox-cli");
+
+	child
+		.stdin
+		.take()
+		.unwrap()
+		.write_all(SPIKE_LEVEL.as_bytes())
+		.unwrap();
+
+	let output = child.wait_with_output().unwrap();
+	assert!(output.status.success());
+
+	let encoded = String::from_utf8(output.stdout).expect("stdout should be text");
+	let bytes = STANDARD
+		.decode(encoded.trim())
+		.expect("stdout should be base64");
+	let replay = Replay::deserialize(&bytes).expect("stdout should hold a GDR replay");
+
+	assert!(replay.inputs.iter().any(|input| input.down));
+}
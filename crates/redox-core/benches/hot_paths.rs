@@ -0,0 +1,144 @@
+//! Benchmarks for the code the search spends its time in, run with
+//! `cargo bench -p redox-core`
+
+use std::hint::black_box;
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use redox_core::{
+	Vec2,
+	config::Config,
+	formats::level,
+	game_object::GameObject,
+	pathfinder::Pathfinder,
+	simulation::{collision, spatial_grid::SpatialGrid},
+	state::{Action, GameMode, State},
+};
+
+/// Cube-only level of spikes, ledges, pillars and stairs, about 8000 units long
+const MEDIUM_LEVEL: &str = include_str!("levels/medium.txt");
+
+const START: Vec2 = Vec2::new(0.0, 15.0);
+const GOAL_X: f32 = 8500.0;
+
+/// Matches the cell size `Pathfinder` builds its grid with
+const GRID_CELL_SIZE: f32 = 128.0;
+
+fn objects() -> Vec<GameObject> {
+	level::parse_objects(MEDIUM_LEVEL)
+		.iter()
+		.map(GameObject::from_raw)
+		.collect()
+}
+
+fn start_state(pathfinder: &Pathfinder) -> State {
+	pathfinder.initial_state(START, &level::parse_header(MEDIUM_LEVEL))
+}
+
+/// Player positions sweeping the level along the ground and at jump height
+fn probe_positions() -> Vec<Vec2> {
+	(0..(GOAL_X / 10.0) as usize)
+		.flat_map(|i| {
+			let x = i as f32 * 10.0;
+			[Vec2::new(x, 15.0), Vec2::new(x, 60.0)]
+		})
+		.collect()
+}
+
+fn bench_collides_info(c: &mut Criterion) {
+	let objects = objects();
+	let grid = SpatialGrid::new(&objects, GRID_CELL_SIZE);
+	let params = Config::default().physics;
+
+	let pathfinder = Pathfinder::new(objects.clone(), GOAL_X);
+	let start = start_state(&pathfinder);
+	let states: Vec<State> = probe_positions()
+		.into_iter()
+		.map(|position| State { position, ..start })
+		.collect();
+
+	let mut group = c.benchmark_group("collides_info");
+	group.throughput(Throughput::Elements(states.len() as u64));
+	group.bench_function("medium_level_sweep", |b| {
+		b.iter(|| {
+			for state in &states {
+				black_box(collision::collides_info(
+					black_box(state),
+					&objects,
+					&grid,
+					&params,
+				));
+			}
+		})
+	});
+	group.finish();
+}
+
+fn bench_grid_query(c: &mut Criterion) {
+	let objects = objects();
+	let grid = SpatialGrid::new(&objects, GRID_CELL_SIZE);
+	let positions = probe_positions();
+
+	let mut group = c.benchmark_group("spatial_grid");
+	group.throughput(Throughput::Elements(positions.len() as u64));
+	group.bench_function("query_collision_window", |b| {
+		// Same window `collides_info` asks for around a 30x30 player
+		let window = 30.0 + 2.0 * GRID_CELL_SIZE;
+		b.iter(|| {
+			for &position in &positions {
+				black_box(grid.query(black_box(position), window, window).count());
+			}
+		})
+	});
+	group.finish();
+}
+
+fn bench_simulate_step(c: &mut Criterion) {
+	let pathfinder = Pathfinder::new(objects(), GOAL_X);
+	let cube = start_state(&pathfinder);
+	let ship = State {
+		mode: GameMode::Ship,
+		..cube
+	};
+
+	let mut group = c.benchmark_group("simulate_step");
+	for (name, state) in [("cube", cube), ("ship", ship)] {
+		group.bench_function(name, |b| {
+			b.iter(|| {
+				let pressed = pathfinder.simulate_step(black_box(&state), Action::Press);
+				black_box(pathfinder.simulate_step(&pressed, Action::None))
+			})
+		});
+	}
+	group.finish();
+}
+
+fn bench_solve(c: &mut Criterion) {
+	let pathfinder = Pathfinder::new(objects(), GOAL_X);
+	let start = start_state(&pathfinder);
+
+	let solve = || {
+		let mut session = pathfinder.start_search_from(start, GOAL_X);
+		pathfinder.step(&mut session, GOAL_X);
+		session
+	};
+
+	// Throughput in expanded nodes, so the report reads as nodes per second
+	let nodes_expanded = solve().nodes_expanded;
+
+	let mut group = c.benchmark_group("solve");
+	group.sample_size(10);
+	group.throughput(Throughput::Elements(nodes_expanded as u64));
+	group.bench_function("medium_level", |b| {
+		b.iter(|| black_box(solve().nodes_expanded))
+	});
+	group.finish();
+}
+
+criterion_group!(
+	benches,
+	bench_collides_info,
+	bench_grid_query,
+	bench_simulate_step,
+	bench_solve
+);
+criterion_main!(benches);
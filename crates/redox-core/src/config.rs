@@ -1,14 +1,88 @@
+use std::{collections::HashMap, time::Duration};
+
+use thiserror::Error;
+
+use crate::{
+	game_object::{GameObjectType, HitboxShape},
+	state::KeyLayout,
+};
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ConfigError {
+	#[error("{field} must be finite, got {value}")]
+	NotFinite { field: String, value: f32 },
+	#[error("{field} must be non-zero")]
+	Zero { field: String },
+	#[error("{field} must be positive, got {value}")]
+	NotPositive { field: String, value: f32 },
+	#[error("Speed index {speed} is out of range, only {count} speeds are configured")]
+	SpeedOutOfRange { speed: usize, count: usize },
+}
+
+#[derive(Clone)]
 pub struct PhysicsParams {
 	pub gravities: [f32; 5],
 	pub jump_velocities: [f32; 5],
 	pub player_speeds: [f32; 5],
 	pub player_width: f32,
 	pub player_height: f32,
+	/// Hitbox scale of a mini player, from a mini start or size portal
+	pub mini_scale: f32,
 	pub ship_velocities: [f32; 5],
+	/// Ship acceleration while holding below and above the threshold velocity, then
+	/// while falling above and below it
+	pub ship_accels: [f32; 4],
+	/// Fastest a ship climbs or falls at each speed
+	pub ship_vy_clamp: [f32; 5],
+	/// Launch velocity of a robot jump, held for as long as the button is down
+	pub robot_jump_velocity: f32,
+	/// Longest a robot jump can be extended by holding, in seconds
+	pub robot_max_hold: f32,
 	pub ship_bounds: f32,
-	pub dt: f32,
+	/// Fraction of its speed a ship keeps when it hits its floor or ceiling, bounced back
+	/// away from it. At 0 the ship stops dead and can slide along the boundary
+	pub ship_boundary_restitution: f32,
+	/// Fastest a swing player falls in either direction
+	pub swing_max_velocity: f32,
+	/// Height of the ground the player lands on, `None` for a level with nothing under
+	/// it, where the player falls until it drops out of the level
+	///
+	/// Without a ground a flying player isn't held up by the bottom of its bounds either.
+	pub ground_y: Option<f32>,
+	/// Highest a flipped player can fall to, `None` uses the top of the highest object
+	pub world_ceiling: Option<f32>,
+	/// Falling below this y kills the player, `None` puts it 100 units under the ground
+	/// or the lowest object, whichever is lower
+	pub world_floor_y: Option<f32>,
+	/// Falling above this y kills a flipped player, `None` puts it 100 units over the
+	/// flipped player's resting height or the highest object, whichever is higher
+	pub world_ceiling_y: Option<f32>,
+	/// Moving left of this x kills the player, `None` puts it 100 units before the level
+	/// start at x = 0 or the leftmost object, whichever is further left
+	pub world_start_x: Option<f32>,
+	/// Moving right of this x kills the player, `None` leaves the end open since reaching
+	/// the goal already ends the run
+	pub world_end_x: Option<f32>,
+	/// Launch velocities of yellow, pink, red and gravity pads
+	pub pad_velocities: [f32; 4],
+	/// Simulation ticks per second, also the framerate written to replays
+	pub tps: f32,
 	pub vertical_dt_scale: f32,
 	pub vy_quantize_step: f32,
+	/// Multiplier applied to the radius of round hazards like sawblades, GD kills on a
+	/// smaller area than the visual
+	///
+	/// Scales circles and capsules. Solids always keep their full size.
+	pub hazard_leniency: f32,
+	/// Multiplier applied to rectangular hazard hitboxes like spikes, in place of
+	/// `hazard_leniency`
+	///
+	/// GD only kills on roughly the middle of a spike's hitbox, so this is lower than
+	/// the round hazards' scale. Solids always keep their full size.
+	pub hazard_hitbox_scale: f32,
+	/// Treat breakable blocks as solid. Off by default, most levels use them as scenery
+	/// the player runs straight through
+	pub breakables_solid: bool,
 }
 
 impl Default for PhysicsParams {
@@ -19,39 +93,447 @@ impl Default for PhysicsParams {
 			player_speeds: [251.16008, 311.58009, 387.42014, 468.00014, 576.0002],
 			player_width: 30.0,
 			player_height: 30.0,
+			mini_scale: 0.6,
 			ship_velocities: [101.541_49, 103.485_5, 103.377_49, 103.809_49, 103.809_49],
+			ship_accels: [1397.0491, 1_117.643_3, 1341.1719, 894.114_6],
+			ship_vy_clamp: [800.0; 5],
+			robot_jump_velocity: 480.0,
+			robot_max_hold: 0.15,
 			ship_bounds: 300.0,
-			dt: 1.0 / 240.0,
+			ship_boundary_restitution: 0.0,
+			swing_max_velocity: 600.0,
+			ground_y: Some(0.0),
+			world_ceiling: None,
+			world_floor_y: None,
+			world_ceiling_y: None,
+			world_start_x: None,
+			world_end_x: None,
+			pad_velocities: [864.0, 561.6, 1080.0, 432.0],
+			tps: 240.0,
 			vertical_dt_scale: 1.0,
 			vy_quantize_step: 1000.0,
+			hazard_leniency: 0.75,
+			hazard_hitbox_scale: 0.5,
+			breakables_solid: false,
+		}
+	}
+}
+
+impl PhysicsParams {
+	/// Best-effort match to Geometry Dash 2.2
+	///
+	/// GD steps its physics in 60 Hz frames and moves the player by 0.9 of its velocity
+	/// each frame. The per-frame constants below are the ones `PlayerObject` uses in the
+	/// 2.206 decompilation under `docs/reference`, converted to units per second by
+	/// multiplying velocities by `60 * 0.9` and accelerations by its square.
+	pub fn gd_2_2() -> Self {
+		const FRAME: f32 = 60.0 * 0.9;
+
+		// Cube gravity, the same at every speed
+		let gravity = 0.958_199 * FRAME * FRAME;
+		// Cube jump, also speed independent
+		let jump = 11.180_032 * FRAME;
+		// The ship switches between its two accelerations at twice the cube gravity
+		let ship_threshold = 2.0 * 0.958_199 * FRAME;
+
+		Self {
+			gravities: [-gravity; 5],
+			jump_velocities: [jump; 5],
+			// Horizontal speed per frame of the 0.5x, 1x, 2x, 3x and 4x speed portals
+			player_speeds: [4.651_113, 5.770_002, 7.174_447, 8.666_669, 10.666_67]
+				.map(|v| v * FRAME),
+			ship_velocities: [ship_threshold; 5],
+			// Fractions of the cube gravity: holding under and over the threshold, then
+			// falling over and under it
+			ship_accels: [0.5, 0.4, 0.48, 0.32].map(|k| k * gravity),
+			..Self::default()
+		}
+	}
+
+	/// Length of one simulation tick in seconds
+	///
+	/// The search expands one node per tick, so a lower TPS also means coarser
+	/// input timing and a smaller search
+	pub fn dt(&self) -> f32 {
+		1.0 / self.tps
+	}
+
+	/// Width and height of the player's hitbox, scaled down when `mini`
+	pub fn player_size(&self, mini: bool) -> (f32, f32) {
+		let scale = if mini { self.mini_scale } else { 1.0 };
+		(self.player_width * scale, self.player_height * scale)
+	}
+
+	/// Checks every parameter the simulation divides by or indexes with
+	///
+	/// A zero or NaN in here doesn't panic, it silently turns every position into NaN
+	/// and the search expands nodes that never go anywhere
+	pub fn validate(&self) -> Result<(), ConfigError> {
+		let arrays: [(&str, &[f32], bool); 7] = [
+			("gravities", &self.gravities, true),
+			("jump_velocities", &self.jump_velocities, true),
+			("player_speeds", &self.player_speeds, true),
+			("ship_vy_clamp", &self.ship_vy_clamp, true),
+			("ship_velocities", &self.ship_velocities, false),
+			("ship_accels", &self.ship_accels, true),
+			("pad_velocities", &self.pad_velocities, false),
+		];
+
+		for (name, values, non_zero) in arrays {
+			for (i, &value) in values.iter().enumerate() {
+				let field = || format!("{name}[{i}]");
+				if !value.is_finite() {
+					return Err(ConfigError::NotFinite {
+						field: field(),
+						value,
+					});
+				}
+				if non_zero && value == 0.0 {
+					return Err(ConfigError::Zero { field: field() });
+				}
+			}
 		}
+
+		// The heuristic divides by the speed, a backwards speed never reaches the goal, and
+		// a negative clamp has its bounds the wrong way around
+		let signed = [
+			("player_speeds", &self.player_speeds),
+			("ship_vy_clamp", &self.ship_vy_clamp),
+		];
+		for (name, values) in signed {
+			for (i, &value) in values.iter().enumerate() {
+				if value < 0.0 {
+					return Err(ConfigError::NotPositive {
+						field: format!("{name}[{i}]"),
+						value,
+					});
+				}
+			}
+		}
+
+		let positive = [
+			("player_width", self.player_width),
+			("player_height", self.player_height),
+			("mini_scale", self.mini_scale),
+			("tps", self.tps),
+			("dt", self.dt()),
+			("vertical_dt_scale", self.vertical_dt_scale),
+			("vy_quantize_step", self.vy_quantize_step),
+		];
+
+		for (name, value) in positive {
+			if !value.is_finite() {
+				return Err(ConfigError::NotFinite {
+					field: name.to_string(),
+					value,
+				});
+			}
+			if value <= 0.0 {
+				return Err(ConfigError::NotPositive {
+					field: name.to_string(),
+					value,
+				});
+			}
+		}
+
+		let finite = [
+			("robot_jump_velocity", self.robot_jump_velocity),
+			("robot_max_hold", self.robot_max_hold),
+			("ship_bounds", self.ship_bounds),
+			("ship_boundary_restitution", self.ship_boundary_restitution),
+			("swing_max_velocity", self.swing_max_velocity),
+			("hazard_leniency", self.hazard_leniency),
+			("hazard_hitbox_scale", self.hazard_hitbox_scale),
+		];
+
+		let optional = [
+			("ground_y", self.ground_y),
+			("world_start_x", self.world_start_x),
+			("world_end_x", self.world_end_x),
+		];
+		let set = optional
+			.into_iter()
+			.filter_map(|(name, value)| value.map(|value| (name, value)));
+		for (name, value) in finite.into_iter().chain(set) {
+			if !value.is_finite() {
+				return Err(ConfigError::NotFinite {
+					field: name.to_string(),
+					value,
+				});
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Checks that `speed`, usually a level header's start speed, indexes every
+	/// per-speed array
+	pub fn validate_speed(&self, speed: usize) -> Result<(), ConfigError> {
+		let count = self.player_speeds.len();
+		if speed >= count {
+			return Err(ConfigError::SpeedOutOfRange { speed, count });
+		}
+
+		Ok(())
+	}
+
+	/// Whether objects of this type are stood on and crashed into like blocks
+	pub fn is_solid(&self, object_type: GameObjectType) -> bool {
+		object_type.is_solid()
+			|| (self.breakables_solid && object_type == GameObjectType::Breakable)
 	}
 }
 
+/// How the open set is managed between expansions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchStrategy {
+	/// Weighted A*, keeps every generated node
+	#[default]
+	AStar,
+	/// Best-first like A*, but the open set never holds more than `width` nodes
+	///
+	/// Whenever it grows past that, the nodes with the worst f-scores are dropped until
+	/// `width` are left. This bounds memory on long levels, but can prune the only
+	/// surviving line and fail where A* succeeds
+	Beam { width: usize },
+}
+
+/// What a path costs besides its running time, see `SearchConfig::objective`
+///
+/// The heuristic only counts time, so it never overestimates whatever presses cost, but
+/// it guides the search less the more they cost. `MinPresses` can expand many more
+/// nodes than the other objectives before reaching the goal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Objective {
+	/// Fewest ticks, presses are free
+	MinTime,
+	/// Fewest presses, then fewest ticks among paths with as many
+	MinPresses,
+	/// Each press costs `press_weight` ticks on top of the time, a 30th of that for a
+	/// ship or swing since flying takes far more presses
+	Balanced { press_weight: f32 },
+}
+
+impl Default for Objective {
+	fn default() -> Self {
+		Objective::Balanced { press_weight: 15.0 }
+	}
+}
+
+impl Objective {
+	/// Cost of one press in ticks, longer than any level for `MinPresses`
+	pub fn press_ticks(self, flying: bool) -> f32 {
+		match self {
+			Objective::MinTime => 0.0,
+			Objective::MinPresses => 100_000.0,
+			Objective::Balanced { press_weight } if flying => press_weight / 30.0,
+			Objective::Balanced { press_weight } => press_weight,
+		}
+	}
+}
+
+/// Which open-set entry is expanded first when their f-scores are equal
+///
+/// Every variant falls back to the most recently generated node, so the order is
+/// always total
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TieBreak {
+	InsertionOrder,
+	/// Furthest along first
+	#[default]
+	HigherX,
+	/// Cheapest so far first
+	LowerG,
+}
+
+/// Sliding-window stagnation check, judged on the rate best x advances rather than on
+/// a fixed interval
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProgressWindow {
+	/// Expansions the rate is measured across
+	pub nodes: usize,
+	/// How many samples of best x the window holds, checked once per sample
+	pub samples: usize,
+	/// Slowest acceptable best-x gain per 1000 expansions
+	pub min_rate: f32,
+	/// Consecutive slow samples needed before the search counts as stuck
+	pub patience: usize,
+}
+
+impl Default for ProgressWindow {
+	fn default() -> Self {
+		Self {
+			nodes: 200_000,
+			samples: 20,
+			min_rate: 0.5,
+			patience: 5,
+		}
+	}
+}
+
+/// First pass of `Pathfinder::solve_hierarchical`, a quick weighted search whose path
+/// the full search is then kept close to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoarsePass {
+	/// Multiplies `y_quant` and `vy_quant`. Merging more states speeds up the pass on
+	/// open levels, but can close off the only line through a tight section, in which
+	/// case the second pass runs unconstrained
+	pub quant_scale: f32,
+	pub heuristic_weight: f32,
+	/// Furthest the second pass may stray above or below the coarse path. Its cost
+	/// grows quickly with the band, a third of a block already leaves room for an
+	/// optimal search to retime every jump
+	pub band: f32,
+}
+
+impl Default for CoarsePass {
+	fn default() -> Self {
+		Self {
+			quant_scale: 1.0,
+			heuristic_weight: 3.0,
+			band: 10.0,
+		}
+	}
+}
+
+#[derive(Clone)]
 pub struct SearchConfig {
+	pub strategy: SearchStrategy,
 	pub heuristic_weight: f32,
+	/// Search for the fewest-frames path: weight 1.0, a heuristic that never
+	/// overestimates and no extra cost for pressing. Much slower than the default
+	/// weighted search, and only optimal up to the closed set's state quantization
+	pub optimal: bool,
+	/// What the search minimizes besides time, `optimal` always uses `Objective::MinTime`
+	pub objective: Objective,
+	pub tie_break: TieBreak,
+	/// Ticks a node has to save over the cheapest expansion of its state key to be
+	/// expanded again
+	///
+	/// The closed set keeps the lowest g each key was expanded at. A node reaching the
+	/// key more cheaply than that by over the slack is reopened, since a weighted
+	/// heuristic can pop the costlier route first. Anything else is closed, including
+	/// routes that are only cheaper by float error, which would otherwise expand every
+	/// equally cheap route to a key over again. With 0.0 any strictly cheaper node is
+	/// reopened.
+	pub reopen_slack: f32,
+	/// f-scores and tie-break keys are rounded to this many decimals before comparing,
+	/// so platform-dependent float rounding can't reorder the open set
+	pub f_score_decimals: u32,
+	/// Extra ship-mode successors that press or release and then hold for this many
+	/// ticks in a single expansion
+	pub macro_hold_frames: Vec<u32>,
+	/// A cube that is held down jumps again as soon as it lands, like holding the button
+	/// in game, so consecutive jumps don't need a release and a new press in between
+	pub allow_buffer_jump: bool,
+	/// Settings for the first pass of `Pathfinder::solve_hierarchical`
+	pub coarse_pass: CoarsePass,
+	/// Relax the heuristic weight toward 1.0 on stagnation instead of stopping right away
+	pub adaptive_weight: bool,
 	pub x_quant: f32,
 	pub y_quant: f32,
 	pub vy_quant: f32,
+	/// Bits of the closed set's state keys given to each quantized value
+	pub key_layout: KeyLayout,
+	/// Check every key for values too large for `key_layout` and warn when one aliases
+	/// other states, on by default in debug builds
+	pub check_key_overflow: bool,
 	pub stagnation_check_interval: usize,
 	pub min_progress_per_interval: f32,
+	/// Count how often each object kills a successor, see `Pathfinder::deadliest`
+	pub count_deaths: bool,
+	/// Time each phase of node expansion into `SearchSession::timings`
+	pub profile: bool,
+	/// Also stop, or relax the weight with `adaptive_weight`, when progress over a
+	/// sliding window stays too slow
+	pub progress_window: Option<ProgressWindow>,
+	/// Expansions between items yielded by `Pathfinder::search_iter`
+	pub progress_interval: usize,
+	/// Every this many generated nodes, one is included in `SearchProgress::sampled_nodes`
+	pub progress_sample_stride: usize,
+	/// Stop once this many nodes have been expanded
+	pub max_nodes: Option<usize>,
+	/// Stop once the search has been running for this long
+	pub time_budget: Option<Duration>,
+}
+
+impl SearchConfig {
+	/// Extra cost of a press in ticks under the objective in use
+	pub fn press_ticks(&self, flying: bool) -> f32 {
+		if self.optimal {
+			Objective::MinTime.press_ticks(flying)
+		} else {
+			self.objective.press_ticks(flying)
+		}
+	}
+
+	/// Weight the search starts with and returns to after progress
+	pub fn base_weight(&self) -> f32 {
+		if self.optimal {
+			1.0
+		} else {
+			self.heuristic_weight
+		}
+	}
 }
 
 impl Default for SearchConfig {
 	fn default() -> Self {
 		Self {
+			strategy: SearchStrategy::AStar,
 			heuristic_weight: 1.8,
+			optimal: false,
+			objective: Objective::default(),
+			tie_break: TieBreak::HigherX,
+			reopen_slack: 0.5,
+			f_score_decimals: 4,
+			macro_hold_frames: vec![2, 4, 8],
+			allow_buffer_jump: false,
+			coarse_pass: CoarsePass::default(),
+			adaptive_weight: false,
 			x_quant: 1.0,
 			y_quant: 1.0,
 			vy_quant: 10.0,
+			key_layout: KeyLayout::default(),
+			check_key_overflow: cfg!(debug_assertions),
 			stagnation_check_interval: 50_000_000,
 			min_progress_per_interval: 15.0,
+			count_deaths: false,
+			profile: false,
+			progress_window: None,
+			progress_interval: 1000,
+			progress_sample_stride: 500,
+			max_nodes: None,
+			time_budget: None,
 		}
 	}
 }
 
-#[derive(Default)]
+/// Type, hitbox shape and unscaled width and height to use for an object id
+pub type IdOverride = (GameObjectType, HitboxShape, f32, f32);
+
+#[derive(Default, Clone)]
 pub struct Config {
 	pub physics: PhysicsParams,
 	pub search: SearchConfig,
+	/// Classifications that replace the built-in mapping, for ids it gets wrong or
+	/// doesn't know
+	pub id_overrides: HashMap<i32, IdOverride>,
+	/// Leave out objects the simulation never reads when building a `Pathfinder`, which
+	/// keeps the spatial grid and object scans small on heavily decorated levels
+	pub drop_decoration: bool,
+	/// With `drop_decoration`, also leave out blocks and hazards on this z layer or any
+	/// further back. GD collides with them wherever they're drawn, so this only suits
+	/// levels that keep their background layers free of anything the player can touch
+	pub background_z_layer: Option<i32>,
+}
+
+impl Config {
+	/// Default search settings over `PhysicsParams::gd_2_2`
+	pub fn gd_accurate() -> Self {
+		Self {
+			physics: PhysicsParams::gd_2_2(),
+			..Self::default()
+		}
+	}
 }
@@ -1,8 +1,21 @@
-use std::io::Read;
+use std::{collections::HashMap, fmt, io::Read};
 
-use base64::{Engine as _, engine::general_purpose::URL_SAFE};
+use base64::{
+	Engine as _,
+	engine::general_purpose::{STANDARD, URL_SAFE},
+};
 use flate2::read::GzDecoder;
 use thiserror::Error;
+use tracing::warn;
+
+use crate::{
+	config::IdOverride,
+	game_object::{GameObject, GameObjectType},
+	state::GameMode,
+};
+
+/// Speed portals, all classified as `GameObjectType::Special`
+const SPEED_PORTAL_IDS: [i32; 5] = [200, 201, 202, 203, 1334];
 
 #[derive(Error, Debug)]
 pub enum ParserError {
@@ -14,63 +27,334 @@ pub enum ParserError {
 	GzipError(#[from] std::io::Error),
 	#[error("Malformed level string")]
 	MalformedLevel,
+	#[error("Missing k4 level data in .gmd file")]
+	MissingGmdLevel,
 }
 
+/// Gzipped level data always starts with this once base64-encoded
+const GZIP_BASE64_MAGIC: &[u8; 4] = b"H4sI";
+
+/// Decodes an encoded level string into its `;`-separated object data
+///
+/// Accepts the bare gzip base64 payload as well as the wrappers tools commonly save it
+/// in: a `k4` plist entry, a server response with the payload under one of its keys,
+/// and robtop's single-byte XOR cipher over any of these.
 pub fn parse_level_data(encoded_data: &str) -> Result<String, ParserError> {
-	let cleaned: String = encoded_data.chars().filter(|c| !c.is_whitespace()).collect();
+	let cleaned: String = encoded_data
+		.chars()
+		.filter(|c| !c.is_whitespace())
+		.collect();
 
 	if cleaned.is_empty() {
 		return Err(ParserError::MissingData);
 	}
 
-	let clean_data = if let Some(idx) = cleaned.find("H4sI") {
-		&cleaned[idx..]
-	} else {
-		&cleaned[..]
+	let unxored = maybe_xor(cleaned.into_bytes());
+	let payload = strip_wrapper(&unxored);
+	gunzip(&base64_decode(payload)?)
+}
+
+/// Undoes a single-byte XOR over the data when it hides the gzip magic
+///
+/// Save files use key 11, which turns the magic into `C?xB`, but the key is worked out
+/// from the data so other exports decode too.
+fn maybe_xor(mut data: Vec<u8>) -> Vec<u8> {
+	if data
+		.windows(GZIP_BASE64_MAGIC.len())
+		.any(|window| window == GZIP_BASE64_MAGIC)
+	{
+		return data;
+	}
+
+	let found = data.windows(GZIP_BASE64_MAGIC.len()).position(|window| {
+		let key = window[0] ^ GZIP_BASE64_MAGIC[0];
+		key != 0
+			&& window
+				.iter()
+				.zip(GZIP_BASE64_MAGIC)
+				.all(|(byte, magic)| byte ^ key == *magic)
+	});
+
+	if let Some(start) = found {
+		let key = data[start] ^ GZIP_BASE64_MAGIC[0];
+		for byte in &mut data[start..] {
+			*byte ^= key;
+		}
+	}
+
+	data
+}
+
+/// The base64 payload inside whatever wraps it, everything from the gzip magic up to
+/// the first byte that can't be base64
+fn strip_wrapper(data: &[u8]) -> &[u8] {
+	let Some(start) = data
+		.windows(GZIP_BASE64_MAGIC.len())
+		.position(|window| window == GZIP_BASE64_MAGIC)
+	else {
+		return data;
 	};
 
-	let decoded_bytes = URL_SAFE.decode(clean_data)?;
+	let payload = &data[start..];
+	let is_base64 = |byte: &u8| byte.is_ascii_alphanumeric() || b"+/-_=".contains(byte);
+	let len = payload
+		.iter()
+		.position(|byte| !is_base64(byte))
+		.unwrap_or(payload.len());
+
+	&payload[..len]
+}
+
+fn base64_decode(payload: &[u8]) -> Result<Vec<u8>, ParserError> {
+	// Save files use the URL-safe alphabet, but other tools emit standard base64
+	match URL_SAFE.decode(payload) {
+		Ok(bytes) => Ok(bytes),
+		Err(url_safe_err) => Ok(STANDARD.decode(payload).map_err(|_| url_safe_err)?),
+	}
+}
 
-	let mut decoder = GzDecoder::new(&decoded_bytes[..]);
+fn gunzip(bytes: &[u8]) -> Result<String, ParserError> {
+	let mut decoder = GzDecoder::new(bytes);
 	let mut s = String::new();
 	decoder.read_to_string(&mut s)?;
 
 	Ok(s)
 }
 
+/// Extracts the level from an editor `.gmd` export, a plist wrapping the encoded
+/// level string under the `k4` key
+pub fn parse_gmd(xml: &str) -> Result<String, ParserError> {
+	let key_end = xml
+		.find("<k>k4</k>")
+		.map(|idx| idx + "<k>k4</k>".len())
+		.ok_or(ParserError::MissingGmdLevel)?;
+
+	let rest = &xml[key_end..];
+	let value_start = rest
+		.find("<s>")
+		.map(|idx| idx + "<s>".len())
+		.ok_or(ParserError::MissingGmdLevel)?;
+	let value_len = rest[value_start..]
+		.find("</s>")
+		.ok_or(ParserError::MissingGmdLevel)?;
+
+	let value = rest[value_start..value_start + value_len].trim();
+
+	// Some tools store the level string already decoded
+	if !value.starts_with("H4sI") && value.contains(';') {
+		return Ok(value.to_string());
+	}
+
+	parse_level_data(value)
+}
+
 #[derive(Debug)]
 pub struct RawObject {
 	pub properties: Vec<(String, String)>,
 }
 
-pub fn parse_objects(level_string: &str) -> Vec<RawObject> {
-	let mut objects = Vec::new();
+/// Start settings stored in the level's header segment
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelHeader {
+	pub game_mode: GameMode,
+	/// Speed index (0=0.5x, 1=1x, 2=2x, 3=3x, 4=4x)
+	pub speed: usize,
+	pub mini: bool,
+	pub gravity_flipped: bool,
+}
 
-	for object_str in level_string.split(';') {
-		if object_str.trim().is_empty() {
-			continue;
+impl Default for LevelHeader {
+	fn default() -> Self {
+		Self {
+			game_mode: GameMode::Cube,
+			speed: 1,
+			mini: false,
+			gravity_flipped: false,
 		}
+	}
+}
+
+/// The header is the first `;`-separated segment and uses `kXX` keys instead of numeric ones
+fn is_header_segment(segment: &str) -> bool {
+	segment.trim_start().starts_with('k')
+}
 
-		let mut properties = Vec::new();
-		let tokens: Vec<&str> = object_str.split(',').collect();
+pub fn parse_header(level_string: &str) -> LevelHeader {
+	let mut header = LevelHeader::default();
+
+	let Some(segment) = level_string.split(';').next() else {
+		return header;
+	};
+
+	if !is_header_segment(segment) {
+		return header;
+	}
 
-		let mut i = 0;
-		while i < tokens.len() {
-			if i + 1 >= tokens.len() {
-				break;
+	let tokens: Vec<&str> = segment.split(',').collect();
+	for pair in tokens.chunks_exact(2) {
+		let (key, val) = (pair[0], pair[1]);
+
+		match key {
+			"kA2" => {
+				header.game_mode = match val {
+					"0" => GameMode::Cube,
+					"1" => GameMode::Ship,
+					"5" => GameMode::Robot,
+					"6" => GameMode::Spider,
+					"7" => GameMode::Swing,
+					other => {
+						warn!("Unsupported start gamemode {}, falling back to cube", other);
+						GameMode::Cube
+					}
+				}
+			}
+			"kA3" => header.mini = val == "1",
+			// The header orders speeds as 1x, 0.5x, 2x, 3x, 4x
+			"kA4" => {
+				header.speed = match val {
+					"1" => 0,
+					"2" => 2,
+					"3" => 3,
+					"4" => 4,
+					_ => 1,
+				}
 			}
+			"kA11" => header.gravity_flipped = val == "1",
+			_ => {}
+		}
+	}
+
+	header
+}
+
+pub fn parse_objects(level_string: &str) -> Vec<RawObject> {
+	level_string.split(';').filter_map(parse_object).collect()
+}
+
+/// Parses every object in the level and converts it with `id_overrides` applied, in
+/// level order
+pub fn parse_game_objects(
+	level_string: &str, id_overrides: &HashMap<i32, IdOverride>,
+) -> Vec<GameObject> {
+	level_string
+		.split(';')
+		.filter_map(parse_object)
+		.map(|raw| GameObject::from_raw_with_overrides(&raw, id_overrides))
+		.collect()
+}
+
+/// `parse_game_objects` spread across rayon's thread pool, worth it for levels with
+/// tens of thousands of objects
+///
+/// Gives the same objects in the same order as the sequential version.
+#[cfg(feature = "rayon")]
+pub fn par_parse_game_objects(
+	level_string: &str, id_overrides: &HashMap<i32, IdOverride>,
+) -> Vec<GameObject> {
+	use rayon::prelude::*;
+
+	let segments: Vec<&str> = level_string.split(';').collect();
+	segments
+		.into_par_iter()
+		.filter_map(parse_object)
+		.map(|raw| GameObject::from_raw_with_overrides(&raw, id_overrides))
+		.collect()
+}
+
+/// One `;`-separated segment as key-value pairs, `None` for the header and empty ones
+fn parse_object(object_str: &str) -> Option<RawObject> {
+	if object_str.trim().is_empty() || is_header_segment(object_str) {
+		return None;
+	}
+
+	let tokens: Vec<&str> = object_str.split(',').collect();
+	let properties: Vec<(String, String)> = tokens
+		.chunks_exact(2)
+		.map(|pair| (pair[0].to_string(), pair[1].to_string()))
+		.collect();
 
-			let key = tokens[i];
-			let val = tokens[i + 1];
+	(!properties.is_empty()).then_some(RawObject { properties })
+}
+
+/// Overview of a parsed level, from `summarize`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LevelStats {
+	pub object_count: usize,
+	pub type_counts: HashMap<GameObjectType, usize>,
+	/// Leftmost and rightmost hitbox edges, `None` for an empty level
+	pub x_range: Option<(f32, f32)>,
+	/// Lowest and highest hitbox edges, `None` for an empty level
+	pub y_range: Option<(f32, f32)>,
+	pub ship_portals: usize,
+	/// Normal, inverse and toggle gravity portals together
+	pub gravity_portals: usize,
+	pub speed_portals: usize,
+	/// No dual portal anywhere in the level
+	pub single_player: bool,
+}
+
+impl LevelStats {
+	/// Objects of type `object_type`
+	pub fn count(&self, object_type: GameObjectType) -> usize {
+		self.type_counts.get(&object_type).copied().unwrap_or(0)
+	}
+
+	pub fn max_x(&self) -> f32 {
+		self.x_range.map_or(0.0, |(_, max)| max)
+	}
+}
+
+impl fmt::Display for LevelStats {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(
+			f,
+			"Level: {} objects, {} ship sections, {} gravity portals, {} speed changes, \
+			 max_x={:.0}",
+			self.object_count,
+			self.ship_portals,
+			self.gravity_portals,
+			self.speed_portals,
+			self.max_x()
+		)?;
 
-			properties.push((key.to_string(), val.to_string()));
-			i += 2;
+		if !self.single_player {
+			f.write_str(", dual")?;
 		}
 
-		if !properties.is_empty() {
-			objects.push(RawObject { properties });
+		Ok(())
+	}
+}
+
+/// Counts what a level is made of, cheap enough to run before every search
+pub fn summarize(objects: &[GameObject]) -> LevelStats {
+	let mut stats = LevelStats {
+		object_count: objects.len(),
+		..Default::default()
+	};
+
+	for obj in objects {
+		*stats.type_counts.entry(obj.object_type).or_default() += 1;
+
+		let (min, max) = obj.aabb();
+		stats.x_range = Some(match stats.x_range {
+			Some((lo, hi)) => (lo.min(min.x), hi.max(max.x)),
+			None => (min.x, max.x),
+		});
+		stats.y_range = Some(match stats.y_range {
+			Some((lo, hi)) => (lo.min(min.y), hi.max(max.y)),
+			None => (min.y, max.y),
+		});
+
+		if SPEED_PORTAL_IDS.contains(&obj.id) {
+			stats.speed_portals += 1;
 		}
 	}
 
-	objects
+	stats.ship_portals = stats.count(GameObjectType::ShipPortal);
+	stats.gravity_portals = stats.count(GameObjectType::NormalGravityPortal)
+		+ stats.count(GameObjectType::InverseGravityPortal)
+		+ stats.count(GameObjectType::GravityTogglePortal);
+	stats.single_player = stats.count(GameObjectType::DualPortal) == 0;
+
+	stats
 }
@@ -0,0 +1,49 @@
+use std::{fs, path::Path};
+
+use anyhow::Result;
+
+use crate::state::Action;
+
+/// Expands merged actions into the absolute frames where the press state changes.
+///
+/// Each entry occupies `round(duration * tps)` frames, the same rounding `save_gdr`
+/// uses, so long `None` runs advance the frame counter by their full length.
+pub fn to_click_frames(path: &[(Action, f32)], tps: f32) -> Vec<(u32, bool)> {
+	let mut clicks = Vec::new();
+	let mut frame = 0u32;
+	let mut pressing = false;
+
+	for &(action, duration) in path {
+		match action {
+			Action::Press if !pressing => {
+				pressing = true;
+				clicks.push((frame, true));
+			}
+			Action::Release if pressing => {
+				pressing = false;
+				clicks.push((frame, false));
+			}
+			_ => {}
+		}
+
+		frame += (duration * tps).round() as u32;
+	}
+
+	clicks
+}
+
+/// Writes one `<frame> <1|0>` line per press state change
+pub fn save_clicks(path: &[(Action, f32)], out_path: impl AsRef<Path>, tps: f32) -> Result<()> {
+	fs::write(out_path, encode_clicks(path, tps))?;
+	Ok(())
+}
+
+/// The `<frame> <1|0>` lines `save_clicks` would write
+pub fn encode_clicks(path: &[(Action, f32)], tps: f32) -> String {
+	let mut text = String::new();
+	for (frame, down) in to_click_frames(path, tps) {
+		text.push_str(&format!("{} {}\n", frame, down as u8));
+	}
+
+	text
+}
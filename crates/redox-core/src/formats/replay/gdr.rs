@@ -1,6 +1,7 @@
 use std::{
-	fs::File,
+	fs::{self, File},
 	io::{self, Write},
+	path::Path,
 };
 
 use anyhow::Result;
@@ -181,9 +182,175 @@ impl Replay {
 
 		Ok(buf)
 	}
+
+	/// Parses a replay in the layout produced by `serialize`
+	pub fn deserialize(data: &[u8]) -> io::Result<Self> {
+		let mut r = Reader { data, pos: 0 };
+
+		if r.take(3)? != b"GDR" {
+			return Err(invalid_data("missing GDR magic"));
+		}
+
+		let version = r.varint()? as i32;
+		let input_tag = r.string()?;
+
+		let author = r.string()?;
+		let description = r.string()?;
+
+		let duration = r.f32_be()?;
+
+		let game_version = r.varint()? as i32;
+		let framerate = r.f64_be()?;
+
+		let seed = r.varint()? as i32;
+		let coins = r.varint()? as i32;
+		let ldm = r.byte()? != 0;
+		let platformer = r.byte()? != 0;
+
+		let bot_info = Bot {
+			name: r.string()?,
+			version: r.varint()? as i32,
+		};
+
+		let level_info = Level {
+			id: r.varint()? as u32,
+			name: r.string()?,
+		};
+
+		let extension = r.extension()?;
+
+		let death_count = r.varint()?;
+		let mut deaths = Vec::new();
+		let mut p = 0u64;
+		for _ in 0..death_count {
+			p = p
+				.checked_add(r.varint()?)
+				.ok_or_else(|| invalid_data("death frame overflows"))?;
+			deaths.push(p);
+		}
+
+		let input_count = r.varint()?;
+		let p1_count = r.varint()?;
+
+		let mut inputs = Vec::new();
+		let mut prev = 0u64;
+		for i in 0..input_count {
+			let player2 = i >= p1_count;
+			if i == p1_count {
+				prev = 0;
+			}
+
+			let packed = r.varint()?;
+			let (delta, button, down) = if platformer {
+				unpack_platformer(packed)
+			} else {
+				unpack_non_platformer(packed)
+			};
+
+			let extension = if input_tag.is_empty() {
+				None
+			} else {
+				r.extension()?
+			};
+
+			let frame = prev
+				.checked_add(delta)
+				.ok_or_else(|| invalid_data("input frame overflows"))?;
+			inputs.push(Input {
+				frame,
+				button,
+				player2,
+				down,
+				extension,
+			});
+
+			prev = frame;
+		}
+
+		Ok(Self {
+			version,
+			input_tag,
+			author,
+			description,
+			duration,
+			game_version,
+			framerate,
+			seed,
+			coins,
+			ldm,
+			platformer,
+			bot_info,
+			level_info,
+			extension,
+			deaths,
+			inputs,
+		})
+	}
+
+	/// Converts player 1's inputs back into frame-timed actions in the merged form
+	/// produced by `Pathfinder::reconstruct_path`
+	pub fn to_actions(&self) -> Vec<(Action, f32)> {
+		let dt = (1.0 / self.framerate) as f32;
+
+		let mut inputs: Vec<&Input> = self.inputs.iter().filter(|i| !i.player2).collect();
+		inputs.sort_by_key(|i| i.frame);
+
+		let mut actions: Vec<(Action, f32)> = Vec::new();
+		let mut push = |action: Action, frames: u64| {
+			if frames == 0 {
+				return;
+			}
+
+			let duration = frames as f32 * dt;
+			match (action, actions.last_mut()) {
+				(Action::None, Some((Action::None, existing))) => *existing += duration,
+				_ => actions.push((action, duration)),
+			}
+		};
+
+		let mut pressing = false;
+		let mut cursor = 0u64;
+		let mut idx = 0;
+		while idx < inputs.len() {
+			let frame = inputs[idx].frame;
+
+			// Several inputs can land on the same frame, the last one wins
+			while idx + 1 < inputs.len() && inputs[idx + 1].frame == frame {
+				idx += 1;
+			}
+			let down = inputs[idx].down;
+			idx += 1;
+
+			push(Action::None, frame.saturating_sub(cursor));
+
+			let action = match (down, pressing) {
+				(true, false) => Action::Press,
+				(false, true) => Action::Release,
+				_ => Action::None,
+			};
+			push(action, 1);
+
+			pressing = down;
+			cursor = frame + 1;
+		}
+
+		let total_frames = (self.duration as f64 * self.framerate).round() as u64;
+		push(Action::None, total_frames.saturating_sub(cursor));
+
+		actions
+	}
 }
 
-pub fn save_gdr(path: &Vec<(Action, f32)>, out_path: &str, fps: f32) -> Result<()> {
+pub fn save_gdr(path: &[(Action, f32)], out_path: &str, fps: f32) -> Result<()> {
+	let data = encode_gdr(path, fps)?;
+	let mut file = File::create(out_path)?;
+	file.write_all(&data)?;
+
+	Ok(())
+}
+
+/// Serializes frame-timed actions into the bytes `save_gdr` would write
+pub fn encode_gdr(path: &[(Action, f32)], fps: f32) -> Result<Vec<u8>> {
 	let mut replay = Replay {
 		framerate: fps as f64,
 		..Default::default()
@@ -230,11 +397,17 @@ pub fn save_gdr(path: &Vec<(Action, f32)>, out_path: &str, fps: f32) -> Result<(
 		current_frame += frames;
 	}
 
-	let data = replay.serialize()?;
-	let mut file = File::create(out_path)?;
-	file.write_all(&data)?;
+	replay.duration = current_frame as f32 / fps;
 
-	Ok(())
+	Ok(replay.serialize()?)
+}
+
+/// Loads a `.gdr` file and returns its inputs as frame-timed actions
+pub fn load_gdr(path: impl AsRef<Path>) -> Result<Vec<(Action, f32)>> {
+	let data = fs::read(path)?;
+	let replay = Replay::deserialize(&data)?;
+
+	Ok(replay.to_actions())
 }
 
 fn write_varint(buf: &mut Vec<u8>, mut v: u64) {
@@ -275,3 +448,76 @@ fn pack_platformer(delta: u64, button: u8, down: bool) -> u64 {
 fn pack_non_platformer(delta: u64, down: bool) -> u64 {
 	(delta << 1) | (down as u64)
 }
+
+fn unpack_platformer(packed: u64) -> (u64, u8, bool) {
+	(packed >> 3, ((packed >> 1) & 0x3) as u8, packed & 1 != 0)
+}
+
+fn unpack_non_platformer(packed: u64) -> (u64, u8, bool) {
+	(packed >> 1, 1, packed & 1 != 0)
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+struct Reader<'a> {
+	data: &'a [u8],
+	pos: usize,
+}
+
+impl<'a> Reader<'a> {
+	fn take(&mut self, len: usize) -> io::Result<&'a [u8]> {
+		let end = self
+			.pos
+			.checked_add(len)
+			.filter(|&end| end <= self.data.len())
+			.ok_or_else(|| invalid_data("unexpected end of replay data"))?;
+
+		let bytes = &self.data[self.pos..end];
+		self.pos = end;
+		Ok(bytes)
+	}
+
+	fn byte(&mut self) -> io::Result<u8> {
+		Ok(self.take(1)?[0])
+	}
+
+	fn varint(&mut self) -> io::Result<u64> {
+		let mut value = 0u64;
+		for shift in (0..64).step_by(7) {
+			let byte = self.byte()?;
+			value |= ((byte & 0x7F) as u64) << shift;
+			if byte & 0x80 == 0 {
+				return Ok(value);
+			}
+		}
+
+		Err(invalid_data("varint is too long"))
+	}
+
+	fn string(&mut self) -> io::Result<String> {
+		let len = self.varint()? as usize;
+		let bytes = self.take(len)?;
+		String::from_utf8(bytes.to_vec()).map_err(|_| invalid_data("string is not valid UTF-8"))
+	}
+
+	fn f32_be(&mut self) -> io::Result<f32> {
+		let bytes = self.take(4)?;
+		Ok(f32::from_be_bytes(bytes.try_into().unwrap()))
+	}
+
+	fn f64_be(&mut self) -> io::Result<f64> {
+		let bytes = self.take(8)?;
+		Ok(f64::from_be_bytes(bytes.try_into().unwrap()))
+	}
+
+	fn extension(&mut self) -> io::Result<Option<Vec<u8>>> {
+		let len = self.varint()? as usize;
+		if len == 0 {
+			return Ok(None);
+		}
+
+		Ok(Some(self.take(len)?.to_vec()))
+	}
+}
@@ -0,0 +1,65 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::{
+	clicks::to_click_frames,
+	gdr::{Input, Replay},
+};
+use crate::state::Action;
+
+/// Press state changes as JSON, with enough timing to play them back
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonReplay {
+	pub tps: f32,
+	/// Length of the whole replay, which can run past the last input
+	pub frames: u32,
+	pub inputs: Vec<JsonInput>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct JsonInput {
+	pub frame: u32,
+	pub down: bool,
+}
+
+/// Serializes frame-timed actions into pretty-printed `JsonReplay` JSON
+pub fn encode_json(path: &[(Action, f32)], tps: f32) -> Result<Vec<u8>> {
+	let replay = JsonReplay {
+		tps,
+		frames: path
+			.iter()
+			.map(|(_, duration)| (duration * tps).round() as u32)
+			.sum(),
+		inputs: to_click_frames(path, tps)
+			.into_iter()
+			.map(|(frame, down)| JsonInput { frame, down })
+			.collect(),
+	};
+
+	Ok(serde_json::to_vec_pretty(&replay)?)
+}
+
+/// Parses `encode_json` output back into merged frame-timed actions
+pub fn decode_json(data: &[u8]) -> Result<Vec<(Action, f32)>> {
+	let replay: JsonReplay = serde_json::from_slice(data)?;
+
+	// Same playback rules as a GDR replay, so both decode alike
+	let replay = Replay {
+		framerate: replay.tps as f64,
+		duration: replay.frames as f32 / replay.tps,
+		inputs: replay
+			.inputs
+			.into_iter()
+			.map(|input| Input {
+				frame: input.frame as u64,
+				button: 1,
+				player2: false,
+				down: input.down,
+				extension: None,
+			})
+			.collect(),
+		..Default::default()
+	};
+
+	Ok(replay.to_actions())
+}
@@ -1,3 +1,53 @@
+pub mod clicks;
 pub mod gdr;
+pub mod json;
 
-pub use gdr::{Bot, Input, Level, Replay, save_gdr};
+use anyhow::Result;
+pub use clicks::{encode_clicks, save_clicks, to_click_frames};
+pub use gdr::{Bot, Input, Level, Replay, encode_gdr, load_gdr, save_gdr};
+pub use json::{JsonInput, JsonReplay, decode_json, encode_json};
+
+use crate::state::Action;
+
+/// A file format a solved path can be written out in
+///
+/// Implement it to plug in another bot's format wherever a `&dyn ReplayFormat` is taken.
+pub trait ReplayFormat {
+	fn encode(&self, path: &[(Action, f32)], tps: f32) -> Result<Vec<u8>>;
+
+	/// Whether the output is binary, and needs encoding to pass through a text stream
+	fn is_binary(&self) -> bool {
+		false
+	}
+}
+
+/// GDReplayFormat, see `encode_gdr`
+pub struct Gdr;
+
+/// Plain `<frame> <1|0>` lines, see `encode_clicks`
+pub struct Clicks;
+
+/// `JsonReplay` JSON, see `encode_json`
+pub struct Json;
+
+impl ReplayFormat for Gdr {
+	fn encode(&self, path: &[(Action, f32)], tps: f32) -> Result<Vec<u8>> {
+		encode_gdr(path, tps)
+	}
+
+	fn is_binary(&self) -> bool {
+		true
+	}
+}
+
+impl ReplayFormat for Clicks {
+	fn encode(&self, path: &[(Action, f32)], tps: f32) -> Result<Vec<u8>> {
+		Ok(encode_clicks(path, tps).into_bytes())
+	}
+}
+
+impl ReplayFormat for Json {
+	fn encode(&self, path: &[(Action, f32)], tps: f32) -> Result<Vec<u8>> {
+		encode_json(path, tps)
+	}
+}
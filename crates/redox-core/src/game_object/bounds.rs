@@ -0,0 +1,107 @@
+use super::types::{GameObject, HitboxShape, RenderShape};
+use crate::vec2::Vec2;
+
+impl GameObject {
+	/// Axis-aligned bounds as `(min, max)` corners, covering the whole rotated hitbox
+	pub fn aabb(&self) -> (Vec2, Vec2) {
+		self.bounds
+	}
+
+	/// Works out `aabb` from the hitbox, once when the object is built
+	pub(super) fn compute_aabb(&self) -> (Vec2, Vec2) {
+		match &self.obb {
+			Some(obb) => {
+				let mut min = obb.corners[0];
+				let mut max = obb.corners[0];
+				for corner in &obb.corners[1..] {
+					min = min.min(*corner);
+					max = max.max(*corner);
+				}
+				(min, max)
+			}
+			None if let Some((a, b, radius)) = self.capsule_segment() => (
+				a.min(b) - Vec2::splat(radius),
+				a.max(b) + Vec2::splat(radius),
+			),
+			None => {
+				// Circles and anything without an OBB
+				let half = Vec2::splat(self.width * 0.5);
+				(self.position - half, self.position + half)
+			}
+		}
+	}
+
+	/// Whether `p` lies inside the object's hitbox
+	pub fn contains_point(&self, p: Vec2) -> bool {
+		if let Some((a, b, radius)) = self.capsule_segment() {
+			return closest_on_segment(p, a, b).distance_squared(p) <= radius * radius;
+		}
+
+		match (self.hitbox_shape, &self.obb) {
+			(HitboxShape::Rectangle, Some(obb)) => {
+				let offset = p - obb.center;
+				let half_extents = [self.width * 0.5, self.height * 0.5];
+
+				obb.axes
+					.iter()
+					.zip(half_extents)
+					.all(|(axis, half)| offset.dot(*axis).abs() <= half)
+			}
+			_ => p.distance_squared(self.position) <= (self.width * 0.5).powi(2),
+		}
+	}
+
+	/// The hitbox as a shape a renderer can draw without knowing how hitboxes are stored
+	pub fn render_shape(&self) -> RenderShape {
+		if let Some((a, b, radius)) = self.capsule_segment() {
+			return RenderShape::Capsule { a, b, radius };
+		}
+
+		match (self.hitbox_shape, &self.obb) {
+			(HitboxShape::Rectangle, Some(obb)) => RenderShape::Rect {
+				corners: obb.corners,
+			},
+			(HitboxShape::Rectangle, None) => {
+				let half = Vec2::new(self.width * 0.5, self.height * 0.5);
+				RenderShape::Rect {
+					corners: [
+						self.position - half,
+						self.position + Vec2::new(half.x, -half.y),
+						self.position + half,
+						self.position + Vec2::new(-half.x, half.y),
+					],
+				}
+			}
+			_ => RenderShape::Circle {
+				center: self.position,
+				radius: self.width * 0.5,
+			},
+		}
+	}
+
+	/// Endpoints and radius of a capsule hitbox, `None` for other shapes
+	pub fn capsule_segment(&self) -> Option<(Vec2, Vec2, f32)> {
+		if self.hitbox_shape != HitboxShape::Capsule {
+			return None;
+		}
+
+		let radius = self.height * 0.5;
+		let half_length = (self.width * 0.5 - radius).max(0.0);
+
+		let rotation = self.rotation.to_radians();
+		let axis = Vec2::new(rotation.cos(), rotation.sin()) * half_length;
+
+		Some((self.position - axis, self.position + axis, radius))
+	}
+}
+
+/// The point on segment `ab` nearest to `p`
+pub fn closest_on_segment(p: Vec2, a: Vec2, b: Vec2) -> Vec2 {
+	let ab = b - a;
+	let len_sq = ab.length_squared();
+	if len_sq <= f32::EPSILON {
+		return a;
+	}
+
+	a + ab * ((p - a).dot(ab) / len_sq).clamp(0.0, 1.0)
+}
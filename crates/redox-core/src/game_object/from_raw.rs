@@ -1,14 +1,20 @@
-use glam::Vec2;
+use std::collections::HashMap;
 
 use super::{
 	mapping::{get_hitbox_for_id, get_object_type_for_id},
 	obb::OBB2D,
-	types::{GameObject, HitboxShape},
+	slope::SlopeOrientation,
+	types::{GameObject, GameObjectType, HitboxShape},
 };
-use crate::formats::level::RawObject;
+use crate::{config::IdOverride, formats::level::RawObject, vec2::Vec2};
 
 impl GameObject {
 	pub fn from_raw(raw: &RawObject) -> Self {
+		Self::from_raw_with_overrides(raw, &HashMap::new())
+	}
+
+	/// Like `from_raw`, but ids in `overrides` skip the built-in type and hitbox mapping
+	pub fn from_raw_with_overrides(raw: &RawObject, overrides: &HashMap<i32, IdOverride>) -> Self {
 		let mut id = 0;
 
 		let mut x = 0.0;
@@ -23,6 +29,13 @@ impl GameObject {
 		let mut flip_x = false;
 		let mut flip_y = false;
 
+		// The editor only stores the vertical distance to the orange exit portal
+		let mut teleport_dy = 100.0_f32;
+
+		let mut z_layer = None;
+		let mut z_order = None;
+		let mut editor_layers = [None; 2];
+
 		for (key, val) in &raw.properties {
 			match key.as_str() {
 				"1" => id = val.parse().unwrap_or(0),
@@ -34,6 +47,11 @@ impl GameObject {
 				"32" => scale = val.parse().unwrap_or(1.0),
 				"128" => scale_x = val.parse().unwrap_or(1.0),
 				"129" => scale_y = val.parse().unwrap_or(1.0),
+				"54" => teleport_dy = val.parse().unwrap_or(100.0),
+				"24" => z_layer = val.parse().ok(),
+				"25" => z_order = val.parse().ok(),
+				"20" => editor_layers[0] = val.parse().ok(),
+				"61" => editor_layers[1] = val.parse().ok(),
 				_ => {}
 			}
 		}
@@ -41,12 +59,14 @@ impl GameObject {
 		scale_x *= scale;
 		scale_y *= scale;
 
-		let (hitbox_shape, base_width, base_height) = get_hitbox_for_id(id);
+		let (object_type, (hitbox_shape, base_width, base_height)) = match overrides.get(&id) {
+			Some(&(object_type, shape, width, height)) => (object_type, (shape, width, height)),
+			None => (get_object_type_for_id(id), get_hitbox_for_id(id)),
+		};
 		let width = base_width * scale_x;
 		let height = base_height * scale_y;
 
 		let position = Vec2::new(x, y);
-		let object_type = get_object_type_for_id(id);
 
 		let obb = if hitbox_shape == HitboxShape::Rectangle {
 			Some(OBB2D::new(position, width, height, rotation))
@@ -54,7 +74,19 @@ impl GameObject {
 			None
 		};
 
-		GameObject {
+		let slope = if object_type == GameObjectType::Slope {
+			SlopeOrientation::from_transform(flip_x, flip_y, rotation)
+		} else {
+			None
+		};
+
+		let teleport_offset = if object_type == GameObjectType::TeleportPortal {
+			Some(Vec2::new(0.0, teleport_dy))
+		} else {
+			None
+		};
+
+		let mut object = GameObject {
 			id,
 			object_type,
 			position,
@@ -66,6 +98,15 @@ impl GameObject {
 			width,
 			height,
 			obb,
-		}
+			slope,
+			teleport_offset,
+			z_layer,
+			z_order,
+			editor_layers,
+			bounds: (Vec2::ZERO, Vec2::ZERO),
+		};
+		object.bounds = object.compute_aabb();
+
+		object
 	}
 }
@@ -103,22 +103,26 @@ pub fn get_hitbox_for_id(id: i32) -> (HitboxShape, f32, f32) {
 		680 => (HitboxShape::Circle, 10.8, 10.8),
 		918 => (HitboxShape::Circle, 24.0, 24.0),
 		1582..=1583 => (HitboxShape::Circle, 4.0, 4.0),
-		1619 => (HitboxShape::Circle, 25.0, 25.0),
-		1620 => (HitboxShape::Circle, 15.0, 15.0),
 		1701..=1703 => (HitboxShape::Circle, 6.0, 6.0),
 
+		// Elongated blades, a circle would leave their ends unguarded
+		1619 => (HitboxShape::Capsule, 25.0, 12.0),
+		1620 => (HitboxShape::Capsule, 15.0, 8.0),
+
 		// Pads
 		35 => (HitboxShape::Rectangle, 25.0, 4.0),
 		140 => (HitboxShape::Rectangle, 25.0, 5.0),
 		67 => (HitboxShape::Rectangle, 25.0, 6.0),
+		1332 => (HitboxShape::Rectangle, 25.0, 6.0),
 
 		// Orbs
-		36 | 84 | 141 => (HitboxShape::Rectangle, 36.0, 36.0),
+		36 | 84 | 141 | 1022 | 1704 => (HitboxShape::Rectangle, 36.0, 36.0),
 
 		// Portals
-		12 | 13 | 47 | 111 | 660 => (HitboxShape::Rectangle, 34.0, 86.0),
-		10 | 11 => (HitboxShape::Rectangle, 25.0, 75.0),
+		12 | 13 | 47 | 111 | 660 | 1933 => (HitboxShape::Rectangle, 34.0, 86.0),
+		10 | 11 | 747 | 2926 => (HitboxShape::Rectangle, 25.0, 75.0),
 		99 | 101 => (HitboxShape::Rectangle, 31.0, 90.0),
+		45 | 46 | 286 | 287 | 745 | 1331 => (HitboxShape::Rectangle, 34.0, 86.0),
 		200 => (HitboxShape::Rectangle, 35.0, 44.0),
 		201 => (HitboxShape::Rectangle, 33.0, 56.0),
 		202 => (HitboxShape::Rectangle, 51.0, 56.0),
@@ -278,6 +282,7 @@ pub fn get_object_type_for_id(id: i32) -> GameObjectType {
 		// Portals
 		11 => GameObjectType::InverseGravityPortal,
 		10 => GameObjectType::NormalGravityPortal,
+		2926 => GameObjectType::GravityTogglePortal,
 		13 => GameObjectType::ShipPortal,
 		12 => GameObjectType::CubePortal,
 		47 => GameObjectType::BallPortal,
@@ -285,6 +290,14 @@ pub fn get_object_type_for_id(id: i32) -> GameObjectType {
 		660 => GameObjectType::WavePortal,
 		99 => GameObjectType::MiniSizePortal,
 		101 => GameObjectType::RegularSizePortal,
+		45 => GameObjectType::InverseMirrorPortal,
+		46 => GameObjectType::NormalMirrorPortal,
+		747 => GameObjectType::TeleportPortal,
+		286 => GameObjectType::DualPortal,
+		287 => GameObjectType::SoloPortal,
+		745 => GameObjectType::RobotPortal,
+		1331 => GameObjectType::SpiderPortal,
+		1933 => GameObjectType::SwingPortal,
 		200..=203 | 1334 => GameObjectType::Special,
 
 		// Slopes
@@ -300,11 +313,14 @@ pub fn get_object_type_for_id(id: i32) -> GameObjectType {
 		35 => GameObjectType::YellowJumpPad,
 		140 => GameObjectType::PinkJumpPad,
 		67 => GameObjectType::GravityPad,
+		1332 => GameObjectType::RedJumpPad,
 
 		// Orbs
 		36 => GameObjectType::YellowJumpRing,
 		84 => GameObjectType::PinkJumpRing,
 		141 => GameObjectType::GravityRing,
+		1022 => GameObjectType::GreenRing,
+		1704 => GameObjectType::DashRing,
 
 		_ => GameObjectType::Unknown,
 	}
@@ -1,7 +1,10 @@
+pub mod bounds;
 pub mod from_raw;
 pub mod mapping;
 pub mod obb;
+pub mod slope;
 pub mod types;
 
 pub use obb::OBB2D;
-pub use types::{GameObject, GameObjectType, HitboxShape};
+pub use slope::SlopeOrientation;
+pub use types::{ForcedInput, GameObject, GameObjectType, HitboxShape, RenderShape};
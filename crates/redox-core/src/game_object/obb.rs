@@ -1,6 +1,6 @@
-use glam::Vec2;
+use crate::vec2::Vec2;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct OBB2D {
 	pub center: Vec2,
 	pub corners: [Vec2; 4],
@@ -0,0 +1,79 @@
+use super::types::GameObject;
+
+/// Orientation of a slope's inclined surface inside its bounding box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlopeOrientation {
+	/// Whether the surface height increases with x
+	pub rising: bool,
+	/// Whether the surface faces downward (the solid part hangs from the top)
+	pub ceiling: bool,
+}
+
+impl SlopeOrientation {
+	/// Unflipped slopes rise to the right with the solid part below the surface.
+	/// Quarter-turn rotations have no sensible surface and are treated as boxes.
+	pub fn from_transform(flip_x: bool, flip_y: bool, rotation: f32) -> Option<Self> {
+		let quarter_turns = (rotation / 90.0).round() as i32;
+		if (rotation - quarter_turns as f32 * 90.0).abs() > 1.0 {
+			return None;
+		}
+
+		let (mut flip_x, mut flip_y) = (flip_x, flip_y);
+		match quarter_turns.rem_euclid(4) {
+			0 => {}
+			2 => {
+				flip_x = !flip_x;
+				flip_y = !flip_y;
+			}
+			_ => return None,
+		}
+
+		Some(Self {
+			rising: !flip_x,
+			ceiling: flip_y,
+		})
+	}
+}
+
+impl GameObject {
+	/// Height of the inclined surface at `x`, clamped to the slope's horizontal extent
+	pub fn slope_surface_y(&self, x: f32) -> Option<f32> {
+		let orientation = self.slope?;
+
+		let left = self.position.x - self.width * 0.5;
+		let bottom = self.position.y - self.height * 0.5;
+
+		let t = ((x - left) / self.width).clamp(0.0, 1.0);
+		let t = if orientation.rising { t } else { 1.0 - t };
+
+		Some(if orientation.ceiling {
+			bottom + self.height * (1.0 - t)
+		} else {
+			bottom + self.height * t
+		})
+	}
+
+	/// Change in surface height per unit of horizontal travel
+	pub fn slope_gradient(&self) -> Option<f32> {
+		let orientation = self.slope?;
+		let gradient = self.height / self.width;
+
+		Some(if orientation.rising != orientation.ceiling {
+			gradient
+		} else {
+			-gradient
+		})
+	}
+
+	/// Highest (or lowest, for ceiling slopes) surface point under a span of x
+	pub fn slope_contact_y(&self, min_x: f32, max_x: f32) -> Option<f32> {
+		let a = self.slope_surface_y(min_x)?;
+		let b = self.slope_surface_y(max_x)?;
+
+		Some(if self.slope?.ceiling {
+			a.min(b)
+		} else {
+			a.max(b)
+		})
+	}
+}
@@ -1,6 +1,6 @@
-use glam::Vec2;
+use crate::vec2::Vec2;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum GameObjectType {
 	Solid = 0,
 	Hazard = 2,
@@ -49,16 +49,145 @@ pub enum GameObjectType {
 	SpiderPad = 44,
 	TeleportOrb = 46,
 	AnimatedHazard = 47,
+	/// Holds the input down while the player overlaps it. GD has no such object, only id
+	/// overrides assign it
+	ForceHold = 48,
+	/// Keeps the input released while the player overlaps it, assigned like `ForceHold`
+	ForceRelease = 49,
 	Unknown = -1,
 }
 
+impl GameObjectType {
+	/// Blocks the player can stand on and crash into. Slopes are handled on their own
+	pub fn is_solid(self) -> bool {
+		matches!(self, Self::Solid)
+	}
+
+	/// Anything that kills on touch
+	pub fn is_hazard(self) -> bool {
+		matches!(self, Self::Hazard | Self::Sawblade | Self::AnimatedHazard)
+	}
+
+	/// Triggered by passing through, changing mode, gravity, size, speed or position
+	pub fn is_portal(self) -> bool {
+		matches!(
+			self,
+			Self::InverseGravityPortal
+				| Self::NormalGravityPortal
+				| Self::ShipPortal
+				| Self::CubePortal
+				| Self::InverseMirrorPortal
+				| Self::NormalMirrorPortal
+				| Self::BallPortal
+				| Self::RegularSizePortal
+				| Self::MiniSizePortal
+				| Self::UfoPortal
+				| Self::DualPortal
+				| Self::SoloPortal
+				| Self::WavePortal
+				| Self::RobotPortal
+				| Self::TeleportPortal
+				| Self::SpiderPortal
+				| Self::SwingPortal
+				| Self::GravityTogglePortal
+		)
+	}
+
+	/// Fires on touch, without a click
+	pub fn is_pad(self) -> bool {
+		matches!(
+			self,
+			Self::YellowJumpPad
+				| Self::PinkJumpPad
+				| Self::GravityPad
+				| Self::RedJumpPad
+				| Self::SpiderPad
+		)
+	}
+
+	/// Never read by the simulation, so a level plays the same without them
+	pub fn is_decoration(self) -> bool {
+		matches!(
+			self,
+			Self::Decoration
+				| Self::Unknown
+				| Self::Collectible
+				| Self::SecretCoin
+				| Self::UserCoin
+		)
+	}
+
+	/// Only ever stood on, crashed into or killed by, without any effect of its own
+	pub fn is_passive(self) -> bool {
+		self.is_solid() || self.is_hazard() || matches!(self, Self::Breakable | Self::Slope)
+	}
+
+	/// The input a forced-input region holds the player to
+	pub fn forced_input(self) -> Option<ForcedInput> {
+		match self {
+			Self::ForceHold => Some(ForcedInput::Hold),
+			Self::ForceRelease => Some(ForcedInput::Release),
+			_ => None,
+		}
+	}
+
+	/// Fires on a click while overlapping
+	pub fn is_orb(self) -> bool {
+		matches!(
+			self,
+			Self::YellowJumpRing
+				| Self::PinkJumpRing
+				| Self::GravityRing
+				| Self::GreenRing
+				| Self::DropRing
+				| Self::RedJumpRing
+				| Self::CustomRing
+				| Self::DashRing
+				| Self::GravityDashRing
+				| Self::SpiderOrb
+				| Self::TeleportOrb
+		)
+	}
+}
+
+/// What a forced-input region does to the input, see `GameObjectType::ForceHold`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForcedInput {
+	Hold,
+	Release,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum HitboxShape {
 	Rectangle,
 	Circle,
+	/// Rounded along the object's width and rotation, with its height as the diameter
+	///
+	/// The segment and radius come from the scaled size, see
+	/// `GameObject::capsule_segment`.
+	Capsule,
 }
 
-#[derive(Debug, Clone)]
+/// An object's hitbox in world coordinates, ready to draw
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RenderShape {
+	/// Corners in order around the edge, rotated with the object
+	Rect {
+		corners: [Vec2; 4],
+	},
+	Circle {
+		center: Vec2,
+		radius: f32,
+	},
+	/// The segment `a`-`b` swept by `radius`
+	Capsule {
+		a: Vec2,
+		b: Vec2,
+		radius: f32,
+	},
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct GameObject {
 	pub id: i32,
 	pub object_type: GameObjectType,
@@ -71,4 +200,52 @@ pub struct GameObject {
 	pub width: f32,
 	pub height: f32,
 	pub obb: Option<super::obb::OBB2D>,
+	pub slope: Option<super::slope::SlopeOrientation>,
+	/// Where a teleport portal moves the player, relative to the player's position
+	pub teleport_offset: Option<Vec2>,
+	/// Draw layer, lower values are further back. `None` leaves it to the object's
+	/// default, which depends on the id
+	pub z_layer: Option<i32>,
+	/// Draw order within `z_layer`
+	pub z_order: Option<i32>,
+	/// The two editor layers the object is grouped under, editor only
+	pub editor_layers: [Option<i32>; 2],
+	/// `aabb`, cached since the hot scans ask for it on every tick
+	pub(super) bounds: (Vec2, Vec2),
+}
+
+/// Everything that tells two objects apart, as far as the simulation can see
+///
+/// Floats are compared by their bits, so only exact copies share a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObjectKey {
+	id: i32,
+	position: [u32; 2],
+	rotation: u32,
+	scale: [u32; 2],
+	flip_x: bool,
+	flip_y: bool,
+}
+
+impl GameObject {
+	/// Whether this is a block or hazard placed on a background layer at or below
+	/// `deepest_z_layer`, where it usually only serves as scenery
+	pub fn is_background(&self, deepest_z_layer: i32) -> bool {
+		self.object_type.is_passive() && self.z_layer.is_some_and(|z| z <= deepest_z_layer)
+	}
+
+	/// Key shared by exact copies of this object, `None` for objects that fire once per
+	/// copy, like stacked pads, orbs and portals, which can't be merged safely
+	pub fn dedup_key(&self) -> Option<ObjectKey> {
+		let passive = self.object_type.is_passive() || self.object_type.is_decoration();
+
+		passive.then(|| ObjectKey {
+			id: self.id,
+			position: [self.position.x.to_bits(), self.position.y.to_bits()],
+			rotation: self.rotation.to_bits(),
+			scale: [self.scale.x.to_bits(), self.scale.y.to_bits()],
+			flip_x: self.flip_x,
+			flip_y: self.flip_y,
+		})
+	}
 }
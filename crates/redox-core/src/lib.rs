@@ -2,7 +2,13 @@ pub mod config;
 pub mod formats;
 pub mod game_object;
 pub mod pathfinder;
+pub mod point;
 pub mod simulation;
 pub mod state;
+pub mod vec2;
 
+pub use formats::replay;
+/// Older name of `replay`, kept so existing imports still build
 pub use formats::replay as gdr;
+pub use point::Point;
+pub use vec2::Vec2;
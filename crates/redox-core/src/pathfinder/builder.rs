@@ -1,58 +1,137 @@
+use std::{collections::HashSet, sync::Arc};
+
+use tracing::debug;
+
 use super::Pathfinder;
-use crate::{config::Config, game_object::GameObject, simulation::spatial_grid::SpatialGrid};
+use crate::{
+	config::{Config, ConfigError},
+	game_object::{GameObject, GameObjectType},
+	simulation::spatial_grid::SpatialGrid,
+};
 
-impl Pathfinder {
-	pub fn new(mut objects: Vec<GameObject>, _goal_x: f32) -> Self {
-		let config = Config::default();
+/// How far past the level's objects the player can go before it counts as fallen out
+const OUT_OF_BOUNDS_MARGIN: f32 = 100.0;
 
-		objects.sort_by(|a, b| {
-			let min_a = a.position.x - a.width * 0.5;
-			let min_b = b.position.x - b.width * 0.5;
-			min_a.partial_cmp(&min_b).unwrap()
-		});
+impl Pathfinder {
+	pub fn new(objects: impl Into<Arc<[GameObject]>>, _goal_x: f32) -> Self {
+		Self::with_config(objects, Config::default())
+	}
 
-		let mut max_obj_width = 0.0f32;
-		for obj in &objects {
-			if obj.width > max_obj_width {
-				max_obj_width = obj.width;
-			}
-		}
+	/// Panics on invalid physics, use [`Self::try_with_config`] for user supplied configs
+	pub fn with_config(objects: impl Into<Arc<[GameObject]>>, config: Config) -> Self {
+		Self::try_with_config(objects, config)
+			.unwrap_or_else(|err| panic!("Invalid physics config: {err}"))
+	}
 
-		max_obj_width += 10.0;
+	/// Objects that already went through a pathfinder, from `shared_objects`, are used
+	/// as they are without copying them
+	pub fn try_with_config(
+		objects: impl Into<Arc<[GameObject]>>, config: Config,
+	) -> Result<Self, ConfigError> {
+		config.physics.validate()?;
+		let mut objects = objects.into();
 
-		let grid = SpatialGrid::new(&objects, 128.0);
+		let background = config.background_z_layer;
+		let dropped = |obj: &GameObject| {
+			config.drop_decoration
+				&& (obj.object_type.is_decoration()
+					|| background.is_some_and(|deepest| obj.is_background(deepest)))
+		};
 
-		Self {
-			objects,
-			config,
-			max_obj_width,
-			grid,
+		// Copy-pasted stacks of blocks and spikes collide exactly like a single copy
+		let mut seen = HashSet::new();
+		let mut duplicates = 0;
+		let keep: Vec<bool> = objects
+			.iter()
+			.map(|obj| {
+				if dropped(obj) {
+					return false;
+				}
+				let unique = obj.dedup_key().is_none_or(|key| seen.insert(key));
+				duplicates += usize::from(!unique);
+				unique
+			})
+			.collect();
+		if duplicates > 0 {
+			debug!("Dropped {} duplicate objects", duplicates);
 		}
-	}
 
-	pub fn with_config(mut objects: Vec<GameObject>, config: Config) -> Self {
-		objects.sort_by(|a, b| {
-			let min_a = a.position.x - a.width * 0.5;
-			let min_b = b.position.x - b.width * 0.5;
-			min_a.partial_cmp(&min_b).unwrap()
-		});
+		let sorted = objects.is_sorted_by(|a, b| a.aabb().0.x <= b.aabb().0.x);
+		if !sorted || keep.contains(&false) {
+			let mut kept: Vec<GameObject> = objects
+				.iter()
+				.zip(&keep)
+				.filter(|&(_, &keep)| keep)
+				.map(|(obj, _)| obj.clone())
+				.collect();
+			kept.sort_by(|a, b| a.aabb().0.x.partial_cmp(&b.aabb().0.x).unwrap());
+			objects = kept.into();
+		}
 
 		let mut max_obj_width = 0.0f32;
-		for obj in &objects {
-			if obj.width > max_obj_width {
-				max_obj_width = obj.width;
-			}
+		for obj in objects.iter() {
+			let (min, max) = obj.aabb();
+			max_obj_width = max_obj_width.max(max.x - min.x);
 		}
 
 		max_obj_width += 10.0;
 
 		let grid = SpatialGrid::new(&objects, 128.0);
+		let world_ceiling = config
+			.physics
+			.world_ceiling
+			.unwrap_or_else(|| level_top(&objects));
+
+		let ground = config.physics.ground_y.unwrap_or(0.0);
+		let level_bottom = objects
+			.iter()
+			.map(|obj| obj.aabb().0.y)
+			.fold(ground, f32::min);
+		let world_floor_y = config
+			.physics
+			.world_floor_y
+			.unwrap_or(level_bottom - OUT_OF_BOUNDS_MARGIN);
+		let world_ceiling_y = config
+			.physics
+			.world_ceiling_y
+			.unwrap_or_else(|| world_ceiling.max(level_top(&objects)) + OUT_OF_BOUNDS_MARGIN);
 
-		Self {
+		// Objects are sorted by left edge by now
+		let level_start = objects.first().map_or(0.0, |obj| obj.aabb().0.x.min(0.0));
+		let world_start_x = config
+			.physics
+			.world_start_x
+			.unwrap_or(level_start - OUT_OF_BOUNDS_MARGIN);
+		let world_end_x = config.physics.world_end_x.unwrap_or(f32::MAX);
+
+		let has_dual = objects
+			.iter()
+			.any(|obj| obj.object_type == GameObjectType::DualPortal);
+		let has_forced_input = objects
+			.iter()
+			.any(|obj| obj.object_type.forced_input().is_some());
+
+		Ok(Self {
 			objects,
 			config,
 			max_obj_width,
+			world_ceiling,
+			world_floor_y,
+			world_ceiling_y,
+			world_start_x,
+			world_end_x,
 			grid,
-		}
+			has_dual,
+			has_forced_input,
+		})
 	}
 }
+
+/// Top edge of the highest object, unbounded for an empty level
+fn level_top(objects: &[GameObject]) -> f32 {
+	objects
+		.iter()
+		.map(|obj| obj.aabb().1.y)
+		.reduce(f32::max)
+		.unwrap_or(f32::MAX)
+}
@@ -0,0 +1,81 @@
+use std::fmt;
+
+use super::Pathfinder;
+use crate::{
+	state::{Action, State},
+	vec2::Vec2,
+};
+
+/// How two paths through the same level differ, from `Pathfinder::compare_paths`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathDiff {
+	/// First tick on which the players were further apart than the threshold, `None`
+	/// when they stayed together for as long as both paths lasted
+	pub divergence: Option<Divergence>,
+	/// Ticks each path covers
+	pub frames: (usize, usize),
+	pub presses: (usize, usize),
+}
+
+/// Where the players were on the tick two paths first came apart
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Divergence {
+	pub frame: usize,
+	pub a: Vec2,
+	pub b: Vec2,
+}
+
+impl fmt::Display for PathDiff {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.divergence {
+			Some(Divergence { frame, a, b }) => writeln!(
+				f,
+				"diverges on frame {}: a at ({:.1}, {:.1}), b at ({:.1}, {:.1})",
+				frame, a.x, a.y, b.x, b.y
+			)?,
+			None => writeln!(f, "no divergence")?,
+		}
+
+		let (a, b) = self.frames;
+		writeln!(f, "frames:  {} vs {} ({:+})", a, b, b as i64 - a as i64)?;
+		let (a, b) = self.presses;
+		write!(f, "presses: {} vs {} ({:+})", a, b, b as i64 - a as i64)
+	}
+}
+
+impl Pathfinder {
+	/// Re-simulates both frame-timed paths from `start` and finds the first tick on
+	/// which the players are more than `threshold` units apart
+	///
+	/// Only the ticks both paths cover are compared, a path that is just longer than
+	/// the other shows up in `PathDiff::frames` instead.
+	pub fn compare_paths(
+		&self, start: State, a: &[(Action, f32)], b: &[(Action, f32)], threshold: f32,
+	) -> PathDiff {
+		let trajectory_a = self.simulate_trajectory(start, a);
+		let trajectory_b = self.simulate_trajectory(start, b);
+
+		let divergence = trajectory_a
+			.iter()
+			.zip(&trajectory_b)
+			.enumerate()
+			.find(|(_, (a, b))| a.position.distance(b.position) > threshold)
+			.map(|(frame, (a, b))| Divergence {
+				frame,
+				a: a.position,
+				b: b.position,
+			});
+
+		let presses = |path: &[(Action, f32)]| {
+			path.iter()
+				.filter(|(action, _)| *action == Action::Press)
+				.count()
+		};
+
+		PathDiff {
+			divergence,
+			frames: (trajectory_a.len() - 1, trajectory_b.len() - 1),
+			presses: (presses(a), presses(b)),
+		}
+	}
+}
@@ -0,0 +1,98 @@
+use tracing::debug;
+
+use super::{
+	Pathfinder,
+	search::{Corridor, SearchSession, StopReason},
+};
+use crate::state::{Action, State};
+
+/// Both passes of `Pathfinder::search_hierarchical`
+pub struct TwoPassSearch {
+	pub coarse: SearchSession,
+	/// Kept inside the coarse path's corridor, or unconstrained when the coarse pass
+	/// didn't reach the goal
+	pub fine: SearchSession,
+}
+
+impl TwoPassSearch {
+	/// Expansions across both passes
+	pub fn nodes_expanded(&self) -> usize {
+		self.coarse.nodes_expanded + self.fine.nodes_expanded
+	}
+
+	pub fn reached_goal(&self) -> bool {
+		self.fine.stop_reason == Some(StopReason::Goal)
+	}
+}
+
+impl Pathfinder {
+	/// Solves coarse to fine, which can cut expansions sharply on long open levels
+	///
+	/// Falls back to a plain search when nothing inside the corridor reaches the goal,
+	/// so it never fails where `step` would succeed, only costs more when it does.
+	pub fn solve_hierarchical(&self, start: State, goal_x: f32) -> Option<Vec<(Action, f32)>> {
+		let mut search = self.search_hierarchical(start, goal_x);
+		if !search.reached_goal() && search.fine.corridor.is_some() {
+			debug!(
+				"No path inside the corridor past x={:.2}, searching without it",
+				search.fine.best_x
+			);
+			search.fine = self.start_search_from(start, goal_x);
+			self.step(&mut search.fine, goal_x);
+		}
+
+		if !search.reached_goal() {
+			return None;
+		}
+
+		let session = &search.fine;
+		let end = &session.all_nodes[session.goal_reached_index?];
+		Some(self.reconstruct_path(&session.all_nodes, end))
+	}
+
+	/// Runs a quick search over coarse states, then the full search kept within
+	/// `CoarsePass::band` of the path it found
+	pub fn search_hierarchical(&self, start: State, goal_x: f32) -> TwoPassSearch {
+		let coarse = self.coarse_search(start, goal_x);
+		let mut fine = self.start_search_from(start, goal_x);
+
+		match coarse.goal_reached_index {
+			Some(end) if coarse.stop_reason == Some(StopReason::Goal) => {
+				debug!(
+					"Coarse pass reached the goal after {} nodes",
+					coarse.nodes_expanded
+				);
+				fine.corridor = Some(Corridor::new(
+					coarse.positions_to(end),
+					self.config.search.coarse_pass.band,
+				));
+			}
+			_ => debug!(
+				"Coarse pass stopped at x={:.2}, the fine pass runs unconstrained",
+				coarse.best_x
+			),
+		}
+
+		self.step(&mut fine, goal_x);
+
+		TwoPassSearch { coarse, fine }
+	}
+
+	fn coarse_search(&self, start: State, goal_x: f32) -> SearchSession {
+		let pass = self.config.search.coarse_pass;
+		let mut config = self.config.clone();
+		// x stays as is, a grounded player moves less than a unit or two per tick, so a
+		// coarser x closes every tick's successor as a revisit of its parent
+		config.search.y_quant *= pass.quant_scale;
+		config.search.vy_quant *= pass.quant_scale;
+		config.search.heuristic_weight = pass.heuristic_weight;
+		config.search.optimal = false;
+
+		// Same objects and physics, so the coarse path lives in the same world
+		let coarse = Self::with_config(self.objects.clone(), config);
+		let mut session = coarse.start_search_from(start, goal_x);
+		coarse.step(&mut session, goal_x);
+
+		session
+	}
+}
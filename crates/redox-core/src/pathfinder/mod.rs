@@ -1,19 +1,71 @@
 pub mod builder;
+pub mod compare;
+pub mod hierarchical;
+pub mod playback;
 pub mod search;
 pub mod sim;
+pub mod smooth;
 pub mod solver;
+pub mod validate;
+
+use std::sync::Arc;
 
 use crate::{config::Config, game_object::GameObject, simulation::spatial_grid::SpatialGrid};
 
 pub struct Pathfinder {
-	pub(crate) objects: Vec<GameObject>,
+	pub(crate) objects: Arc<[GameObject]>,
 	pub(crate) config: Config,
 	pub(crate) max_obj_width: f32,
+	/// What a flipped player rests against when there is no block above
+	pub(crate) world_ceiling: f32,
+	/// Out-of-bounds limits, see `PhysicsParams::world_floor_y` and `world_ceiling_y`
+	pub(crate) world_floor_y: f32,
+	pub(crate) world_ceiling_y: f32,
+	/// Walls either side of the level, see `PhysicsParams::world_start_x` and `world_end_x`
+	pub(crate) world_start_x: f32,
+	pub(crate) world_end_x: f32,
 	pub(crate) grid: SpatialGrid,
+	/// Whether the level has a dual portal, without one the second player is never
+	/// simulated or checked
+	pub(crate) has_dual: bool,
+	/// Whether the level has any forced-input regions, without one expansions never look
+	/// for them
+	pub(crate) has_forced_input: bool,
 }
 
 impl Pathfinder {
 	pub fn dt(&self) -> f32 {
-		self.config.physics.dt
+		self.config.physics.dt()
+	}
+
+	pub fn tps(&self) -> f32 {
+		self.config.physics.tps
+	}
+
+	/// The level's objects, sorted by the left edge of their hitboxes
+	pub fn objects(&self) -> &[GameObject] {
+		&self.objects
+	}
+
+	/// The same objects as `objects`, for building another pathfinder over the level
+	/// without copying them
+	pub fn shared_objects(&self) -> Arc<[GameObject]> {
+		Arc::clone(&self.objects)
+	}
+
+	/// Objects whose hitbox spans some x in `min_x..=max_x`, sorted like `objects`
+	pub fn objects_in_range(
+		&self, min_x: f32, max_x: f32,
+	) -> impl Iterator<Item = &GameObject> + '_ {
+		// No object is wider than `max_obj_width`, so nothing starting further left can
+		// reach the window
+		let start = self
+			.objects
+			.partition_point(|obj| obj.aabb().0.x < min_x - self.max_obj_width);
+		let end = self.objects.partition_point(|obj| obj.aabb().0.x <= max_x);
+
+		self.objects[start..end.max(start)]
+			.iter()
+			.filter(move |obj| obj.aabb().1.x >= min_x)
 	}
 }
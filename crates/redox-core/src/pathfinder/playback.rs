@@ -0,0 +1,63 @@
+/// Where playback of a simulated trajectory is, counted in ticks rather than seconds
+///
+/// Whole ticks are exact in an `f32`, so seeking to a frame and reading it back always
+/// agree. Going through seconds loses that to rounding, at 240 TPS `15.0 * dt / dt`
+/// floors to 14.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Playhead {
+	tick: f32,
+	/// Index of the trajectory's last state
+	last: usize,
+}
+
+impl Playhead {
+	/// A playhead at the start of a trajectory of `states` states
+	pub fn new(states: usize) -> Self {
+		Self {
+			tick: 0.0,
+			last: states.saturating_sub(1),
+		}
+	}
+
+	/// Index of the state being shown
+	pub fn frame(&self) -> usize {
+		self.tick as usize
+	}
+
+	/// Ticks into the trajectory, including how far it is towards the next state
+	pub fn ticks(&self) -> f32 {
+		self.tick
+	}
+
+	pub fn last_frame(&self) -> usize {
+		self.last
+	}
+
+	/// How far through the trajectory the playhead is, from 0 to 1
+	pub fn progress(&self) -> f32 {
+		if self.last == 0 {
+			0.0
+		} else {
+			self.tick / self.last as f32
+		}
+	}
+
+	pub fn at_end(&self) -> bool {
+		self.tick >= self.last as f32
+	}
+
+	/// Jumps to `frame`, or the last frame past the end
+	pub fn seek(&mut self, frame: usize) {
+		self.tick = frame.min(self.last) as f32;
+	}
+
+	/// Jumps to `progress` of the way through, as a progress bar would
+	pub fn seek_progress(&mut self, progress: f32) {
+		self.tick = progress.clamp(0.0, 1.0) * self.last as f32;
+	}
+
+	/// Plays `ticks` forward, stopping at the end
+	pub fn advance(&mut self, ticks: f32) {
+		self.tick = (self.tick + ticks).min(self.last as f32);
+	}
+}
@@ -1,6 +1,97 @@
-use std::collections::{BinaryHeap, HashMap};
+use std::{
+	borrow::Cow,
+	collections::{BinaryHeap, HashMap, VecDeque},
+	fmt,
+	fs::File,
+	io::{BufReader, BufWriter},
+	path::Path,
+	time::{Duration, Instant},
+};
 
-use crate::state::{Node, State, StateKey};
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	config::{SearchConfig, TieBreak},
+	state::{Action, Node, State, StateKey},
+	vec2::Vec2,
+};
+
+/// Why a search session stopped expanding nodes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StopReason {
+	/// A node reached the goal
+	Goal,
+	/// Progress stalled across a stagnation check interval
+	Stagnation,
+	/// `SearchConfig::max_nodes` was exceeded
+	NodeBudget,
+	/// `SearchConfig::time_budget` was exceeded
+	Timeout,
+	/// The open set ran dry without reaching the goal
+	Exhausted,
+}
+
+impl fmt::Display for StopReason {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			StopReason::Goal => "goal",
+			StopReason::Stagnation => "stagnation",
+			StopReason::NodeBudget => "node_budget",
+			StopReason::Timeout => "timeout",
+			StopReason::Exhausted => "exhausted",
+		})
+	}
+}
+
+/// The parts of node expansion `PhaseTimings` tracks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+	Simulate,
+	Collision,
+	Heuristic,
+	Heap,
+}
+
+/// Wall time spent in each expansion phase, with `SearchConfig::profile`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PhaseTimings {
+	pub simulate: Duration,
+	pub collision: Duration,
+	pub heuristic: Duration,
+	pub heap: Duration,
+}
+
+impl PhaseTimings {
+	/// Adds the time since `started` to `phase`, nothing when not profiling
+	pub fn add(&mut self, phase: Phase, started: Option<Instant>) {
+		let Some(started) = started else {
+			return;
+		};
+
+		let slot = match phase {
+			Phase::Simulate => &mut self.simulate,
+			Phase::Collision => &mut self.collision,
+			Phase::Heuristic => &mut self.heuristic,
+			Phase::Heap => &mut self.heap,
+		};
+		*slot += started.elapsed();
+	}
+
+	/// Each phase with its name, in expansion order
+	pub fn phases(&self) -> [(&'static str, Duration); 4] {
+		[
+			("simulate", self.simulate),
+			("collision", self.collision),
+			("heuristic", self.heuristic),
+			("heap", self.heap),
+		]
+	}
+
+	pub fn total(&self) -> Duration {
+		self.simulate + self.collision + self.heuristic + self.heap
+	}
+}
 
 pub struct SearchSession {
 	pub open_set: BinaryHeap<NodeIndexWrapper>,
@@ -12,18 +103,110 @@ pub struct SearchSession {
 	pub best_x: f32,
 	pub checkpoint_best_x: f32,
 	pub checkpoint_nodes: usize,
+	/// `(nodes_expanded, best_x)` samples for `SearchConfig::progress_window`, oldest first
+	pub progress_samples: VecDeque<(usize, f32)>,
+	/// Consecutive window samples that came in under the minimum rate
+	pub slow_samples: usize,
+	/// Largest the open set has grown to
+	pub open_set_peak: usize,
+	/// Deaths per object index with `SearchConfig::count_deaths`, empty otherwise
+	pub death_counts: HashMap<usize, u32>,
+	/// Filled in with `SearchConfig::profile`, restarts from zero when loaded
+	pub timings: PhaseTimings,
+	/// Keys with a value too large for `SearchConfig::key_layout`, only counted with
+	/// `SearchConfig::check_key_overflow` and restarts from zero when loaded
+	pub key_overflows: usize,
+	/// Weight applied to the heuristic for newly generated nodes
+	pub heuristic_weight: f32,
+	/// Weight from before the stagnation interval check relaxed it, put back once an
+	/// interval makes progress again
+	pub interval_relaxed_from: Option<f32>,
+	/// Weight from before `SearchConfig::progress_window` relaxed it, put back once the
+	/// rate recovers
+	pub window_relaxed_from: Option<f32>,
+	pub started_at: Instant,
+	/// Set once the session has stopped, `None` while it can still make progress
+	pub stop_reason: Option<StopReason>,
+	/// Successors outside this band are dropped, see `Pathfinder::solve_hierarchical`
+	pub corridor: Option<Corridor>,
+	/// Successors of the node being expanded, kept between expansions so its
+	/// allocation is reused
+	pub(crate) successors: Vec<Node>,
+}
+
+/// Bumped whenever the checkpoint layout or anything it contains changes shape
+const CHECKPOINT_VERSION: u32 = 11;
+
+/// On-disk form of a `SearchSession`
+///
+/// The open set is stored as node indices and rebuilt on load, so the heap order
+/// always follows the loading config's tie-break.
+#[derive(Serialize, Deserialize)]
+struct Checkpoint<'a> {
+	version: u32,
+	all_nodes: Cow<'a, [Node]>,
+	closed_set: Cow<'a, HashMap<StateKey, f32>>,
+	open_indices: Vec<usize>,
+	nodes_expanded: usize,
+	goal_reached_index: Option<usize>,
+	best_x_index: usize,
+	best_x: f32,
+	checkpoint_best_x: f32,
+	checkpoint_nodes: usize,
+	progress_samples: Cow<'a, VecDeque<(usize, f32)>>,
+	slow_samples: usize,
+	open_set_peak: usize,
+	death_counts: Cow<'a, HashMap<usize, u32>>,
+	heuristic_weight: f32,
+	interval_relaxed_from: Option<f32>,
+	window_relaxed_from: Option<f32>,
+	stop_reason: Option<StopReason>,
+	corridor: Option<Cow<'a, Corridor>>,
+}
+
+/// Snapshot of a running search, yielded by `Pathfinder::search_iter`
+#[derive(Debug, Clone)]
+pub struct SearchProgress {
+	pub best_x: f32,
+	pub nodes_expanded: usize,
+	pub open_set_size: usize,
+	/// Player positions from the start to the goal node, or to the furthest node while
+	/// the goal hasn't been reached
+	pub best_path: Vec<Vec2>,
+	/// Position and g-score of a sparse sample of the nodes generated since the previous
+	/// item, filled in by `Pathfinder::search_iter`
+	pub sampled_nodes: Vec<(Vec2, f32)>,
+	/// Only set on the final item
+	pub stop_reason: Option<StopReason>,
+}
+
+/// Outcome of a whole search run with `Pathfinder::solve`
+#[derive(Debug, Clone)]
+pub struct SolveResult {
+	/// Inputs to the goal, or to the furthest node when the goal wasn't reached
+	pub actions: Vec<(Action, f32)>,
+	pub reached_goal: bool,
+	pub best_x: f32,
+	pub nodes_expanded: usize,
+	pub stop_reason: StopReason,
+}
+
+impl SearchProgress {
+	pub fn is_finished(&self) -> bool {
+		self.stop_reason.is_some()
+	}
+
+	pub fn reached_goal(&self) -> bool {
+		self.stop_reason == Some(StopReason::Goal)
+	}
 }
 
 impl SearchSession {
-	pub fn new(start_node: Node, start_pos_x: f32) -> Self {
+	pub fn new(start_node: Node, start_pos_x: f32, config: &SearchConfig) -> Self {
 		let all_nodes = vec![start_node];
 
 		let mut open_set = BinaryHeap::new();
-		open_set.push(NodeIndexWrapper {
-			f: start_node.f,
-			index: 0,
-			x: start_node.state.position.x,
-		});
+		open_set.push(NodeIndexWrapper::new(&start_node, 0, config));
 
 		Self {
 			open_set,
@@ -35,18 +218,220 @@ impl SearchSession {
 			best_x: start_pos_x,
 			checkpoint_best_x: start_pos_x,
 			checkpoint_nodes: 0,
+			progress_samples: VecDeque::new(),
+			slow_samples: 0,
+			open_set_peak: 1,
+			death_counts: HashMap::new(),
+			timings: PhaseTimings::default(),
+			key_overflows: 0,
+			heuristic_weight: config.base_weight(),
+			interval_relaxed_from: None,
+			window_relaxed_from: None,
+			started_at: Instant::now(),
+			stop_reason: None,
+			corridor: None,
+			successors: Vec::new(),
+		}
+	}
+
+	/// Writes everything needed to continue this search later with `load`
+	pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+		let path = path.as_ref();
+		let checkpoint = Checkpoint {
+			version: CHECKPOINT_VERSION,
+			all_nodes: Cow::Borrowed(&self.all_nodes),
+			closed_set: Cow::Borrowed(&self.closed_set),
+			open_indices: self.open_set.iter().map(|entry| entry.index).collect(),
+			nodes_expanded: self.nodes_expanded,
+			goal_reached_index: self.goal_reached_index,
+			best_x_index: self.best_x_index,
+			best_x: self.best_x,
+			checkpoint_best_x: self.checkpoint_best_x,
+			checkpoint_nodes: self.checkpoint_nodes,
+			progress_samples: Cow::Borrowed(&self.progress_samples),
+			slow_samples: self.slow_samples,
+			open_set_peak: self.open_set_peak,
+			death_counts: Cow::Borrowed(&self.death_counts),
+			heuristic_weight: self.heuristic_weight,
+			interval_relaxed_from: self.interval_relaxed_from,
+			window_relaxed_from: self.window_relaxed_from,
+			stop_reason: self.stop_reason,
+			corridor: self.corridor.as_ref().map(Cow::Borrowed),
+		};
+
+		let file = File::create(path)
+			.with_context(|| format!("Failed to create checkpoint: {:?}", path))?;
+		bincode::serialize_into(BufWriter::new(file), &checkpoint)?;
+
+		Ok(())
+	}
+
+	/// Restores a session written by `save`
+	///
+	/// `config` should match the one the search was started with. The time budget
+	/// restarts from the moment of loading.
+	pub fn load(path: impl AsRef<Path>, config: &SearchConfig) -> Result<Self> {
+		let path = path.as_ref();
+		let file =
+			File::open(path).with_context(|| format!("Failed to open checkpoint: {:?}", path))?;
+		let checkpoint: Checkpoint = bincode::deserialize_from(BufReader::new(file))
+			.with_context(|| format!("Malformed checkpoint: {:?}", path))?;
+
+		if checkpoint.version != CHECKPOINT_VERSION {
+			bail!(
+				"Checkpoint version {} is not supported, expected {}",
+				checkpoint.version,
+				CHECKPOINT_VERSION
+			);
+		}
+
+		let all_nodes = checkpoint.all_nodes.into_owned();
+		let open_set = checkpoint
+			.open_indices
+			.into_iter()
+			.map(|index| match all_nodes.get(index) {
+				Some(node) => Ok(NodeIndexWrapper::new(node, index, config)),
+				None => bail!(
+					"Checkpoint open set points past its {} nodes",
+					all_nodes.len()
+				),
+			})
+			.collect::<Result<_>>()?;
+
+		Ok(Self {
+			open_set,
+			closed_set: checkpoint.closed_set.into_owned(),
+			all_nodes,
+			nodes_expanded: checkpoint.nodes_expanded,
+			goal_reached_index: checkpoint.goal_reached_index,
+			best_x_index: checkpoint.best_x_index,
+			best_x: checkpoint.best_x,
+			checkpoint_best_x: checkpoint.checkpoint_best_x,
+			checkpoint_nodes: checkpoint.checkpoint_nodes,
+			progress_samples: checkpoint.progress_samples.into_owned(),
+			slow_samples: checkpoint.slow_samples,
+			open_set_peak: checkpoint.open_set_peak,
+			death_counts: checkpoint.death_counts.into_owned(),
+			timings: PhaseTimings::default(),
+			key_overflows: 0,
+			heuristic_weight: checkpoint.heuristic_weight,
+			interval_relaxed_from: checkpoint.interval_relaxed_from,
+			window_relaxed_from: checkpoint.window_relaxed_from,
+			started_at: Instant::now(),
+			stop_reason: checkpoint.stop_reason,
+			corridor: checkpoint.corridor.map(Cow::into_owned),
+			successors: Vec::new(),
+		})
+	}
+
+	/// Player positions along the chain of parents ending at `index`, start first
+	pub fn positions_to(&self, index: usize) -> Vec<Vec2> {
+		let mut positions = Vec::new();
+		let mut current = Some(index);
+		while let Some(idx) = current {
+			let node = &self.all_nodes[idx];
+			positions.push(node.state.position);
+			current = node.parent_index;
+		}
+
+		positions.reverse();
+		positions
+	}
+
+	/// Node the session's result path ends on, the goal node once one has been found
+	/// and the furthest node otherwise
+	pub fn end_index(&self) -> usize {
+		match self.stop_reason {
+			Some(StopReason::Goal) => self.goal_reached_index.unwrap_or(self.best_x_index),
+			_ => self.best_x_index,
+		}
+	}
+
+	/// Snapshot of the session, pointing at the goal node once one has been found
+	pub fn progress(&self) -> SearchProgress {
+		let end_index = self.end_index();
+
+		SearchProgress {
+			best_x: self.best_x,
+			nodes_expanded: self.nodes_expanded,
+			open_set_size: self.open_set.len(),
+			best_path: self.positions_to(end_index),
+			sampled_nodes: Vec::new(),
+			stop_reason: self.stop_reason,
 		}
 	}
 }
 
-#[derive(Debug, PartialEq)]
+/// Band around a path that a search is kept inside of
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Corridor {
+	/// Sorted by x
+	points: Vec<Vec2>,
+	/// Furthest a position may be above or below the path
+	pub band: f32,
+}
+
+impl Corridor {
+	pub fn new(mut points: Vec<Vec2>, band: f32) -> Self {
+		points.sort_by(|a, b| a.x.total_cmp(&b.x));
+		Self { points, band }
+	}
+
+	/// y of the path at `x`, interpolated between the points either side of it and
+	/// held flat past either end
+	pub fn y_at(&self, x: f32) -> Option<f32> {
+		let next = self.points.partition_point(|point| point.x < x);
+		let (Some(before), Some(after)) = (
+			self.points.get(next.saturating_sub(1)),
+			self.points
+				.get(next.min(self.points.len().saturating_sub(1))),
+		) else {
+			return None;
+		};
+
+		let span = after.x - before.x;
+		if span <= f32::EPSILON {
+			return Some(after.y);
+		}
+
+		let t = ((x - before.x) / span).clamp(0.0, 1.0);
+		Some(before.y + (after.y - before.y) * t)
+	}
+
+	/// Whether `position` is within `band` of the path straight above or below it
+	pub fn contains(&self, position: Vec2) -> bool {
+		self.y_at(position.x)
+			.is_none_or(|y| (position.y - y).abs() <= self.band)
+	}
+}
+
+/// Open-set entry, compared on integer keys so the order is identical across platforms
+#[derive(Debug, PartialEq, Eq)]
 pub struct NodeIndexWrapper {
-	pub f: f32,
+	pub f_key: i64,
+	pub tie_key: i64,
 	pub index: usize,
-	pub x: f32,
 }
 
-impl Eq for NodeIndexWrapper {}
+impl NodeIndexWrapper {
+	pub fn new(node: &Node, index: usize, config: &SearchConfig) -> Self {
+		let scale = 10f64.powi(config.f_score_decimals as i32);
+		let quantize = |value: f32| (value as f64 * scale).round() as i64;
+
+		// Greater keys are expanded first
+		let tie_key = match config.tie_break {
+			TieBreak::InsertionOrder => 0,
+			TieBreak::HigherX => quantize(node.state.position.x),
+			TieBreak::LowerG => -quantize(node.g),
+		};
+
+		Self {
+			f_key: quantize(node.f),
+			tie_key,
+			index,
+		}
+	}
+}
 
 impl PartialOrd for NodeIndexWrapper {
 	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
@@ -56,29 +441,21 @@ impl PartialOrd for NodeIndexWrapper {
 
 impl Ord for NodeIndexWrapper {
 	fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-		match other
-			.f
-			.partial_cmp(&self.f)
-			.unwrap_or(std::cmp::Ordering::Equal)
-		{
-			std::cmp::Ordering::Equal => {
-				match self
-					.x
-					.partial_cmp(&other.x)
-					.unwrap_or(std::cmp::Ordering::Equal)
-				{
-					std::cmp::Ordering::Equal => self.index.cmp(&other.index),
-					ord => ord,
-				}
-			}
-			ord => ord,
-		}
+		// Reversed on f because BinaryHeap is a max-heap and we want min-f
+		other
+			.f_key
+			.cmp(&self.f_key)
+			.then(self.tie_key.cmp(&other.tie_key))
+			.then(self.index.cmp(&other.index))
 	}
 }
 
 pub fn heuristic(
 	state: &State, goal_x: f32, player_speeds: &[f32; 5], heuristic_weight: f32,
 ) -> f32 {
+	// The goal is reached anywhere at or past `goal_x`, matching the goal test. While
+	// mirrored every tick moves away from it, the distance still has to be covered
+	// once the player is heading right again
 	let dist = (goal_x - state.position.x).max(0.0);
 	let time_to_goal = dist / player_speeds[state.speed];
 
@@ -91,3 +468,14 @@ pub fn heuristic(
 
 	(time_to_goal + penalty) * heuristic_weight
 }
+
+/// Whole ticks of running left before the goal, never more than the real remaining
+/// time since nothing changes the player's speed mid-level
+pub fn lower_bound(state: &State, goal_x: f32, player_speeds: &[f32; 5], dt: f32) -> f32 {
+	let per_tick = player_speeds[state.speed] * dt;
+	// Rounded down a hair first so float error can't add a whole tick
+	let ticks = ((goal_x - state.position.x) / per_tick - 1e-3)
+		.ceil()
+		.max(0.0);
+	ticks * dt
+}
@@ -1,44 +1,71 @@
-use super::Pathfinder;
+use std::ptr;
+
+use tracing::instrument;
+
+use super::{Pathfinder, search::PhaseTimings};
 use crate::{
-	game_object::{GameObjectType, OBB2D},
-	simulation::physics,
+	game_object::{ForcedInput, GameObject, GameObjectType, OBB2D},
+	simulation::{
+		collision::{self, CollisionKind},
+		physics,
+	},
 	state::{Action, GameMode, State},
 };
 
 impl Pathfinder {
 	pub fn apply_landing_logic(&self, prev_state: &State, mut next_state: State) -> State {
 		next_state.on_ground = false;
+		let (player_width, player_height) = self.config.physics.player_size(next_state.mini);
 
 		let prev_player_bottom = if prev_state.gravity_flipped {
-			prev_state.position.y + self.config.physics.player_height * 0.5
+			prev_state.position.y + player_height * 0.5
 		} else {
-			prev_state.position.y - self.config.physics.player_height * 0.5
+			prev_state.position.y - player_height * 0.5
 		};
 
 		let player_bottom = if next_state.gravity_flipped {
-			next_state.position.y + self.config.physics.player_height * 0.5
+			next_state.position.y + player_height * 0.5
 		} else {
-			next_state.position.y - self.config.physics.player_height * 0.5
+			next_state.position.y - player_height * 0.5
 		};
 
-		let mut landed = false;
+		// Resting y and vy of the surface the player lands on. When it crosses more than
+		// one in a tick, the first in its way wins, the highest one or the lowest flipped
+		let mut landing: Option<(f32, f32)> = None;
+		let mut offer = |y: f32, vy: f32| {
+			let first_in_way = landing.is_none_or(|(best, _)| {
+				if next_state.gravity_flipped {
+					y < best
+				} else {
+					y > best
+				}
+			});
+			if first_in_way {
+				landing = Some((y, vy));
+			}
+		};
 
-		let new_min_x = next_state.position.x - self.config.physics.player_width * 0.5;
-		let new_max_x = next_state.position.x + self.config.physics.player_width * 0.5;
+		let new_min_x = next_state.position.x - player_width * 0.5;
+		let new_max_x = next_state.position.x + player_width * 0.5;
 		let search_start_x = new_min_x - self.max_obj_width;
 
-		let start_idx = self.objects.partition_point(|obj| {
-			let obj_min_x = obj.position.x - obj.width * 0.5;
-			obj_min_x < search_start_x
-		});
+		let start_idx = self
+			.objects
+			.partition_point(|obj| obj.aabb().0.x < search_start_x);
 
 		for obj in &self.objects[start_idx..] {
-			let obj_min_x = obj.position.x - obj.width * 0.5;
-			if obj_min_x > new_max_x {
+			if obj.aabb().0.x > new_max_x {
 				break;
 			}
 
-			if obj.object_type != GameObjectType::Solid {
+			if obj.object_type == GameObjectType::Slope {
+				if let Some((y, vy)) = self.slope_landing(obj, prev_state, &next_state) {
+					offer(y, vy);
+				}
+				continue;
+			}
+
+			if !self.config.physics.is_solid(obj.object_type) {
 				continue;
 			}
 
@@ -46,74 +73,86 @@ impl Pathfinder {
 				continue;
 			}
 
-			if let Some(obj_obb) = &obj.obb {
-				let player_obb = OBB2D::new(
-					next_state.position,
-					self.config.physics.player_width,
-					self.config.physics.player_height,
-					0.0,
-				);
-
-				if player_obb.overlaps(obj_obb) {
-					let obj_top = obj.position.y + obj.height * 0.5;
-					let obj_bottom = obj.position.y - obj.height * 0.5;
-
-					let obj_left = obj.position.x - obj.width * 0.5;
-					let obj_right = obj.position.x + obj.width * 0.5;
-
-					let player_left =
-						next_state.position.x - self.config.physics.player_width * 0.5;
-					let player_right =
-						next_state.position.x + self.config.physics.player_width * 0.5;
-
-					let h_overlap =
-						(player_right.min(obj_right) - player_left.max(obj_left)).max(0.0);
-					let min_width = self.config.physics.player_width.min(obj.width);
-					let sufficient_h_overlap = h_overlap >= min_width * 0.5;
-
-					let (coming_from_correct_side, falling_towards_surface, landing_on_surface) =
-						if next_state.gravity_flipped {
-							(
-								prev_player_bottom <= obj_bottom + 2.0,
-								next_state.vy >= 0.0,
-								player_bottom >= obj_bottom - 5.0 && player_bottom <= obj_top,
-							)
-						} else {
-							(
-								prev_player_bottom >= obj_top - 2.0,
-								next_state.vy <= 0.0,
-								player_bottom <= obj_top + 5.0 && player_bottom >= obj_bottom,
-							)
-						};
-
-					if coming_from_correct_side
-						&& falling_towards_surface
-						&& landing_on_surface
-						&& sufficient_h_overlap
-					{
-						if next_state.gravity_flipped {
-							next_state.position.y =
-								obj_bottom - self.config.physics.player_height * 0.5 - 0.001;
-						} else {
-							next_state.position.y =
-								obj_top + self.config.physics.player_height * 0.5 + 0.001;
-						}
-
-						next_state.vy = 0.0;
-						next_state.on_ground = true;
-						next_state.rotation = 0.0;
-						landed = true;
-						break;
-					}
-				}
+			let Some(obj_obb) = &obj.obb else {
+				continue;
+			};
+
+			// Extent along the world axes, matching the surface the collision check allows
+			// the player to rest on for rotated blocks
+			let (obj_min, obj_max) = obj.aabb();
+			let (obj_top, obj_bottom) = (obj_max.y, obj_min.y);
+			let (obj_left, obj_right) = (obj_min.x, obj_max.x);
+
+			let h_overlap = (new_max_x.min(obj_right) - new_min_x.max(obj_left)).max(0.0);
+			let min_width = player_width.min(obj_right - obj_left);
+			let sufficient_h_overlap = h_overlap >= min_width * 0.5;
+
+			let (
+				coming_from_correct_side,
+				falling_towards_surface,
+				landing_on_surface,
+				passed_through,
+			) = if next_state.gravity_flipped {
+				(
+					prev_player_bottom <= obj_bottom + 2.0,
+					next_state.vy >= 0.0,
+					player_bottom >= obj_bottom - 5.0 && player_bottom <= obj_top,
+					player_bottom > obj_top,
+				)
+			} else {
+				(
+					prev_player_bottom >= obj_top - 2.0,
+					next_state.vy <= 0.0,
+					player_bottom <= obj_top + 5.0 && player_bottom >= obj_bottom,
+					player_bottom < obj_bottom,
+				)
+			};
+
+			if !(coming_from_correct_side && falling_towards_surface && sufficient_h_overlap) {
+				continue;
 			}
+
+			// A fast fall can carry the player clean past a thin platform within one tick,
+			// so crossing the whole block counts as landing without any overlap left
+			let touching = passed_through
+				|| landing_on_surface
+					&& OBB2D::new(next_state.position, player_width, player_height, 0.0)
+						.overlaps(obj_obb);
+
+			if touching {
+				let y = if next_state.gravity_flipped {
+					obj_bottom - player_height * 0.5 - 0.001
+				} else {
+					obj_top + player_height * 0.5 + 0.001
+				};
+				offer(y, 0.0);
+			}
+		}
+
+		let landed = landing.is_some();
+		if let Some((y, vy)) = landing {
+			next_state.position.y = y;
+			next_state.vy = vy;
+			next_state.on_ground = true;
+			next_state.rotation = 0.0;
 		}
 
+		let half_height = player_height * 0.5;
+		let ground = self.config.physics.ground_y;
 		if !landed
 			&& !next_state.gravity_flipped
-			&& next_state.position.y < self.config.physics.player_height * 0.5
+			&& let Some(ground) = ground
+			&& next_state.position.y < ground + half_height
+		{
+			next_state.position.y = ground + half_height;
+			next_state.vy = 0.0;
+			next_state.on_ground = true;
+			next_state.rotation = 0.0;
+		} else if !landed
+			&& next_state.gravity_flipped
+			&& next_state.position.y > self.world_ceiling - half_height
 		{
-			next_state.position.y = self.config.physics.player_height * 0.5;
+			next_state.position.y = self.world_ceiling - half_height;
 			next_state.vy = 0.0;
 			next_state.on_ground = true;
 			next_state.rotation = 0.0;
@@ -122,74 +161,547 @@ impl Pathfinder {
 		next_state
 	}
 
+	/// Returns the player's new y and vy if it should be resting on the slope's incline
+	fn slope_landing(
+		&self, obj: &GameObject, prev_state: &State, next_state: &State,
+	) -> Option<(f32, f32)> {
+		let orientation = obj.slope?;
+		if orientation.ceiling != next_state.gravity_flipped {
+			return None;
+		}
+
+		let (player_width, player_height) = self.config.physics.player_size(next_state.mini);
+		let half_w = player_width * 0.5;
+		let half_h = player_height * 0.5;
+
+		let obj_left = obj.position.x - obj.width * 0.5;
+		let obj_right = obj.position.x + obj.width * 0.5;
+		let obj_top = obj.position.y + obj.height * 0.5;
+		let obj_bottom = obj.position.y - obj.height * 0.5;
+
+		let min_x = next_state.position.x - half_w;
+		let max_x = next_state.position.x + half_w;
+		if max_x < obj_left || min_x > obj_right {
+			return None;
+		}
+
+		let surface = obj.slope_contact_y(min_x, max_x)?;
+		let prev_surface = obj.slope_contact_y(
+			prev_state.position.x - half_w,
+			prev_state.position.x + half_w,
+		)?;
+
+		// Carry the climb along the incline, but not once the contact point has
+		// reached the crest and the player is running onto whatever lies beyond
+		let climb = obj.slope_gradient()? * self.config.physics.player_speeds[next_state.speed];
+
+		if next_state.gravity_flipped {
+			let prev_feet = prev_state.position.y + half_h;
+			let feet = next_state.position.y + half_h;
+
+			let from_below = prev_feet <= prev_surface + 2.0;
+			let towards_surface = next_state.vy >= 0.0 || prev_state.on_ground;
+			let on_surface = feet >= surface - 5.0 && feet <= obj_top + 5.0;
+
+			let vy = if surface > obj_bottom + 0.01 {
+				climb.min(0.0)
+			} else {
+				0.0
+			};
+
+			(from_below && towards_surface && on_surface).then_some((surface - half_h - 0.001, vy))
+		} else {
+			let prev_feet = prev_state.position.y - half_h;
+			let feet = next_state.position.y - half_h;
+
+			let from_above = prev_feet >= prev_surface - 2.0;
+			let towards_surface = next_state.vy <= 0.0 || prev_state.on_ground;
+			let on_surface = feet <= surface + 5.0 && feet >= obj_bottom - 5.0;
+
+			let vy = if surface < obj_top - 0.01 {
+				climb.max(0.0)
+			} else {
+				0.0
+			};
+
+			(from_above && towards_surface && on_surface).then_some((surface + half_h + 0.001, vy))
+		}
+	}
+
 	pub fn check_portal_collisions(&self, mut state: State) -> State {
-		let player_obb = OBB2D::new(
-			state.position,
-			self.config.physics.player_width,
-			self.config.physics.player_height,
-			0.0,
-		);
-
-		let player_min_x = state.position.x - self.config.physics.player_width * 0.5;
-		let player_max_x = state.position.x + self.config.physics.player_width * 0.5;
+		let mut touched: Vec<(u32, &GameObject)> = self
+			.touching_indexed(&state)
+			.filter(|(_, obj)| obj.object_type.is_portal() || obj.object_type.is_pad())
+			.collect();
+
+		// Stacked portals apply left to right, so the last one passed wins whatever
+		// order the level lists them in
+		touched.sort_by(|(_, a), (_, b)| a.position.x.total_cmp(&b.position.x));
+
+		// The pad or teleport on cooldown re-arms once the player is off it
+		let last_pad = state.last_pad;
+		if !touched.iter().any(|&(index, _)| Some(index) == last_pad) {
+			state.last_pad = None;
+		}
+
+		for (index, obj) in touched {
+			// A short teleport can leave the player still inside the portal, which would
+			// move it again every tick
+			if obj.object_type.is_pad() || obj.object_type == GameObjectType::TeleportPortal {
+				if Some(index) == last_pad {
+					continue;
+				}
+				state.last_pad = Some(index);
+			}
+			self.apply_trigger(&mut state, obj);
+		}
+		state
+	}
+
+	/// Hands a ship or swing over to a grounded mode, in the air and keeping half its
+	/// vertical velocity, but never more than a jump's worth
+	///
+	/// GD carries some of the ship's momentum through the portal, half is our estimate.
+	/// Without the cap, a ship at its velocity clamp would leave the portal climbing or
+	/// falling far faster than a cube ever can. `pressing` is left alone, it is the
+	/// button and not the player. Nothing changes for a player that isn't flying.
+	fn leave_flight(&self, state: &mut State) {
+		if !state.mode.is_flying() {
+			return;
+		}
+
+		let jump = self.config.physics.jump_velocities[state.speed].abs();
+		state.vy = (state.vy * 0.5).clamp(-jump, jump);
+		state.on_ground = false;
+	}
+
+	/// Applies a portal or pad the player overlaps
+	fn apply_trigger(&self, state: &mut State, obj: &GameObject) {
+		match obj.object_type {
+			GameObjectType::ShipPortal | GameObjectType::SwingPortal => {
+				state.mode = if obj.object_type == GameObjectType::ShipPortal {
+					GameMode::Ship
+				} else {
+					GameMode::Swing
+				};
+				state.on_ground = false;
+				self.set_flight_bounds(state, obj.position.y);
+			}
+			GameObjectType::CubePortal => {
+				self.leave_flight(state);
+				state.mode = GameMode::Cube;
+				state.floor = 0.0;
+				state.ceiling = f32::MAX;
+			}
+			GameObjectType::SpiderPortal => {
+				self.leave_flight(state);
+				state.mode = GameMode::Spider;
+				state.floor = 0.0;
+				state.ceiling = f32::MAX;
+			}
+			// The second player spawns alongside with opposite gravity
+			GameObjectType::DualPortal if state.dual.is_none() => {
+				let mut second = *state;
+				second.gravity_flipped = !state.gravity_flipped;
+				second.on_ground = false;
+				second.vy = 0.0;
+				state.set_dual_state(&second);
+			}
+			GameObjectType::SoloPortal => state.dual = None,
+			GameObjectType::RobotPortal => {
+				self.leave_flight(state);
+				state.mode = GameMode::Robot;
+				state.floor = 0.0;
+				state.ceiling = f32::MAX;
+			}
+			GameObjectType::MiniSizePortal => state.mini = true,
+			GameObjectType::RegularSizePortal => state.mini = false,
+			GameObjectType::InverseMirrorPortal => state.mirrored = true,
+			GameObjectType::NormalMirrorPortal => state.mirrored = false,
+			GameObjectType::TeleportPortal => {
+				if let Some(offset) = obj.teleport_offset {
+					state.position += offset;
+				}
+			}
+			GameObjectType::InverseGravityPortal => state.gravity_flipped = true,
+			GameObjectType::NormalGravityPortal => state.gravity_flipped = false,
+			GameObjectType::YellowJumpPad
+			| GameObjectType::PinkJumpPad
+			| GameObjectType::RedJumpPad => {
+				let pad = match obj.object_type {
+					GameObjectType::YellowJumpPad => 0,
+					GameObjectType::PinkJumpPad => 1,
+					_ => 2,
+				};
+				let gravity_mult = if state.gravity_flipped { -1.0 } else { 1.0 };
+
+				state.vy = self.config.physics.pad_velocities[pad] * gravity_mult;
+				state.on_ground = false;
+			}
+			GameObjectType::GravityPad => {
+				// Keyed on the pad's orientation rather than toggling, the player
+				// overlaps it for several ticks
+				let upside_down = obj.flip_y != (obj.rotation.rem_euclid(360.0) == 180.0);
+				state.gravity_flipped = !upside_down;
+
+				let gravity_mult = if state.gravity_flipped { -1.0 } else { 1.0 };
+				state.vy = -self.config.physics.pad_velocities[3] * gravity_mult;
+				state.on_ground = false;
+
+				// A ship or swing takes its bounds from the pad, as from a portal
+				if matches!(state.mode, GameMode::Ship | GameMode::Swing) {
+					self.set_flight_bounds(state, obj.position.y);
+				}
+			}
+			_ => {}
+		}
+	}
+
+	/// Centers a ship or swing's floor and ceiling on the portal or pad at `trigger_y`,
+	/// snapped to the block grid, and moves the player inside them
+	fn set_flight_bounds(&self, state: &mut State, trigger_y: f32) {
+		let half_bounds = self.config.physics.ship_bounds / 2.0;
+		let floor = 30.0 * ((trigger_y - (half_bounds + 30.0)) / 30.0).ceil();
+		state.floor = floor.max(self.config.physics.ground_y.unwrap_or(f32::MIN));
+		state.ceiling = state.floor + self.config.physics.ship_bounds;
+
+		// A tall or scaled portal can be entered outside the new bounds, which would
+		// pin the player against them and cancel its velocity on the next tick
+		let half_height = self.config.physics.player_size(state.mini).1 * 0.5;
+		let lowest = match self.config.physics.ground_y {
+			Some(_) => state.floor + half_height,
+			None => f32::MIN,
+		};
+		state.position.y = state
+			.position
+			.y
+			.clamp(lowest, (state.ceiling - half_height).max(lowest));
+	}
+
+	/// Gravity toggle portals flip gravity on the tick the player enters one and flip it
+	/// back on the tick it leaves, so the effect only holds inside the portal
+	pub fn apply_gravity_regions(&self, prev_state: &State, mut state: State) -> State {
+		let regions = |state: &State| -> Vec<&GameObject> {
+			self.touching(state)
+				.filter(|obj| obj.object_type == GameObjectType::GravityTogglePortal)
+				.collect()
+		};
+		let before = regions(prev_state);
+		let after = regions(&state);
+
+		// Every portal entered or left this tick flips gravity once
+		let not_in =
+			|objs: &[&GameObject], obj: &GameObject| !objs.iter().any(|o| ptr::eq(*o, obj));
+		let crossed = after.iter().filter(|obj| not_in(&before, obj)).count()
+			+ before.iter().filter(|obj| not_in(&after, obj)).count();
+
+		if crossed % 2 == 1 {
+			state.gravity_flipped = !state.gravity_flipped;
+		}
+
+		state
+	}
+
+	/// First ring the player overlaps that a click would activate
+	///
+	/// A ring works on any tick of the overlap, but only once per pass, the one clicked
+	/// last is skipped until the player is off it.
+	pub fn touching_orb(&self, state: &State) -> Option<&GameObject> {
+		self.touching_orb_indexed(state).map(|(_, obj)| obj)
+	}
+
+	/// Like `touching_orb`, along with the ring's index
+	fn touching_orb_indexed(&self, state: &State) -> Option<(u32, &GameObject)> {
+		self.touching_indexed(state).find(|&(index, obj)| {
+			Some(index) != state.last_orb
+				&& matches!(
+					obj.object_type,
+					GameObjectType::YellowJumpRing
+						| GameObjectType::GreenRing
+						| GameObjectType::DashRing
+				)
+		})
+	}
+
+	/// The input the first forced-input region the player overlaps holds it to
+	pub fn forced_input(&self, state: &State) -> Option<ForcedInput> {
+		if !self.has_forced_input {
+			return None;
+		}
+
+		self.touching(state)
+			.find_map(|obj| obj.object_type.forced_input())
+	}
+
+	/// Objects whose hitbox box overlaps the player's, left to right by left edge
+	fn touching(&self, state: &State) -> impl Iterator<Item = &GameObject> + '_ {
+		self.touching_indexed(state).map(|(_, obj)| obj)
+	}
+
+	/// Like `touching`, along with each object's index
+	fn touching_indexed(&self, state: &State) -> impl Iterator<Item = (u32, &GameObject)> + '_ {
+		let (player_width, player_height) = self.config.physics.player_size(state.mini);
+		let player_obb = OBB2D::new(state.position, player_width, player_height, 0.0);
+
+		let player_min_x = state.position.x - player_width * 0.5;
+		let player_max_x = state.position.x + player_width * 0.5;
 		let search_start_x = player_min_x - self.max_obj_width;
 
-		let start_idx = self.objects.partition_point(|obj| {
-			let obj_min_x = obj.position.x - obj.width * 0.5;
-			obj_min_x < search_start_x
-		});
+		let start_idx = self
+			.objects
+			.partition_point(|obj| obj.aabb().0.x < search_start_x);
+
+		self.objects[start_idx..]
+			.iter()
+			.zip(start_idx as u32..)
+			.take_while(move |(obj, _)| obj.aabb().0.x <= player_max_x)
+			.filter(move |(obj, _)| obj.obb.as_ref().is_some_and(|obb| player_obb.overlaps(obb)))
+			.map(|(obj, index)| (index, obj))
+	}
+
+	/// Applies a clicked ring to the player, taking the place of a jump
+	fn activate_orb(&self, state: &mut State, orb: &GameObject) {
+		match orb.object_type {
+			// Launches like a jump off the ground, mid-air as well
+			GameObjectType::YellowJumpRing => {
+				let direction = if state.gravity_flipped { -1.0 } else { 1.0 };
+				state.vy = self.config.physics.jump_velocities[state.speed] * direction;
+				state.dashing = false;
+			}
+			GameObjectType::GreenRing => {
+				state.gravity_flipped = !state.gravity_flipped;
+				state.vy = 0.0;
+				state.dashing = false;
+			}
+			GameObjectType::DashRing => {
+				// Rotation is clockwise, steep angles are capped so the dash still
+				// moves forward
+				let angle = (-orb.rotation).clamp(-70.0, 70.0).to_radians();
+				state.vy = self.config.physics.player_speeds[state.speed] * angle.tan();
+				state.dashing = true;
+			}
+			_ => return,
+		}
+
+		state.on_ground = false;
+		state.jump_hold_frames = 0;
+	}
+
+	/// Replays merged actions frame by frame, returning every state including `start`
+	pub fn simulate_trajectory(&self, start: State, actions: &[(Action, f32)]) -> Vec<State> {
+		let dt = self.config.physics.dt();
+
+		let mut states = Vec::with_capacity(actions.len() + 1);
+		let mut state = start;
+		states.push(state);
+
+		for &(action, duration) in actions {
+			state = self.simulate_step(&state, action);
+			states.push(state);
+
+			let remaining_frames = ((duration / dt).round() as usize).saturating_sub(1);
+			for _ in 0..remaining_frames {
+				state = self.simulate_step(&state, Action::None);
+				states.push(state);
+			}
+		}
+
+		states
+	}
+
+	/// Applies `action` on the first of `n` ticks and idles for the rest
+	///
+	/// Stops before the first tick that kills the player or drops it out of the level,
+	/// returning the state just before it and `true`.
+	pub fn simulate_n(&self, state: &State, action: Action, n: u32) -> (State, bool) {
+		// Not profiled, like `reachable_x`
+		let mut timings = PhaseTimings::default();
+		match self.run_ticks(state, action, n, &mut timings, |_, _| false) {
+			Ok(state) => (state, false),
+			Err((state, _)) => (state, true),
+		}
+	}
+
+	/// Whether either player has fallen out of the level, past the floor or, with
+	/// flipped gravity, past the ceiling, or has left it through the start or end wall
+	///
+	/// Ship and swing are held inside their own bounds, so only the floor applies to them.
+	pub fn out_of_bounds(&self, state: &State) -> bool {
+		// Both players share an x
+		if !(self.world_start_x..=self.world_end_x).contains(&state.position.x) {
+			return true;
+		}
+
+		let outside = |y: f32, gravity_flipped: bool| {
+			y < self.world_floor_y
+				|| gravity_flipped && !state.mode.is_flying() && y > self.world_ceiling_y
+		};
+
+		outside(state.position.y, state.gravity_flipped)
+			|| self.has_dual
+				&& state
+					.dual
+					.is_some_and(|dual| outside(dual.y, dual.gravity_flipped))
+	}
+
+	/// Returns the id of the object that kills the player in this state, if any
+	///
+	/// In dual mode either player dying counts.
+	pub fn collides(&self, state: &State) -> Option<i32> {
+		self.collides_detailed(state).map(|(id, _)| id)
+	}
+
+	/// Like `collides`, but also reports what kind of collision it was
+	pub fn collides_detailed(&self, state: &State) -> Option<(i32, CollisionKind)> {
+		self.colliding_object(state)
+			.map(|(index, kind)| (self.objects[index].id, kind))
+	}
+
+	/// Index of the object either player dies on, for telling apart objects sharing an id
+	pub fn colliding_object(&self, state: &State) -> Option<(usize, CollisionKind)> {
+		let physics = &self.config.physics;
 
+		let first = collision::colliding_object(state, &self.objects, &self.grid, physics);
+		if first.is_some() || !self.has_dual {
+			return first;
+		}
+
+		let second = state.dual_state()?;
+		collision::colliding_object(&second, &self.objects, &self.grid, physics)
+	}
+
+	/// Where a spider tap would put the player, the nearest surface on the opposite side
+	/// of its column
+	///
+	/// Returns the player's new center y, or `None` when nothing is there to catch it.
+	pub fn spider_target(&self, state: &State) -> Option<f32> {
+		let (player_width, player_height) = self.config.physics.player_size(state.mini);
+		let half_w = player_width * 0.5;
+		let half_h = player_height * 0.5;
+		let player_min_x = state.position.x - half_w;
+		let player_max_x = state.position.x + half_w;
+
+		let start_idx = self
+			.objects
+			.partition_point(|obj| obj.aabb().0.x < player_min_x - self.max_obj_width);
+
+		let mut target: Option<f32> = None;
 		for obj in &self.objects[start_idx..] {
-			let obj_min_x = obj.position.x - obj.width * 0.5;
-			if obj_min_x > player_max_x {
+			let (min, max) = obj.aabb();
+			if min.x > player_max_x {
 				break;
 			}
-			if let Some(obj_obb) = &obj.obb
-				&& player_obb.overlaps(obj_obb)
-			{
-				match obj.object_type {
-					GameObjectType::ShipPortal => {
-						state.mode = GameMode::Ship;
-						state.on_ground = false;
-
-						let portal_y = obj.position.y;
-
-						let half_bounds = self.config.physics.ship_bounds / 2.0;
-						state.floor =
-							(30.0 * ((portal_y - (half_bounds + 30.0)) / 30.0).ceil()).max(0.0);
-						state.ceiling = state.floor + self.config.physics.ship_bounds;
-					}
-					GameObjectType::CubePortal => {
-						state.mode = GameMode::Cube;
-						state.floor = 0.0;
-						state.ceiling = f32::MAX;
-					}
-					GameObjectType::InverseGravityPortal => {
-						if !state.gravity_flipped {
-							state.gravity_flipped = true;
-						}
-					}
-					GameObjectType::NormalGravityPortal => {
-						if state.gravity_flipped {
-							state.gravity_flipped = false;
-						}
-					}
-					_ => {}
-				}
+
+			// Same overlap the landing logic needs to keep the player on the block
+			let h_overlap = player_max_x.min(max.x) - player_min_x.max(min.x);
+			let min_width = player_width.min(max.x - min.x);
+			if !self.config.physics.is_solid(obj.object_type) || h_overlap < min_width * 0.5 {
+				continue;
+			}
+
+			// Heading up lands against the underside of a block, heading down on its top
+			let candidate = if state.gravity_flipped {
+				(max.y <= state.position.y - half_h).then_some(max.y + half_h)
+			} else {
+				(min.y >= state.position.y + half_h).then_some(min.y - half_h)
+			};
+
+			if let Some(y) = candidate {
+				target = Some(match target {
+					Some(best) if state.gravity_flipped => best.max(y),
+					Some(best) => best.min(y),
+					None => y,
+				});
 			}
 		}
-		state
+
+		// The ground always catches a spider falling back down, when there is one
+		if state.gravity_flipped && target.is_none() {
+			target = self.config.physics.ground_y.map(|ground| ground + half_h);
+		}
+
+		target
 	}
 
+	/// Advances one tick, moving both players in dual mode
+	#[instrument(level = "trace", skip_all)]
 	pub fn simulate_step(&self, state: &State, action: Action) -> State {
+		let mut next_state = self.simulate_player(state, action);
+
+		// Portals hit by the first player decide whether dual mode continues
+		if self.has_dual
+			&& next_state.dual.is_some()
+			&& state.dual.is_some()
+			&& let Some(second) = state.dual_state()
+		{
+			let next_second = self.simulate_player(&second, action);
+			next_state.set_dual_state(&next_second);
+		}
+
+		next_state
+	}
+
+	fn simulate_player(&self, state: &State, action: Action) -> State {
+		let mut state = *state;
+		// Only the press that starts a hold clicks a ring, holding through it does nothing
+		// The used ring re-arms once the player is off it
+		if let Some(last_orb) = state.last_orb
+			&& !self
+				.touching_indexed(&state)
+				.any(|(index, _)| index == last_orb)
+		{
+			state.last_orb = None;
+		}
+
+		if action == Action::Press
+			&& !state.pressing
+			&& let Some((index, orb)) = self.touching_orb_indexed(&state)
+		{
+			self.activate_orb(&mut state, orb);
+			state.last_orb = Some(index);
+		}
+
+		if state.mode == GameMode::Spider
+			&& action == Action::Press
+			&& state.on_ground
+			&& let Some(y) = self.spider_target(&state)
+		{
+			state.position.y = y;
+			state.vy = 0.0;
+			state.gravity_flipped = !state.gravity_flipped;
+		}
+		let state = &state;
+
+		// Holding on the ground counts as a fresh press for a buffered jump
+		let action = if self.config.search.allow_buffer_jump
+			&& state.mode == GameMode::Cube
+			&& state.pressing
+			&& state.on_ground
+			&& action == Action::None
+		{
+			Action::Press
+		} else {
+			action
+		};
+
 		let mut next_state = physics::simulate_step(state, action, &self.config.physics);
 
 		// Landing logic (special case for Cube mode)
-		if next_state.mode == GameMode::Cube {
+		if !next_state.mode.is_flying() {
 			next_state = self.apply_landing_logic(state, next_state);
 		}
+		if next_state.on_ground {
+			next_state.dashing = false;
+		}
 
+		let pre_portal_y = next_state.position.y;
 		next_state = self.check_portal_collisions(next_state);
+		next_state = self.apply_gravity_regions(state, next_state);
+
+		// A teleport can drop the player right onto a block, so settle it again
+		if !next_state.mode.is_flying() && next_state.position.y != pre_portal_y {
+			next_state = self.apply_landing_logic(&next_state, next_state);
+		}
 
 		next_state
 	}
@@ -0,0 +1,199 @@
+use std::iter;
+
+use super::Pathfinder;
+use crate::state::{Action, State};
+
+impl Pathfinder {
+	/// Merges holds and gaps shorter than `min_hold_frames` into the input around them,
+	/// for replays a human could follow
+	///
+	/// A merge is kept only if the re-simulated player stays within `tolerance` units of
+	/// the original trajectory on every tick, never dies and still reaches `goal_x`, so
+	/// the result is never worse than `path`. A path that doesn't validate to begin with
+	/// is returned unchanged.
+	pub fn smooth_path(
+		&self, start: State, path: &[(Action, f32)], goal_x: f32, min_hold_frames: usize,
+		tolerance: f32,
+	) -> Vec<(Action, f32)> {
+		if !self.validate_path(start, path, goal_x).is_ok() {
+			return path.to_vec();
+		}
+
+		let dt = self.dt();
+		let mut held = held_per_frame(path, start.pressing, dt);
+
+		// Repeated presses collapse into one hold, so check the path still works as ticks
+		if !self
+			.validate_path(start, &to_actions(&held, start.pressing, dt), goal_x)
+			.is_ok()
+		{
+			return path.to_vec();
+		}
+
+		let reference = self.trajectory(start, &held, goal_x);
+		let mut timeline = Timeline {
+			current: reference.clone(),
+			reference,
+			pressing: start.pressing,
+			goal_x,
+			tolerance,
+		};
+
+		loop {
+			let mut merged_any = false;
+
+			let mut run_start = 0;
+			while run_start < held.len() {
+				let value = held[run_start];
+				let run_len = held[run_start..]
+					.iter()
+					.take_while(|&&h| h == value)
+					.count();
+				let run_end = run_start + run_len;
+
+				// Only runs with a neighbour to merge into, and that the player still plays
+				// before reaching the goal
+				let is_whole_path = run_start == 0 && run_end == held.len();
+				if run_len < min_hold_frames
+					&& !is_whole_path
+					&& run_start + 1 < timeline.current.len()
+				{
+					held[run_start..run_end].fill(!value);
+
+					if self.resimulate(&mut timeline, &held, run_start, run_end) {
+						merged_any = true;
+
+						// Carry on from the start of the run it merged into
+						while run_start > 0 && held[run_start - 1] != value {
+							run_start -= 1;
+						}
+						continue;
+					}
+
+					held[run_start..run_end].fill(value);
+				}
+
+				run_start = run_end;
+			}
+
+			if !merged_any {
+				break;
+			}
+		}
+
+		to_actions(&held, start.pressing, dt)
+	}
+
+	/// The state before each tick of `held` and its trailing idle, up to the goal
+	fn trajectory(&self, start: State, held: &[bool], goal_x: f32) -> Vec<State> {
+		let mut states = vec![start];
+		let mut state = start;
+		let mut frame = 0;
+		while state.position.x < goal_x {
+			state = self.simulate_step(&state, action_at(held, frame, start.pressing));
+			states.push(state);
+			frame += 1;
+		}
+		states
+	}
+
+	/// Replays `held` from the first changed tick until it rejoins the current run or
+	/// reaches the goal, splicing the new states in if it survives and never strays too
+	/// far from the reference
+	fn resimulate(
+		&self, timeline: &mut Timeline, held: &[bool], from: usize, changed_until: usize,
+	) -> bool {
+		let mut state = timeline.current[from];
+		let mut replayed = Vec::new();
+		let mut frame = from;
+
+		loop {
+			if state.position.x >= timeline.goal_x {
+				timeline.current.truncate(from + 1);
+				timeline.current.extend(replayed);
+				return true;
+			}
+
+			state = self.simulate_step(&state, action_at(held, frame, timeline.pressing));
+			frame += 1;
+
+			let Some(expected) = timeline.reference.get(frame) else {
+				// The original was at the goal by now
+				return false;
+			};
+			if (state.position - expected.position).length() > timeline.tolerance
+				|| self.out_of_bounds(&state)
+				|| self.collides_detailed(&state).is_some()
+			{
+				return false;
+			}
+
+			replayed.push(state);
+
+			// Past the edit, the same state means the rest of the run is unchanged
+			if frame >= changed_until && timeline.current.get(frame) == Some(&state) {
+				timeline.current.splice(from + 1..=frame, replayed);
+				return true;
+			}
+		}
+	}
+}
+
+/// The state before each tick of the original path and of the one being smoothed
+struct Timeline {
+	reference: Vec<State>,
+	current: Vec<State>,
+	pressing: bool,
+	goal_x: f32,
+	tolerance: f32,
+}
+
+/// The input on tick `frame`, holding whatever the path ended on once it runs out
+fn action_at(held: &[bool], frame: usize, pressing: bool) -> Action {
+	let down = |frame: usize| held.get(frame).or(held.last()).copied().unwrap_or(pressing);
+	let before = if frame == 0 {
+		pressing
+	} else {
+		down(frame - 1)
+	};
+
+	match (before, down(frame)) {
+		(false, true) => Action::Press,
+		(true, false) => Action::Release,
+		_ => Action::None,
+	}
+}
+
+/// Whether the button is down on each tick of `path`
+fn held_per_frame(path: &[(Action, f32)], mut pressing: bool, dt: f32) -> Vec<bool> {
+	let mut held = Vec::new();
+	for &(action, duration) in path {
+		let frames = ((duration / dt).round() as usize).max(1);
+		match action {
+			Action::Press => pressing = true,
+			Action::Release => pressing = false,
+			Action::None => {}
+		}
+		held.extend(iter::repeat_n(pressing, frames));
+	}
+	held
+}
+
+/// Turns per-tick button state back into presses, releases and merged idle spans
+fn to_actions(held: &[bool], mut pressing: bool, dt: f32) -> Vec<(Action, f32)> {
+	let mut actions: Vec<(Action, f32)> = Vec::new();
+	for &down in held {
+		let action = match (pressing, down) {
+			(false, true) => Action::Press,
+			(true, false) => Action::Release,
+			_ => Action::None,
+		};
+		pressing = down;
+
+		match (action, actions.last_mut()) {
+			(Action::None, Some((Action::None, duration))) => *duration += dt,
+			_ => actions.push((action, dt)),
+		}
+	}
+	actions
+}
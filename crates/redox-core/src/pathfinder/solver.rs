@@ -1,72 +1,241 @@
-use glam::Vec2;
-use tracing::info;
+use std::{
+	cmp::Reverse,
+	collections::{BinaryHeap, HashSet},
+	iter, mem,
+	path::Path,
+	time::Instant,
+};
+
+use anyhow::Result;
+use tracing::{debug, info, instrument, trace_span, warn};
 
 use super::{
 	Pathfinder,
-	search::{self, NodeIndexWrapper, SearchSession},
+	search::{
+		self, NodeIndexWrapper, Phase, PhaseTimings, SearchProgress, SearchSession, SolveResult,
+		StopReason,
+	},
 };
 use crate::{
-	simulation::collision,
+	config::{ProgressWindow, SearchStrategy},
+	formats::level::LevelHeader,
+	game_object::{ForcedInput, GameObject},
+	point::Point,
 	state::{Action, GameMode, Node, State, StateKey},
 };
 
 impl Pathfinder {
-	pub fn start_search(&self, start_pos: Vec2, goal_x: f32) -> SearchSession {
-		let start_state = State {
-			position: start_pos,
+	/// Builds the player's spawn state from the level's start settings
+	pub fn initial_state(&self, start_pos: impl Into<Point>, header: &LevelHeader) -> State {
+		let ground = self.config.physics.ground_y.unwrap_or(0.0);
+		let (floor, ceiling) = match header.game_mode {
+			GameMode::Cube | GameMode::Spider | GameMode::Robot => (ground, f32::MAX),
+			GameMode::Ship | GameMode::Swing => (ground, ground + self.config.physics.ship_bounds),
+		};
+
+		State {
+			position: start_pos.into(),
 			vy: 0.0,
-			on_ground: true,
+			on_ground: !header.game_mode.is_flying() && !header.gravity_flipped,
 			rotation: 0.0,
-			mode: GameMode::Cube,
-			gravity_flipped: false,
-			floor: 0.0,
-			ceiling: f32::MAX,
+			mode: header.game_mode,
+			gravity_flipped: header.gravity_flipped,
+			floor,
+			ceiling,
 			pressing: false,
-			speed: 1,
-		};
+			speed: header.speed,
+			mini: header.mini,
+			mirrored: false,
+			jump_hold_frames: 0,
+			dashing: false,
+			last_pad: None,
+			last_orb: None,
+			dual: None,
+		}
+	}
+
+	pub fn start_search(&self, start_pos: impl Into<Point>, goal_x: f32) -> SearchSession {
+		self.start_search_from(
+			self.initial_state(start_pos, &LevelHeader::default()),
+			goal_x,
+		)
+	}
+
+	#[instrument(name = "start_search", level = "trace", skip_all, fields(goal_x))]
+	pub fn start_search_from(&self, start_state: State, goal_x: f32) -> SearchSession {
+		let start_pos = start_state.position;
 
 		let start_node = Node {
 			g: 0.0,
-			f: search::heuristic(
-				&start_state,
-				goal_x,
-				&self.config.physics.player_speeds,
-				self.config.search.heuristic_weight,
-			),
+			f: self.estimate(&start_state, goal_x, self.config.search.base_weight()),
 			state: start_state,
 			parent_index: None,
 			action: None,
+			frames: 0,
 		};
 
-		SearchSession::new(start_node, start_pos.x)
+		SearchSession::new(start_node, start_pos.x, &self.config.search)
+	}
+
+	/// Searches from `start` with the default level settings until the search stops
+	pub fn solve(&self, start: impl Into<Point>, goal_x: f32) -> SolveResult {
+		self.solve_from(self.initial_state(start, &LevelHeader::default()), goal_x)
+	}
+
+	/// Like `solve`, from a state built with `initial_state` or anywhere mid-level
+	pub fn solve_from(&self, start: State, goal_x: f32) -> SolveResult {
+		let mut session = self.start_search_from(start, goal_x);
+		self.step(&mut session, goal_x);
+
+		let stop_reason = session
+			.stop_reason
+			.expect("step only returns once the session has stopped");
+		let end = session.end_index();
+
+		SolveResult {
+			actions: self.reconstruct_path(&session.all_nodes, &session.all_nodes[end]),
+			reached_goal: stop_reason == StopReason::Goal,
+			best_x: session.best_x,
+			nodes_expanded: session.nodes_expanded,
+			stop_reason,
+		}
+	}
+
+	/// Moves a session's goal to `new_goal_x` so it can carry on with the tree it already
+	/// built, such as after reaching a closer goal
+	///
+	/// g doesn't depend on the goal so the closed set stays valid, only the open nodes'
+	/// f-scores are recomputed. A goal node that stopped the session goes back on the
+	/// open set, it was never expanded. Whatever stopped the session is cleared so the
+	/// next `step` carries on, a budget that ran out stops it again straight away.
+	pub fn extend_goal(&self, session: &mut SearchSession, new_goal_x: f32) {
+		let mut indices: Vec<usize> = session.open_set.drain().map(|entry| entry.index).collect();
+		if session.stop_reason.take() == Some(StopReason::Goal)
+			&& let Some(index) = session.goal_reached_index.take()
+		{
+			indices.push(index);
+		}
+
+		for index in indices {
+			let node = &mut session.all_nodes[index];
+			node.f = node.g + self.estimate(&node.state, new_goal_x, session.heuristic_weight);
+			session
+				.open_set
+				.push(NodeIndexWrapper::new(node, index, &self.config.search));
+		}
+	}
+
+	/// Restores a session saved with `SearchSession::save`, rebuilding its open set
+	/// under this pathfinder's search config
+	pub fn resume_search(&self, checkpoint: impl AsRef<Path>) -> Result<SearchSession> {
+		SearchSession::load(checkpoint, &self.config.search)
 	}
 
 	pub fn step_single(&self, session: &mut SearchSession, goal_x: f32) -> bool {
-		if session.goal_reached_index.is_some() || session.open_set.is_empty() {
+		if session.goal_reached_index.is_some() || session.stop_reason.is_some() {
+			return true;
+		}
+
+		if session.open_set.is_empty() {
+			session.stop_reason = Some(StopReason::Exhausted);
 			return true;
 		}
 
+		if let Some(max_nodes) = self.config.search.max_nodes
+			&& session.nodes_expanded >= max_nodes
+		{
+			info!(
+				"Node budget of {} exhausted at x={:.2}",
+				max_nodes, session.best_x
+			);
+			return self.stop_at_best(session, StopReason::NodeBudget);
+		}
+
+		// Checking the clock every node is wasteful, so sample it periodically
+		if let Some(budget) = self.config.search.time_budget
+			&& session.nodes_expanded.is_multiple_of(1024)
+			&& session.started_at.elapsed() >= budget
+		{
+			info!(
+				"Time budget of {:?} exhausted at x={:.2}",
+				budget, session.best_x
+			);
+			return self.stop_at_best(session, StopReason::Timeout);
+		}
+
 		if session.nodes_expanded
 			>= session.checkpoint_nodes + self.config.search.stagnation_check_interval
 		{
 			let progress = session.best_x - session.checkpoint_best_x;
-			if progress < self.config.search.min_progress_per_interval {
-				info!(
+			let stalled = progress < self.config.search.min_progress_per_interval;
+
+			if stalled && self.config.search.adaptive_weight && session.heuristic_weight > 1.05 {
+				// Give the search one more interval with a more admissible heuristic
+				session
+					.interval_relaxed_from
+					.get_or_insert(session.heuristic_weight);
+				session.heuristic_weight = (session.heuristic_weight + 1.0) * 0.5;
+				debug!(
+					"Stagnation detected at x={:.2}, relaxing heuristic weight to {:.2}",
+					session.best_x, session.heuristic_weight
+				);
+			} else if stalled {
+				debug!(
 					"Stagnation detected: only {:.2} units progress in {} nodes. Stopping at x={:.2}",
 					progress, self.config.search.stagnation_check_interval, session.best_x
 				);
 
-				session.goal_reached_index = Some(session.best_x_index);
-				return true;
+				return self.stop_at_best(session, StopReason::Stagnation);
+			} else {
+				restore_weight(
+					&mut session.interval_relaxed_from,
+					&mut session.window_relaxed_from,
+					&mut session.heuristic_weight,
+				);
 			}
 
 			session.checkpoint_best_x = session.best_x;
 			session.checkpoint_nodes = session.nodes_expanded;
 		}
 
-		if let Some(wrapper) = session.open_set.pop() {
+		if let Some(window) = self.config.search.progress_window
+			&& session.nodes_expanded > 0
+			&& session
+				.nodes_expanded
+				.is_multiple_of((window.nodes / window.samples.max(1)).max(1))
+			&& self.window_stalled(session, window)
+		{
+			if self.config.search.adaptive_weight && session.heuristic_weight > 1.05 {
+				session
+					.window_relaxed_from
+					.get_or_insert(session.heuristic_weight);
+				session.heuristic_weight = (session.heuristic_weight + 1.0) * 0.5;
+				session.progress_samples.clear();
+				session.slow_samples = 0;
+				debug!(
+					"Progress rate too low at x={:.2}, relaxing heuristic weight to {:.2}",
+					session.best_x, session.heuristic_weight
+				);
+			} else {
+				debug!(
+					"Stagnation detected: under {:.2} units per 1000 nodes for {} samples. \
+					 Stopping at x={:.2}",
+					window.min_rate, window.patience, session.best_x
+				);
+
+				return self.stop_at_best(session, StopReason::Stagnation);
+			}
+		}
+
+		let clock = self.clock();
+		let popped = session.open_set.pop();
+		session.timings.add(Phase::Heap, clock);
+
+		if let Some(wrapper) = popped {
 			let current_idx = wrapper.index;
-			let current_node = session.all_nodes[current_idx];
+			// Borrowed rather than copied, the successors are only appended once it's
+			// no longer needed
+			let current_node = &session.all_nodes[current_idx];
 
 			session.nodes_expanded += 1;
 
@@ -76,115 +245,415 @@ impl Pathfinder {
 			}
 
 			if current_node.state.position.x >= goal_x {
-				info!("Goal reached after {} nodes!", session.nodes_expanded);
+				debug!("Goal reached after {} nodes!", session.nodes_expanded);
 
 				session.goal_reached_index = Some(current_idx);
+				session.stop_reason = Some(StopReason::Goal);
 				return true;
 			}
 
-			let key = StateKey::from_state(
-				&current_node.state,
-				self.config.search.x_quant,
-				self.config.search.y_quant,
-				self.config.search.vy_quant,
-			);
-
-			if let Some(&best_g) = session.closed_set.get(&key)
-				&& current_node.g > best_g + (self.config.physics.dt * 0.5)
-			{
+			let key = self.state_key(&current_node.state);
+			self.check_key(&current_node.state, &mut session.key_overflows);
+			if self.is_closed(session, &key, current_node.g) {
 				return false;
 			}
 
-			session.closed_set.insert(key, current_node.g);
-
-			let mut actions_to_try: [Action; 2] = [Action::None, Action::None];
-			let mut action_count = 1;
-			// actions_to_try[0] is already Action::None
-
-			if current_node.state.pressing {
-				actions_to_try[1] = Action::Release;
-				action_count = 2;
-			} else {
-				match current_node.state.mode {
-					GameMode::Cube => {
-						if current_node.state.on_ground {
-							actions_to_try[1] = Action::Press;
-							action_count = 2;
+			session
+				.closed_set
+				.entry(key)
+				.and_modify(|best_g| *best_g = best_g.min(current_node.g))
+				.or_insert(current_node.g);
+
+			let mut successors = mem::take(&mut session.successors);
+			for (action, frames) in self.edges(&current_node.state) {
+				let next_state = match self.simulate_edge(
+					&current_node.state,
+					action,
+					frames,
+					&mut session.timings,
+				) {
+					Ok(next_state) => next_state,
+					Err(killer) => {
+						if let Some(index) = killer
+							&& self.config.search.count_deaths
+						{
+							*session.death_counts.entry(index).or_default() += 1;
 						}
+						continue;
 					}
-					GameMode::Ship => {
-						actions_to_try[1] = Action::Press;
-						action_count = 2;
-					}
-				}
-			}
-
-			for action in actions_to_try.iter().take(action_count) {
-				let action = *action;
-				let next_state = self.simulate_step(&current_node.state, action);
-
-				if next_state.position.y < -100.0 {
-					continue;
-				}
+				};
+				self.check_key(&next_state, &mut session.key_overflows);
 
-				if collision::collides_info(
-					&next_state,
-					&self.objects,
-					&self.grid,
-					&self.config.physics,
-				)
-				.is_some()
+				if session
+					.corridor
+					.as_ref()
+					.is_some_and(|corridor| !corridor.contains(next_state.position))
 				{
 					continue;
 				}
 
-				let mut new_g = current_node.g + self.config.physics.dt;
+				let mut new_g = current_node.g + self.config.physics.dt() * frames as f32;
 				if action == Action::Press {
-					if current_node.state.mode == GameMode::Cube {
-						new_g += 15.0 * self.config.physics.dt;
-					} else {
-						new_g += 0.5 * self.config.physics.dt;
-					}
+					let flying = current_node.state.mode.is_flying();
+					new_g += self.config.search.press_ticks(flying) * self.config.physics.dt();
+				}
+
+				// Popping it would find it closed, so pushing it would only grow the arena
+				// and heap. A route cheaper by over the reopen slack still goes through
+				if self.is_closed(session, &self.state_key(&next_state), new_g) {
+					continue;
 				}
 
-				let new_f = new_g
-					+ search::heuristic(
-						&next_state,
-						goal_x,
-						&self.config.physics.player_speeds,
-						self.config.search.heuristic_weight,
-					);
+				let clock = self.clock();
+				let new_f = new_g + self.estimate(&next_state, goal_x, session.heuristic_weight);
+				session.timings.add(Phase::Heuristic, clock);
 
-				let next_node = Node {
+				successors.push(Node {
 					g: new_g,
 					f: new_f,
 					state: next_state,
 					parent_index: Some(current_idx),
 					action: Some(action),
-				};
+					frames,
+				});
+			}
 
+			let clock = self.clock();
+			for next_node in successors.drain(..) {
 				let next_idx = session.all_nodes.len();
-
+				session.open_set.push(NodeIndexWrapper::new(
+					&next_node,
+					next_idx,
+					&self.config.search,
+				));
 				session.all_nodes.push(next_node);
-				session.open_set.push(NodeIndexWrapper {
-					f: new_f,
-					index: next_idx,
-					x: next_state.position.x,
-				});
+			}
+			session.successors = successors;
+			session.timings.add(Phase::Heap, clock);
+
+			session.open_set_peak = session.open_set_peak.max(session.open_set.len());
+
+			if let SearchStrategy::Beam { width } = self.config.search.strategy
+				&& session.open_set.len() > width
+			{
+				prune_open_set(session, width);
 			}
 		}
 
 		false
 	}
 
+	/// Furthest x a greedy expansion from `start` reaches within `max_nodes`
+	///
+	/// Always expands the rightmost state next with no regard for time, so it is quick
+	/// and cheap but can fall short of what the full search finds. Meant as a probe for
+	/// where a level is likely to stall before committing to a long search.
+	pub fn reachable_x(&self, start: State, max_nodes: usize) -> f32 {
+		let mut states = vec![start];
+		let mut frontier = BinaryHeap::from([(x_key(start.position.x), Reverse(0))]);
+		let mut seen = HashSet::new();
+		let mut best_x = start.position.x;
+
+		// Only here to satisfy `simulate_edge`, the probe isn't profiled
+		let mut timings = PhaseTimings::default();
+
+		let mut expanded = 0;
+		while expanded < max_nodes
+			&& let Some((_, Reverse(index))) = frontier.pop()
+		{
+			let state = states[index];
+			if !seen.insert(self.state_key(&state)) {
+				continue;
+			}
+
+			expanded += 1;
+			best_x = best_x.max(state.position.x);
+
+			for (action, frames) in self.edges(&state) {
+				if let Ok(next) = self.simulate_edge(&state, action, frames, &mut timings) {
+					frontier.push((x_key(next.position.x), Reverse(states.len())));
+					states.push(next);
+				}
+			}
+		}
+
+		best_x
+	}
+
+	/// Inputs worth trying from `state`, each with how many ticks it is held for
+	fn edges(&self, state: &State) -> Vec<(Action, u32)> {
+		// Inside a forced-input region the only choice is whether the input already
+		// matches, so there is nothing to branch on
+		if let Some(forced) = self.forced_input(state) {
+			let action = match (forced, state.pressing) {
+				(ForcedInput::Hold, false) => Action::Press,
+				(ForcedInput::Release, true) => Action::Release,
+				_ => Action::None,
+			};
+			return vec![(action, 1)];
+		}
+
+		let mut actions_to_try: [Action; 2] = [Action::None, Action::None];
+		let mut action_count = 1;
+		// actions_to_try[0] is already Action::None
+
+		// Holding does nothing for a swing, so a press is always let go of straight away
+		if state.pressing && state.mode == GameMode::Swing {
+			actions_to_try[0] = Action::Release;
+		} else if state.pressing {
+			actions_to_try[1] = Action::Release;
+			action_count = 2;
+		} else if self.press_has_effect(state)
+			|| self.has_dual
+				&& state
+					.dual_state()
+					.is_some_and(|second| self.press_has_effect(&second))
+		{
+			actions_to_try[1] = Action::Press;
+			action_count = 2;
+		}
+
+		let mut edges: Vec<(Action, u32)> = actions_to_try[..action_count]
+			.iter()
+			.map(|&action| (action, 1))
+			.collect();
+
+		// Ship and swing inputs matter over spans of ticks, so also jump straight to
+		// holding the new input for a while instead of branching on every tick
+		if state.mode.is_flying() && action_count == 2 {
+			let action = actions_to_try[1];
+			edges.extend(
+				self.config
+					.search
+					.macro_hold_frames
+					.iter()
+					.map(|&frames| (action, frames)),
+			);
+		}
+
+		edges
+	}
+
+	/// Applies `action` and then idles for the rest of `frames` ticks
+	///
+	/// Fails if the player dies or falls out of the level on any of them, with the index
+	/// of the object that killed it when there was one. Also fails without a killer when
+	/// a multi-tick edge idles into a forced-input region against its input, the single
+	/// tick edges already cover that stretch.
+	fn simulate_edge(
+		&self, state: &State, action: Action, frames: u32, timings: &mut PhaseTimings,
+	) -> Result<State, Option<usize>> {
+		self.run_ticks(state, action, frames.max(1), timings, |frame, state| {
+			frame + 1 < frames
+				&& self
+					.forced_input(state)
+					.is_some_and(|forced| state.pressing != (forced == ForcedInput::Hold))
+		})
+		.map_err(|(_, killer)| killer)
+	}
+
+	/// Applies `action` on the first of `n` ticks and idles for the rest, the one loop
+	/// behind `simulate_edge` and `simulate_n`
+	///
+	/// Fails on the first tick the player dies or falls out of the level, or that `stop`
+	/// rejects given its frame number and the state after it. The error holds the state
+	/// from before that tick and the index of the object that killed the player, if any.
+	pub(super) fn run_ticks(
+		&self, state: &State, action: Action, n: u32, timings: &mut PhaseTimings,
+		mut stop: impl FnMut(u32, &State) -> bool,
+	) -> Result<State, (State, Option<usize>)> {
+		let mut state = *state;
+		for frame in 0..n {
+			let tick_action = if frame == 0 { action } else { Action::None };
+			let clock = self.clock();
+			let next = self.simulate_step(&state, tick_action);
+			timings.add(Phase::Simulate, clock);
+
+			if self.out_of_bounds(&next) {
+				return Err((state, None));
+			}
+
+			let clock = self.clock();
+			let hit = self.colliding_object(&next);
+			timings.add(Phase::Collision, clock);
+
+			if let Some((index, _)) = hit {
+				return Err((state, Some(index)));
+			}
+
+			if stop(frame, &next) {
+				return Err((state, None));
+			}
+
+			state = next;
+		}
+
+		Ok(state)
+	}
+
+	/// The `n` objects that killed the most successors, deadliest first, with their death
+	/// counts. Empty unless the search ran with `SearchConfig::count_deaths`
+	pub fn deadliest(&self, session: &SearchSession, n: usize) -> Vec<(&GameObject, u32)> {
+		let mut counts: Vec<_> = session.death_counts.iter().collect();
+		counts.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+
+		counts
+			.into_iter()
+			.take(n)
+			.map(|(&index, &count)| (&self.objects[index], count))
+			.collect()
+	}
+
+	/// Whether pressing in this state would do anything, so the search can skip
+	/// pointless presses
+	fn press_has_effect(&self, state: &State) -> bool {
+		if self.touching_orb(state).is_some() {
+			return true;
+		}
+
+		match state.mode {
+			GameMode::Cube | GameMode::Robot => state.on_ground,
+			GameMode::Ship | GameMode::Swing => true,
+			GameMode::Spider => state.on_ground && self.spider_target(state).is_some(),
+		}
+	}
+
+	/// Runs the search, yielding a snapshot every `SearchConfig::progress_interval`
+	/// expansions and a final one carrying the stop reason
+	pub fn search_iter<'a>(
+		&'a self, session: &'a mut SearchSession, goal_x: f32,
+	) -> impl Iterator<Item = SearchProgress> + 'a {
+		let interval = self.config.search.progress_interval.max(1);
+		let stride = self.config.search.progress_sample_stride.max(1);
+		let mut next_sample = 0;
+		let mut finished = false;
+
+		iter::from_fn(move || {
+			if finished {
+				return None;
+			}
+
+			let _batch = trace_span!("step_batch", interval).entered();
+			for _ in 0..interval {
+				if self.step_single(session, goal_x) {
+					finished = true;
+					break;
+				}
+			}
+
+			let mut progress = session.progress();
+			while next_sample < session.all_nodes.len() {
+				let node = &session.all_nodes[next_sample];
+				progress.sampled_nodes.push((node.state.position, node.g));
+				next_sample += stride;
+			}
+
+			Some(progress)
+		})
+	}
+
+	#[instrument(level = "trace", skip_all, fields(goal_x))]
 	pub fn step(&self, session: &mut SearchSession, goal_x: f32) -> bool {
-		while !session.open_set.is_empty() {
+		loop {
 			if self.step_single(session, goal_x) {
 				return true;
 			}
 		}
+	}
 
-		false
+	/// Start of a timed phase, `None` unless profiling so the off path skips the clock
+	fn clock(&self) -> Option<Instant> {
+		self.config.search.profile.then(Instant::now)
+	}
+
+	fn state_key(&self, state: &State) -> StateKey {
+		StateKey::with_layout(
+			state,
+			self.config.search.x_quant,
+			self.config.search.y_quant,
+			self.config.search.vy_quant,
+			self.config.search.key_layout,
+		)
+	}
+
+	/// Counts `state` into `overflows` when its key wrapped, warning on the first
+	fn check_key(&self, state: &State, overflows: &mut usize) {
+		if !self.config.search.check_key_overflow {
+			return;
+		}
+
+		let search = &self.config.search;
+		if let Some((field, value)) =
+			search
+				.key_layout
+				.overflow(state, search.x_quant, search.y_quant, search.vy_quant)
+		{
+			if *overflows == 0 {
+				warn!(
+					"Quantized {field:?} {value} at x={:.2} doesn't fit the state key, distinct \
+					 states may be merged. Give it more bits in the key layout",
+					state.position.x
+				);
+			}
+			*overflows += 1;
+		}
+	}
+
+	/// Whether a state under `key` was already expanded at a cost `g` doesn't beat by
+	/// more than `SearchConfig::reopen_slack`
+	fn is_closed(&self, session: &SearchSession, key: &StateKey, g: f32) -> bool {
+		let slack = self.config.search.reopen_slack * self.config.physics.dt();
+		session
+			.closed_set
+			.get(key)
+			.is_some_and(|&best_g| g >= best_g - slack)
+	}
+
+	/// Heuristic for `state`, admissible when searching for an optimal path
+	fn estimate(&self, state: &State, goal_x: f32, weight: f32) -> f32 {
+		let speeds = &self.config.physics.player_speeds;
+		if self.config.search.optimal {
+			search::lower_bound(state, goal_x, speeds, self.config.physics.dt())
+		} else {
+			search::heuristic(state, goal_x, speeds, weight)
+		}
+	}
+
+	/// Records a best-x sample and reports whether the rate across the window has been
+	/// too low for `window.patience` samples in a row
+	fn window_stalled(&self, session: &mut SearchSession, window: ProgressWindow) -> bool {
+		let samples = &mut session.progress_samples;
+		samples.push_back((session.nodes_expanded, session.best_x));
+		if samples.len() <= window.samples {
+			return false;
+		}
+		samples.pop_front();
+
+		let (oldest_nodes, oldest_x) = samples[0];
+		let nodes = (session.nodes_expanded - oldest_nodes).max(1) as f32;
+		let rate = (session.best_x - oldest_x) / nodes * 1000.0;
+
+		if rate < window.min_rate {
+			session.slow_samples += 1;
+		} else {
+			session.slow_samples = 0;
+			restore_weight(
+				&mut session.window_relaxed_from,
+				&mut session.interval_relaxed_from,
+				&mut session.heuristic_weight,
+			);
+		}
+
+		session.slow_samples >= window.patience
+	}
+
+	/// Ends the search early, leaving the furthest node so far as the result
+	///
+	/// `goal_reached_index` stays empty so nothing mistakes the partial run for a solve,
+	/// `SearchSession::end_index` picks up `best_x_index` instead.
+	fn stop_at_best(&self, session: &mut SearchSession, reason: StopReason) -> bool {
+		session.stop_reason = Some(reason);
+		true
 	}
 
 	pub fn reconstruct_path(&self, nodes: &[Node], end_node: &Node) -> Vec<(Action, f32)> {
@@ -192,7 +661,12 @@ impl Pathfinder {
 		let mut current = end_node;
 		while let Some(parent_idx) = current.parent_index {
 			if let Some(action) = current.action {
-				path.push((action, self.config.physics.dt));
+				// Walking backwards, so the idle tail of a held edge comes first
+				let dt = self.config.physics.dt();
+				if current.frames > 1 {
+					path.push((Action::None, dt * (current.frames - 1) as f32));
+				}
+				path.push((action, dt));
 			}
 			current = &nodes[parent_idx];
 		}
@@ -213,3 +687,38 @@ impl Pathfinder {
 		merged
 	}
 }
+
+/// Drops one detector's claim on a relaxed heuristic weight once it sees progress again
+///
+/// The weight only goes back up once neither detector is holding it down. If the other
+/// one still is, it takes over the higher of the two saved weights, which is the one
+/// from before either of them relaxed it.
+fn restore_weight(own: &mut Option<f32>, other: &mut Option<f32>, weight: &mut f32) {
+	let Some(from) = own.take() else {
+		return;
+	};
+
+	match other {
+		Some(other_from) => *other_from = other_from.max(from),
+		None => *weight = from,
+	}
+}
+
+/// Keeps the `keep` lowest f-scores in the open set and drops the rest
+///
+/// Dropped nodes stay in `all_nodes`, so `best_x_index` and path reconstruction
+/// still work, they just won't be expanded
+fn prune_open_set(session: &mut SearchSession, keep: usize) {
+	let mut nodes = mem::take(&mut session.open_set).into_vec();
+
+	// `NodeIndexWrapper` orders lower f-scores as greater
+	nodes.sort_unstable_by(|a, b| b.cmp(a));
+	nodes.truncate(keep.max(1));
+
+	session.open_set = BinaryHeap::from(nodes);
+}
+
+/// Orders x positions in a heap, to a thousandth of a unit
+fn x_key(x: f32) -> i64 {
+	(x * 1000.0).round() as i64
+}
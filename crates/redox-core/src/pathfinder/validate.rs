@@ -0,0 +1,137 @@
+use std::{fmt, iter};
+
+use super::Pathfinder;
+use crate::{
+	simulation::collision::CollisionKind,
+	state::{Action, State},
+};
+
+/// Idle ticks to keep simulating after the actions run out before giving up
+const MAX_TRAILING_FRAMES: usize = 100_000;
+
+/// Outcome of re-simulating a path with `Pathfinder::validate_path`
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathValidation {
+	/// The player reached the goal, `frame` ticks in
+	Ok { final_x: f32, frame: usize },
+	/// The first tick on which the run went wrong, and the state right after it
+	Failed {
+		frame: usize,
+		reason: FailureReason,
+		state: State,
+	},
+}
+
+impl PathValidation {
+	pub fn is_ok(&self) -> bool {
+		matches!(self, PathValidation::Ok { .. })
+	}
+}
+
+impl fmt::Display for PathValidation {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			PathValidation::Ok { final_x, frame } => {
+				write!(f, "reaches the goal at x={:.1} on frame {}", final_x, frame)
+			}
+			PathValidation::Failed {
+				frame,
+				reason,
+				state,
+			} => write!(
+				f,
+				"{} on frame {} at ({:.1}, {:.1}) in {:?} mode",
+				reason, frame, state.position.x, state.position.y, state.mode
+			),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureReason {
+	Collision {
+		object_id: i32,
+		kind: CollisionKind,
+	},
+	/// Left the level through its floor, or its ceiling with flipped gravity
+	FellOut,
+	/// Idled well past the end of the actions without reaching the goal
+	Stalled,
+}
+
+impl fmt::Display for FailureReason {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			FailureReason::Collision { object_id, kind } => {
+				write!(f, "dies on object {} ({})", object_id, kind)
+			}
+			FailureReason::FellOut => f.write_str("falls out of the level"),
+			FailureReason::Stalled => f.write_str("stalls without reaching the goal"),
+		}
+	}
+}
+
+impl Pathfinder {
+	/// Re-simulates frame-timed `actions` from `start` with the same stepping and
+	/// collision checks the search uses, stopping at the goal or the first failure
+	///
+	/// Once the actions run out the player keeps idling, so a path that only needs to
+	/// coast the rest of the way still counts as reaching the goal.
+	pub fn validate_path(
+		&self, start: State, actions: &[(Action, f32)], goal_x: f32,
+	) -> PathValidation {
+		let dt = self.dt();
+		let frame_count = |duration: f32| ((duration / dt).round() as usize).max(1);
+
+		let frames = actions
+			.iter()
+			.flat_map(|&(action, duration)| {
+				iter::once(action).chain(iter::repeat_n(Action::None, frame_count(duration) - 1))
+			})
+			.chain(iter::repeat_n(Action::None, MAX_TRAILING_FRAMES));
+
+		let mut state = start;
+		let mut frame = 0;
+
+		for action in frames {
+			if state.position.x >= goal_x {
+				return PathValidation::Ok {
+					final_x: state.position.x,
+					frame,
+				};
+			}
+
+			state = self.simulate_step(&state, action);
+			frame += 1;
+
+			if self.out_of_bounds(&state) {
+				return PathValidation::Failed {
+					frame,
+					reason: FailureReason::FellOut,
+					state,
+				};
+			}
+
+			if let Some((object_id, kind)) = self.collides_detailed(&state) {
+				return PathValidation::Failed {
+					frame,
+					reason: FailureReason::Collision { object_id, kind },
+					state,
+				};
+			}
+		}
+
+		if state.position.x >= goal_x {
+			return PathValidation::Ok {
+				final_x: state.position.x,
+				frame,
+			};
+		}
+
+		PathValidation::Failed {
+			frame,
+			reason: FailureReason::Stalled,
+			state,
+		}
+	}
+}
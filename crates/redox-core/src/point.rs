@@ -0,0 +1,6 @@
+/// A world position at the public boundary, so callers don't have to build positions
+/// with the same glam version this crate uses
+///
+/// Anything taking `impl Into<Point>` also accepts `(x, y)` and `[x, y]`, and with the
+/// default `glam` feature a `glam::Vec2` as before.
+pub type Point = crate::vec2::Vec2;
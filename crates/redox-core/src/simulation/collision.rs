@@ -1,10 +1,13 @@
-use glam::Vec2;
+use std::fmt;
+
+use tracing::instrument;
 
 use crate::{
 	config::PhysicsParams,
-	game_object::{GameObject, GameObjectType, HitboxShape, OBB2D},
+	game_object::{GameObject, GameObjectType, HitboxShape, OBB2D, bounds::closest_on_segment},
 	simulation::spatial_grid::SpatialGrid,
-	state::{GameMode, State},
+	state::State,
+	vec2::Vec2,
 };
 
 pub fn circle_rect_intersects(
@@ -24,40 +27,160 @@ pub fn circle_rect_intersects(
 	dist_sq <= radius * radius
 }
 
+/// Whether the capsule around segment `a`-`b` overlaps an axis-aligned rectangle
+pub fn capsule_rect_intersects(
+	a: Vec2, b: Vec2, radius: f32, rect_center: Vec2, rect_w: f32, rect_h: f32,
+) -> bool {
+	let half = Vec2::new(rect_w / 2.0, rect_h / 2.0);
+	let min = rect_center - half;
+	let max = rect_center + half;
+
+	if segment_crosses_rect(a, b, min, max) {
+		return true;
+	}
+
+	// Apart from crossing, the closest pair always involves an endpoint of the segment or
+	// a corner of the rectangle
+	let radius_sq = radius * radius;
+	let endpoint_hit = [a, b]
+		.into_iter()
+		.any(|p| p.clamp(min, max).distance_squared(p) <= radius_sq);
+	let corner_hit = [min, max, Vec2::new(min.x, max.y), Vec2::new(max.x, min.y)]
+		.into_iter()
+		.any(|corner| closest_on_segment(corner, a, b).distance_squared(corner) <= radius_sq);
+
+	endpoint_hit || corner_hit
+}
+
+/// Liang-Barsky clip of segment `a`-`b` against the box `min`-`max`
+fn segment_crosses_rect(a: Vec2, b: Vec2, min: Vec2, max: Vec2) -> bool {
+	let d = b - a;
+	let mut t_enter = 0.0f32;
+	let mut t_exit = 1.0f32;
+
+	for (p, q) in [
+		(-d.x, a.x - min.x),
+		(d.x, max.x - a.x),
+		(-d.y, a.y - min.y),
+		(d.y, max.y - a.y),
+	] {
+		if p == 0.0 {
+			if q < 0.0 {
+				return false;
+			}
+			continue;
+		}
+
+		let t = q / p;
+		if p < 0.0 {
+			t_enter = t_enter.max(t);
+		} else {
+			t_exit = t_exit.min(t);
+		}
+
+		if t_enter > t_exit {
+			return false;
+		}
+	}
+
+	true
+}
+
+/// What a deadly collision was with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollisionKind {
+	Hazard,
+	Sawblade,
+	/// Ran into the side of a solid block
+	SolidWall,
+	/// Hit the top or bottom face of a solid block too deep to count as standing on it
+	Surface,
+}
+
+impl fmt::Display for CollisionKind {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			CollisionKind::Hazard => "hazard",
+			CollisionKind::Sawblade => "sawblade",
+			CollisionKind::SolidWall => "solid wall",
+			CollisionKind::Surface => "surface",
+		})
+	}
+}
+
+/// Id of the first object that kills the player in `state`
 pub fn collides_info(
 	state: &State, objects: &[GameObject], grid: &SpatialGrid, params: &PhysicsParams,
 ) -> Option<i32> {
-	let player_obb = OBB2D::new(
-		state.position,
-		params.player_width,
-		params.player_height,
-		0.0,
-	);
+	collides_detailed(state, objects, grid, params).map(|(id, _)| id)
+}
+
+/// Like `collides_info`, but also reports what kind of collision it was
+pub fn collides_detailed(
+	state: &State, objects: &[GameObject], grid: &SpatialGrid, params: &PhysicsParams,
+) -> Option<(i32, CollisionKind)> {
+	colliding_object(state, objects, grid, params).map(|(index, kind)| (objects[index].id, kind))
+}
+
+/// Index into `objects` of the first object that kills the player in `state`
+#[instrument(level = "trace", skip_all)]
+pub fn colliding_object(
+	state: &State, objects: &[GameObject], grid: &SpatialGrid, params: &PhysicsParams,
+) -> Option<(usize, CollisionKind)> {
+	let (player_width, player_height) = params.player_size(state.mini);
+	let player_obb = OBB2D::new(state.position, player_width, player_height, 0.0);
+
+	let player_min_x = state.position.x - player_width * 0.5;
+	let player_max_x = state.position.x + player_width * 0.5;
 
+	// Objects sit in every cell their bounds overlap, so a cell of margin either side
+	// is plenty
+	let margin = 2.0 * grid.cell_size();
 	for obj_idx in grid.query(
 		state.position,
-		params.player_width + 400.0,
-		params.player_height + 400.0,
+		player_width + margin,
+		player_height + margin,
 	) {
 		let obj = &objects[obj_idx];
 
-		if (obj.position.x - state.position.x).abs() > 200.0 {
+		// Cells are coarse, skip anything that isn't level with the player before the
+		// exact shape tests
+		let (min, max) = obj.aabb();
+		if max.x < player_min_x || min.x > player_max_x {
 			continue;
 		}
 
 		let is_colliding = match obj.hitbox_shape {
 			HitboxShape::Circle => {
-				let radius = obj.width / 2.0;
+				let radius = obj.width / 2.0 * params.hazard_leniency;
 				circle_rect_intersects(
 					obj.position,
 					radius,
 					state.position,
-					params.player_width,
-					params.player_height,
+					player_width,
+					player_height,
 				)
 			}
+			HitboxShape::Capsule => obj.capsule_segment().is_some_and(|(a, b, radius)| {
+				capsule_rect_intersects(
+					a,
+					b,
+					radius * params.hazard_leniency,
+					state.position,
+					player_width,
+					player_height,
+				)
+			}),
 			HitboxShape::Rectangle => {
-				if let Some(obj_obb) = &obj.obb {
+				if obj.object_type.is_hazard() {
+					let hazard_obb = OBB2D::new(
+						obj.position,
+						obj.width * params.hazard_hitbox_scale,
+						obj.height * params.hazard_hitbox_scale,
+						obj.rotation,
+					);
+					player_obb.overlaps(&hazard_obb)
+				} else if let Some(obj_obb) = &obj.obb {
 					player_obb.overlaps(obj_obb)
 				} else {
 					false
@@ -66,41 +189,53 @@ pub fn collides_info(
 		};
 
 		if is_colliding {
-			if matches!(obj.object_type, GameObjectType::Sawblade) {
-				return Some(obj.id);
-			}
-
-			if matches!(obj.object_type, GameObjectType::Hazard) {
-				return Some(obj.id);
+			if obj.object_type.is_hazard() {
+				let kind = if obj.object_type == GameObjectType::Sawblade {
+					CollisionKind::Sawblade
+				} else {
+					CollisionKind::Hazard
+				};
+				return Some((obj_idx, kind));
 			}
 
-			if matches!(obj.object_type, GameObjectType::Solid) {
-				let obj_top = obj.position.y + obj.height * 0.5;
-				let obj_bottom = obj.position.y - obj.height * 0.5;
+			if params.is_solid(obj.object_type) {
+				// The hitbox's extent along the world axes, so a rotated block's surface is
+				// its highest or lowest corner rather than where its unrotated face would be
+				let (obj_top, obj_bottom) = (max.y, min.y);
+				let (obj_left, obj_right) = (min.x, max.x);
 
-				let obj_left = obj.position.x - obj.width * 0.5;
-				let obj_right = obj.position.x + obj.width * 0.5;
+				let player_top = state.position.y + player_height * 0.5;
+				let player_bottom = state.position.y - player_height * 0.5;
 
-				let player_top = state.position.y + params.player_height * 0.5;
-				let player_bottom = state.position.y - params.player_height * 0.5;
+				let player_left = state.position.x - player_width * 0.5;
+				let player_right = state.position.x + player_width * 0.5;
 
-				let player_left = state.position.x - params.player_width * 0.5;
-				let player_right = state.position.x + params.player_width * 0.5;
+				let h_overlap = player_right.min(obj_right) - player_left.max(obj_left);
+				let v_overlap = player_top.min(obj_top) - player_bottom.max(obj_bottom);
 
-				// Ship mode: crash on side collisions, but allow grazing top/bottom surfaces
-				if state.mode == GameMode::Ship {
-					let h_overlap = player_right.min(obj_right) - player_left.max(obj_left);
-					let v_overlap = player_top.min(obj_top) - player_bottom.max(obj_bottom);
+				// Entering through a face shows up as the shallower overlap
+				let kind = if v_overlap < h_overlap {
+					CollisionKind::Surface
+				} else {
+					CollisionKind::SolidWall
+				};
 
+				// Ship and swing: crash on side collisions, but allow grazing top/bottom surfaces.
+				// The allowances scale with the hitbox, so a smaller player grazes as closely
+				// relative to its size as a full-size one
+				if state.mode.is_flying() {
+					let edge_margin = player_height / 6.0;
 					let player_center_y = state.position.y;
-					let is_above_obj = player_center_y >= obj_top - 5.0;
-					let is_below_obj = player_center_y <= obj_bottom + 5.0;
+					let is_above_obj = player_center_y >= obj_top - edge_margin;
+					let is_below_obj = player_center_y <= obj_bottom + edge_margin;
 
-					if (is_above_obj || is_below_obj) && v_overlap < 15.0 && h_overlap > 5.0 {
+					let shallow = v_overlap < player_height * 0.5;
+					let alongside = h_overlap > player_width / 6.0;
+					if (is_above_obj || is_below_obj) && shallow && alongside {
 						continue;
 					}
 
-					return Some(obj.id);
+					return Some((obj_idx, kind));
 				}
 
 				// Cube mode: check surface zone logic
@@ -110,17 +245,11 @@ pub fn collides_info(
 					(player_bottom, obj_top)
 				};
 
-				let is_in_surface_zone = if state.gravity_flipped {
-					player_feet <= surface_level + 5.0 && player_feet >= surface_level - 5.0
-				} else {
-					player_feet >= surface_level - 5.0 && player_feet <= surface_level + 5.0
-				};
-
-				if is_in_surface_zone {
+				if (player_feet - surface_level).abs() <= 5.0 {
 					continue;
 				}
 
-				return Some(obj.id);
+				return Some((obj_idx, kind));
 			}
 
 			if matches!(obj.object_type, GameObjectType::Unknown) {
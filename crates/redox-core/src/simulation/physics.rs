@@ -1,6 +1,7 @@
 use crate::{
 	config::PhysicsParams,
 	state::{Action, GameMode, State},
+	vec2::Vec2,
 };
 
 pub fn simulate_step(state: &State, action: Action, params: &PhysicsParams) -> State {
@@ -12,24 +13,68 @@ pub fn simulate_step(state: &State, action: Action, params: &PhysicsParams) -> S
 		Action::None => {} // Keep current state
 	}
 
-	let gravity_mult = if state.gravity_flipped { -1.0 } else { 1.0 };
+	let direction = if state.mirrored { -1.0 } else { 1.0 };
+
+	// Letting go ends a dash, otherwise it carries straight on along the ring's angle
+	if state.dashing && !new_state.pressing {
+		new_state.dashing = false;
+	}
+	if new_state.dashing {
+		new_state.position += Vec2::new(
+			params.player_speeds[state.speed] * direction,
+			new_state.vy * params.vertical_dt_scale,
+		) * params.dt();
+		new_state.on_ground = false;
+		return new_state;
+	}
+
+	// A swing tap switches which way is down before gravity applies
+	if state.mode == GameMode::Swing && action == Action::Press {
+		new_state.gravity_flipped = !state.gravity_flipped;
+	}
+
+	let gravity_mult = if new_state.gravity_flipped { -1.0 } else { 1.0 };
 	let effective_gravity = params.gravities[state.speed] * gravity_mult;
 
 	match state.mode {
-		GameMode::Cube => {
-			if action == Action::Press && new_state.on_ground {
+		GameMode::Cube | GameMode::Spider | GameMode::Robot => {
+			// Spider taps are resolved by the pathfinder, which can see the level
+			if state.mode == GameMode::Cube && action == Action::Press && new_state.on_ground {
 				new_state.vy = params.jump_velocities[state.speed] * gravity_mult;
 				new_state.on_ground = false;
 			}
 
-			new_state.vy += effective_gravity * params.dt;
+			if state.mode == GameMode::Robot {
+				let hold_window = (params.robot_max_hold / params.dt())
+					.round()
+					.min(u8::MAX as f32) as u8;
+
+				if action == Action::Press && new_state.on_ground {
+					new_state.vy = params.robot_jump_velocity * gravity_mult;
+					new_state.on_ground = false;
+					new_state.jump_hold_frames = 1;
+				} else if new_state.pressing
+					&& new_state.jump_hold_frames > 0
+					&& new_state.jump_hold_frames < hold_window
+				{
+					// Holding keeps the robot rising at its launch speed
+					new_state.vy = params.robot_jump_velocity * gravity_mult;
+					new_state.jump_hold_frames += 1;
+				} else {
+					new_state.jump_hold_frames = 0;
+				}
+			}
+
+			new_state.vy += effective_gravity * params.dt();
 			new_state.vy =
 				(new_state.vy * params.vy_quantize_step).round() / params.vy_quantize_step;
 
-			new_state.position.y += new_state.vy * params.dt * params.vertical_dt_scale;
+			new_state.position.y += new_state.vy * params.dt() * params.vertical_dt_scale;
 
-			if !new_state.on_ground {
-				new_state.rotation -= 360.0 * params.dt * gravity_mult;
+			if state.mode != GameMode::Cube {
+				new_state.rotation = 0.0;
+			} else if !new_state.on_ground {
+				new_state.rotation -= 360.0 * params.dt() * gravity_mult;
 			} else {
 				new_state.rotation = (new_state.rotation / 90.0).round() * 90.0;
 			}
@@ -41,58 +86,32 @@ pub fn simulate_step(state: &State, action: Action, params: &PhysicsParams) -> S
 				if (gravity_mult > 0.0 && new_state.vy <= threshold)
 					|| (gravity_mult < 0.0 && new_state.vy >= threshold)
 				{
-					1397.0491 * gravity_mult
+					params.ship_accels[0] * gravity_mult
 				} else {
-					1_117.643_3 * gravity_mult
+					params.ship_accels[1] * gravity_mult
 				}
 			} else if (gravity_mult > 0.0 && new_state.vy >= threshold)
 				|| (gravity_mult < 0.0 && new_state.vy <= threshold)
 			{
-				-1341.1719 * gravity_mult
+				-params.ship_accels[2] * gravity_mult
 			} else {
-				-894.114_6 * gravity_mult
+				-params.ship_accels[3] * gravity_mult
 			};
 
-			new_state.vy += effective_accel * params.dt;
-			new_state.vy = new_state.vy.clamp(-800.0, 800.0);
+			new_state.vy += effective_accel * params.dt();
+			let max_vy = params.ship_vy_clamp[state.speed];
+			new_state.vy = new_state.vy.clamp(-max_vy, max_vy);
 			new_state.vy =
 				(new_state.vy * params.vy_quantize_step).round() / params.vy_quantize_step;
 
-			new_state.position.y += new_state.vy * params.dt * params.vertical_dt_scale;
-
-			if new_state.ceiling < f32::MAX / 2.0 {
-				let half_height = params.player_height * 0.5;
-				let player_top = new_state.position.y + half_height;
-				let player_bottom = new_state.position.y - half_height;
-
-				if state.gravity_flipped {
-					if player_bottom < new_state.floor {
-						if new_state.vy < 0.0 {
-							new_state.vy = 0.0;
-						}
-						new_state.position.y = new_state.floor + half_height;
-					}
-					if player_top > new_state.ceiling {
-						if new_state.vy > 0.0 {
-							new_state.vy = 0.0;
-						}
-						new_state.position.y = new_state.ceiling - half_height;
-					}
-				} else {
-					if player_top > new_state.ceiling {
-						if new_state.vy > 0.0 {
-							new_state.vy = 0.0;
-						}
-						new_state.position.y = new_state.ceiling - half_height;
-					}
-					if player_bottom < new_state.floor {
-						if new_state.vy < 0.0 {
-							new_state.vy = 0.0;
-						}
-						new_state.position.y = new_state.floor + half_height;
-					}
-				}
-			}
+			new_state.position.y += new_state.vy * params.dt() * params.vertical_dt_scale;
+
+			clamp_to_bounds(
+				&mut new_state,
+				state.gravity_flipped,
+				params.ship_boundary_restitution,
+				params,
+			);
 
 			let target_rotation = (new_state.vy / 8.0).clamp(-45.0, 45.0) * gravity_mult;
 			new_state.rotation = target_rotation;
@@ -100,9 +119,69 @@ pub fn simulate_step(state: &State, action: Action, params: &PhysicsParams) -> S
 			// Ship is never "on_ground" in the cube sense
 			new_state.on_ground = false;
 		}
+		GameMode::Swing => {
+			new_state.vy += effective_gravity * params.dt();
+			new_state.vy = new_state
+				.vy
+				.clamp(-params.swing_max_velocity, params.swing_max_velocity);
+			new_state.vy =
+				(new_state.vy * params.vy_quantize_step).round() / params.vy_quantize_step;
+
+			new_state.position.y += new_state.vy * params.dt() * params.vertical_dt_scale;
+
+			let flipped = new_state.gravity_flipped;
+			clamp_to_bounds(&mut new_state, flipped, 0.0, params);
+
+			// Nose follows the direction of travel
+			new_state.rotation = (new_state.vy / 8.0).clamp(-45.0, 45.0);
+			new_state.on_ground = false;
+		}
 	}
 
-	new_state.position.x += params.player_speeds[state.speed] * params.dt;
+	new_state.position.x += params.player_speeds[state.speed] * direction * params.dt();
 
 	new_state
 }
+
+/// Keeps a flying player between its floor and ceiling, stopping it against either
+///
+/// A player moving into a boundary keeps `restitution` of its speed, turned back
+/// away from it. Only the ceiling holds in a level without ground.
+fn clamp_to_bounds(
+	state: &mut State, gravity_flipped: bool, restitution: f32, params: &PhysicsParams,
+) {
+	if state.ceiling < f32::MAX / 2.0 {
+		let half_height = params.player_size(state.mini).1 * 0.5;
+		let player_top = state.position.y + half_height;
+		let player_bottom = state.position.y - half_height;
+		let has_floor = params.ground_y.is_some();
+
+		if gravity_flipped {
+			if has_floor && player_bottom < state.floor {
+				if state.vy < 0.0 {
+					state.vy *= -restitution;
+				}
+				state.position.y = state.floor + half_height;
+			}
+			if player_top > state.ceiling {
+				if state.vy > 0.0 {
+					state.vy *= -restitution;
+				}
+				state.position.y = state.ceiling - half_height;
+			}
+		} else {
+			if player_top > state.ceiling {
+				if state.vy > 0.0 {
+					state.vy *= -restitution;
+				}
+				state.position.y = state.ceiling - half_height;
+			}
+			if has_floor && player_bottom < state.floor {
+				if state.vy < 0.0 {
+					state.vy *= -restitution;
+				}
+				state.position.y = state.floor + half_height;
+			}
+		}
+	}
+}
@@ -1,89 +1,135 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+	cell::{Cell, RefCell},
+	collections::HashMap,
+};
 
-use glam::Vec2;
-
-use crate::game_object::GameObject;
+use crate::{game_object::GameObject, vec2::Vec2};
 
 type Cells = HashMap<(i32, i32), Vec<usize>>;
 
 pub struct SpatialGrid {
 	cell_size: f32,
 	cells: Cells,
+	/// Per-object query generation that last returned it, so `query` can dedup without
+	/// allocating
+	stamps: RefCell<Vec<u32>>,
+	generation: Cell<u32>,
 }
 
 impl SpatialGrid {
 	pub fn new(objects: &[GameObject], cell_size: f32) -> Self {
-		let mut cells: Cells = HashMap::new();
+		let mut grid = Self {
+			cell_size,
+			cells: HashMap::new(),
+			stamps: RefCell::new(vec![0; objects.len()]),
+			generation: Cell::new(0),
+		};
 
 		for (idx, obj) in objects.iter().enumerate() {
-			// Get the bounding box of the object
-			let (min_x, min_y, max_x, max_y) = if let Some(obb) = &obj.obb {
-				let mut min_x = f32::MAX;
-				let mut min_y = f32::MAX;
-				let mut max_x = f32::MIN;
-				let mut max_y = f32::MIN;
-				for corner in &obb.corners {
-					min_x = min_x.min(corner.x);
-					min_y = min_y.min(corner.y);
-					max_x = max_x.max(corner.x);
-					max_y = max_y.max(corner.y);
-				}
-				(min_x, min_y, max_x, max_y)
-			} else {
-				// Circular object or fallback
-				let radius = obj.width * 0.5;
-				(
-					obj.position.x - radius,
-					obj.position.y - radius,
-					obj.position.x + radius,
-					obj.position.y + radius,
-				)
-			};
-
-			let start_x = (min_x / cell_size).floor() as i32;
-			let end_x = (max_x / cell_size).floor() as i32;
-			let start_y = (min_y / cell_size).floor() as i32;
-			let end_y = (max_y / cell_size).floor() as i32;
-
-			for cx in start_x..=end_x {
-				for cy in start_y..=end_y {
-					cells.entry((cx, cy)).or_default().push(idx);
-				}
-			}
+			grid.insert(idx, obj.aabb());
 		}
 
-		Self { cell_size, cells }
+		grid
 	}
 
-	pub fn query(
-		&self, position: Vec2, width: f32, height: f32,
-	) -> impl Iterator<Item = usize> + '_ {
-		let half_w = width * 0.5;
-		let half_h = height * 0.5;
+	/// Drops every cell and re-inserts `objects` from their current bounds
+	pub fn clear_and_rebuild(&mut self, objects: &[GameObject]) {
+		self.cells.clear();
 
-		let min_x = position.x - half_w;
-		let max_x = position.x + half_w;
+		for (idx, obj) in objects.iter().enumerate() {
+			self.insert(idx, obj.aabb());
+		}
+	}
+
+	/// Adds `idx` to every cell overlapped by `aabb`
+	pub fn insert(&mut self, idx: usize, aabb: (Vec2, Vec2)) {
+		let stamps = self.stamps.get_mut();
+		if idx >= stamps.len() {
+			stamps.resize(idx + 1, 0);
+		}
 
-		let min_y = position.y - half_h;
-		let max_y = position.y + half_h;
+		let (xs, ys) = self.cell_range(aabb.0, aabb.1);
 
-		let start_x = (min_x / self.cell_size).floor() as i32;
-		let end_x = (max_x / self.cell_size).floor() as i32;
+		for cx in xs {
+			for cy in ys.clone() {
+				self.cells.entry((cx, cy)).or_default().push(idx);
+			}
+		}
+	}
 
-		let start_y = (min_y / self.cell_size).floor() as i32;
-		let end_y = (max_y / self.cell_size).floor() as i32;
+	/// Removes `idx` from every cell overlapped by `aabb`, emptied cells are dropped
+	pub fn remove(&mut self, idx: usize, aabb: (Vec2, Vec2)) {
+		let (xs, ys) = self.cell_range(aabb.0, aabb.1);
 
-		let mut unique_indices = HashSet::new();
-		for cx in start_x..=end_x {
-			for cy in start_y..=end_y {
-				if let Some(indices) = self.cells.get(&(cx, cy)) {
-					for &idx in indices {
-						unique_indices.insert(idx);
+		for cx in xs {
+			for cy in ys.clone() {
+				if let Some(indices) = self.cells.get_mut(&(cx, cy)) {
+					indices.retain(|&i| i != idx);
+					if indices.is_empty() {
+						self.cells.remove(&(cx, cy));
 					}
 				}
 			}
 		}
+	}
+
+	/// Moves `idx` from the cells under `old_aabb` to the ones under `new_aabb`
+	pub fn update_object(&mut self, idx: usize, old_aabb: (Vec2, Vec2), new_aabb: (Vec2, Vec2)) {
+		if self.cell_range(old_aabb.0, old_aabb.1) == self.cell_range(new_aabb.0, new_aabb.1) {
+			return;
+		}
+
+		self.remove(idx, old_aabb);
+		self.insert(idx, new_aabb);
+	}
+
+	/// Side length of a cell in world units
+	pub fn cell_size(&self) -> f32 {
+		self.cell_size
+	}
+
+	pub fn query(
+		&self, position: Vec2, width: f32, height: f32,
+	) -> impl Iterator<Item = usize> + '_ {
+		let half = Vec2::new(width * 0.5, height * 0.5);
+		let (xs, ys) = self.cell_range(position - half, position + half);
+
+		let generation = self.next_generation();
+
+		xs.flat_map(move |cx| ys.clone().map(move |cy| (cx, cy)))
+			.filter_map(|cell| self.cells.get(&cell))
+			.flatten()
+			.copied()
+			.filter(move |&idx| {
+				let mut stamps = self.stamps.borrow_mut();
+				let seen = stamps[idx] == generation;
+				stamps[idx] = generation;
+				!seen
+			})
+	}
+
+	fn next_generation(&self) -> u32 {
+		let generation = self.generation.get().wrapping_add(1);
+
+		// Stale stamps could match again after wrapping around
+		if generation == 0 {
+			self.stamps.borrow_mut().fill(0);
+			self.generation.set(1);
+			return 1;
+		}
+
+		self.generation.set(generation);
+		generation
+	}
+
+	fn cell_range(
+		&self, min: Vec2, max: Vec2,
+	) -> (std::ops::RangeInclusive<i32>, std::ops::RangeInclusive<i32>) {
+		let start_x = (min.x / self.cell_size).floor() as i32;
+		let end_x = (max.x / self.cell_size).floor() as i32;
+		let start_y = (min.y / self.cell_size).floor() as i32;
+		let end_y = (max.y / self.cell_size).floor() as i32;
 
-		unique_indices.into_iter()
+		(start_x..=end_x, start_y..=end_y)
 	}
 }
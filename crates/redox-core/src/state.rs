@@ -1,19 +1,31 @@
-use glam::Vec2;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+use crate::vec2::Vec2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum GameMode {
 	Cube,
 	Ship,
+	Spider,
+	Robot,
+	Swing,
+}
+
+impl GameMode {
+	/// Ship and swing fly between a floor and ceiling instead of landing on blocks
+	pub fn is_flying(self) -> bool {
+		matches!(self, Self::Ship | Self::Swing)
+	}
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Action {
 	None,    // No change to press state
 	Press,   // Toggle press ON (start holding)
 	Release, // Toggle press OFF (stop holding)
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct State {
 	pub position: Vec2,
 	pub vy: f32,
@@ -29,69 +41,256 @@ pub struct State {
 	pub pressing: bool,
 	/// Speed index (0=0.5x, 1=1x, 2=2x, 3=3x, 4=4x)
 	pub speed: usize,
+	/// Whether the player is mini-sized (set by size portals)
+	pub mini: bool,
+	/// Whether horizontal travel is reversed (set by mirror portals)
+	pub mirrored: bool,
+	/// Ticks the current robot jump has been held for, 0 once it can no longer be extended
+	pub jump_hold_frames: u8,
+	/// Held dash from a dash ring, moving at a fixed `vy` without gravity until release
+	/// or a landing
+	pub dashing: bool,
+	/// Index of the pad or teleport portal that last fired, ignored until the player
+	/// has left it so a multi-tick overlap only fires it once
+	pub last_pad: Option<u32>,
+	/// Index of the ring the player last clicked, which can't be clicked again until the
+	/// player has left it
+	pub last_orb: Option<u32>,
+	/// The second player while a dual portal is active
+	pub dual: Option<DualPlayer>,
 }
 
 impl Eq for State {}
 
-// We use a bit-packed u128 for the StateKey to speed up hashing and comparison
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct StateKey(pub u128);
+/// The vertical half of the second player in dual mode
+///
+/// Both players share x, speed, mode and input, so only what can differ between them
+/// is tracked here.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DualPlayer {
+	pub y: f32,
+	pub vy: f32,
+	pub on_ground: bool,
+	pub rotation: f32,
+	pub gravity_flipped: bool,
+	pub jump_hold_frames: u8,
+	pub last_pad: Option<u32>,
+	pub last_orb: Option<u32>,
+}
+
+impl State {
+	/// The second player as a standalone state that can be simulated like the first
+	pub fn dual_state(&self) -> Option<State> {
+		let dual = self.dual?;
 
-impl StateKey {
-	pub fn from_state(state: &State, x_quant: f32, y_quant: f32, vy_quant: f32) -> Self {
-		let xi = (state.position.x / x_quant).floor() as i32;
-		let yi = (state.position.y / y_quant).floor() as i32;
-		let vyi = (state.vy / vy_quant).floor() as i32;
+		Some(State {
+			position: Vec2::new(self.position.x, dual.y),
+			vy: dual.vy,
+			on_ground: dual.on_ground,
+			rotation: dual.rotation,
+			gravity_flipped: dual.gravity_flipped,
+			jump_hold_frames: dual.jump_hold_frames,
+			last_pad: dual.last_pad,
+			last_orb: dual.last_orb,
+			dual: None,
+			..*self
+		})
+	}
+
+	/// Stores the vertical half of `state` as the second player
+	pub fn set_dual_state(&mut self, state: &State) {
+		self.dual = Some(DualPlayer {
+			y: state.position.y,
+			vy: state.vy,
+			on_ground: state.on_ground,
+			rotation: state.rotation,
+			gravity_flipped: state.gravity_flipped,
+			jump_hold_frames: state.jump_hold_frames,
+			last_pad: state.last_pad,
+			last_orb: state.last_orb,
+		});
+	}
+}
+
+// We use a bit-packed u128 for the StateKey to speed up hashing and comparison, the first
+// u64 holds the second player in dual mode and the last holds both players' pad cooldowns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct StateKey(pub u128, pub u64, pub u64);
+
+/// One of the quantized values packed into a `StateKey`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyField {
+	X,
+	Y,
+	Vy,
+	Ceiling,
+}
+
+/// Bits given to each quantized field of a `StateKey`
+///
+/// Fields are packed in order from bit 0 and the flags take the `StateKey::FLAG_BITS`
+/// after them, so very long levels can trade y or velocity precision for more x bits.
+/// Values are stored as two's complement and wrap when they don't fit, aliasing with
+/// other states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyLayout {
+	pub x_bits: u32,
+	pub y_bits: u32,
+	pub vy_bits: u32,
+	pub ceiling_bits: u32,
+}
+
+impl Default for KeyLayout {
+	fn default() -> Self {
+		Self {
+			x_bits: 32,
+			y_bits: 32,
+			vy_bits: 24,
+			ceiling_bits: 16,
+		}
+	}
+}
+
+impl KeyLayout {
+	/// Whether every field has between 2 and 64 bits and they all fit beside the flags
+	pub fn is_valid(&self) -> bool {
+		let widths = self.widths();
+		widths.iter().all(|bits| (2..=64).contains(bits))
+			&& widths.iter().sum::<u32>() + StateKey::FLAG_BITS <= 128
+	}
 
-		let ceiling_i = if state.ceiling >= f32::MAX / 2.0 {
-			10000 // Large constant for cube mode
+	/// The first field of `state` whose quantized value doesn't fit its bits, and that
+	/// value
+	pub fn overflow(
+		&self, state: &State, x_quant: f32, y_quant: f32, vy_quant: f32,
+	) -> Option<(KeyField, i64)> {
+		let fields = [KeyField::X, KeyField::Y, KeyField::Vy, KeyField::Ceiling];
+		let values = self.quantize(state, x_quant, y_quant, vy_quant);
+
+		fields
+			.into_iter()
+			.zip(values)
+			.zip(self.widths())
+			.find(|&((_, value), bits)| !fits(value, bits))
+			.map(|((field, value), _)| (field, value))
+	}
+
+	fn widths(&self) -> [u32; 4] {
+		[self.x_bits, self.y_bits, self.vy_bits, self.ceiling_bits]
+	}
+
+	/// Quantized x, y, vy and ceiling, in packing order
+	fn quantize(&self, state: &State, x_quant: f32, y_quant: f32, vy_quant: f32) -> [i64; 4] {
+		let ceiling = if state.ceiling >= f32::MAX / 2.0 {
+			// Unbounded in cube mode, kept apart from any real ceiling
+			max_value(self.ceiling_bits)
 		} else {
-			(state.ceiling / 30.0).floor() as i32
+			(state.ceiling / 30.0).floor() as i64
 		};
 
+		[
+			(state.position.x / x_quant).floor() as i64,
+			(state.position.y / y_quant).floor() as i64,
+			(state.vy / vy_quant).floor() as i64,
+			ceiling,
+		]
+	}
+}
+
+fn max_value(bits: u32) -> i64 {
+	((1u64 << (bits - 1)) - 1) as i64
+}
+
+/// Whether `value` survives being stored as a `bits`-wide two's complement integer
+fn fits(value: i64, bits: u32) -> bool {
+	bits >= 64 || (-max_value(bits) - 1..=max_value(bits)).contains(&value)
+}
+
+fn mask(bits: u32) -> u128 {
+	(1u128 << bits) - 1
+}
+
+impl StateKey {
+	/// Bits taken by the flags packed after the quantized fields
+	pub const FLAG_BITS: u32 = 21;
+
+	pub fn from_state(state: &State, x_quant: f32, y_quant: f32, vy_quant: f32) -> Self {
+		Self::with_layout(state, x_quant, y_quant, vy_quant, KeyLayout::default())
+	}
+
+	/// Packs `state` using `layout`'s field widths, see `KeyLayout::overflow` for
+	/// finding values that wrapped
+	pub fn with_layout(
+		state: &State, x_quant: f32, y_quant: f32, vy_quant: f32, layout: KeyLayout,
+	) -> Self {
+		debug_assert!(layout.is_valid(), "invalid key layout {layout:?}");
+
+		let mut packed = 0u128;
+		let mut offset = 0;
+		for (value, bits) in layout
+			.quantize(state, x_quant, y_quant, vy_quant)
+			.into_iter()
+			.zip(layout.widths())
+		{
+			packed |= (value as u128 & mask(bits)) << offset;
+			offset += bits;
+		}
+
 		let mode_bit = match state.mode {
 			GameMode::Cube => 0,
 			GameMode::Ship => 1,
+			GameMode::Spider => 2,
+			GameMode::Robot => 3,
+			GameMode::Swing => 4,
 		};
 
-		let mut packed = 0u128;
-		// xi: 32 bits (offset 0)
-		packed |= xi as u32 as u128;
-		// yi: 32 bits (offset 32)
-		packed |= (yi as u32 as u128) << 32;
-		// vyi: 24 bits (offset 64)
-		packed |= ((vyi & 0xFFFFFF) as u128) << 64;
-		// ceiling_i: 16 bits (offset 88)
-		packed |= ((ceiling_i & 0xFFFF) as u128) << 88;
-
-		// Flags: 8 bits (offset 104)
-		if state.on_ground {
-			packed |= 1 << 104;
-		}
+		let mut flags = 0u128;
+		flags |= state.on_ground as u128;
+		flags |= (state.gravity_flipped as u128) << 1;
+		flags |= (state.pressing as u128) << 2;
+		flags |= (state.mini as u128) << 3;
+		flags |= ((mode_bit as u128) & 0x7) << 4;
+		flags |= ((state.speed as u128) & 0x7) << 7;
+		flags |= (state.mirrored as u128) << 10;
+		flags |= (state.jump_hold_frames as u128) << 11;
+		flags |= (state.dashing as u128) << 19;
+		// Only whether a ring is used up, the one in question is whichever the player is
+		// overlapping
+		flags |= (state.last_orb.is_some() as u128) << 20;
+		packed |= flags << offset;
 
-		if state.gravity_flipped {
-			packed |= 1 << 105;
-		}
+		// The second player's quantized height and velocity, zero outside dual mode
+		let mut dual_packed = 0u64;
+		if let Some(dual) = state.dual {
+			let yi = (dual.y / y_quant).floor() as i32;
+			let vyi = (dual.vy / vy_quant).floor() as i32;
 
-		if state.pressing {
-			packed |= 1 << 106;
+			dual_packed |= yi as u32 as u64;
+			dual_packed |= ((vyi & 0xFFFFFF) as u64) << 32;
+			dual_packed |= (dual.on_ground as u64) << 56;
+			dual_packed |= (dual.gravity_flipped as u64) << 57;
+			dual_packed |= 1 << 58;
+			dual_packed |= (dual.last_orb.is_some() as u64) << 59;
 		}
 
-		packed |= (mode_bit as u128) << 107;
-		packed |= ((state.speed as u128) & 0x7) << 108;
+		// Pad indices shifted up by one so no cooldown stays apart from the first pad
+		let pad = |last_pad: Option<u32>| last_pad.map_or(0, |index| index as u64 + 1);
+		let pad_packed = pad(state.last_pad) | pad(state.dual.and_then(|dual| dual.last_pad)) << 32;
 
-		Self(packed)
+		Self(packed, dual_packed, pad_packed)
 	}
 }
 
 // A wrapper for the priority queue that orders by f-score (lowest first)
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Node {
 	pub g: f32, // cost so far (time)
 	pub f: f32, // estimated total cost (g + h)
 	pub state: State,
 	pub parent_index: Option<usize>,
 	pub action: Option<Action>,
+	/// Ticks covered by the edge from the parent, `action` on the first then `None`
+	pub frames: u32,
 }
 
 impl PartialEq for Node {
@@ -0,0 +1,220 @@
+use std::{
+	fmt,
+	ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// A position or offset in world units
+///
+/// The crate's own type rather than `glam::Vec2`, so callers aren't tied to the glam
+/// version this crate was built against. With the default `glam` feature it converts
+/// to and from `glam::Vec2`. Serialized as `[x, y]`, the same as glam's.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(from = "[f32; 2]", into = "[f32; 2]")]
+pub struct Vec2 {
+	pub x: f32,
+	pub y: f32,
+}
+
+impl Vec2 {
+	pub const X: Self = Self::new(1.0, 0.0);
+	pub const Y: Self = Self::new(0.0, 1.0);
+	pub const ZERO: Self = Self::splat(0.0);
+
+	pub const fn new(x: f32, y: f32) -> Self {
+		Self { x, y }
+	}
+
+	pub const fn splat(v: f32) -> Self {
+		Self { x: v, y: v }
+	}
+
+	pub fn dot(self, rhs: Self) -> f32 {
+		self.x * rhs.x + self.y * rhs.y
+	}
+
+	pub fn length_squared(self) -> f32 {
+		self.dot(self)
+	}
+
+	pub fn length(self) -> f32 {
+		self.length_squared().sqrt()
+	}
+
+	pub fn distance(self, rhs: Self) -> f32 {
+		(self - rhs).length()
+	}
+
+	pub fn distance_squared(self, rhs: Self) -> f32 {
+		(self - rhs).length_squared()
+	}
+
+	/// Rotated a quarter turn counterclockwise
+	pub fn perp(self) -> Self {
+		Self::new(-self.y, self.x)
+	}
+
+	/// The z of the 3D cross product, positive when `rhs` is counterclockwise of `self`
+	pub fn perp_dot(self, rhs: Self) -> f32 {
+		self.x * rhs.y - self.y * rhs.x
+	}
+
+	/// Scaled to unit length, or zero if it is too short to have a direction
+	pub fn normalize_or_zero(self) -> Self {
+		let rcp = self.length().recip();
+		if rcp.is_finite() && rcp > 0.0 {
+			self * rcp
+		} else {
+			Self::ZERO
+		}
+	}
+
+	/// Per-component minimum
+	pub fn min(self, rhs: Self) -> Self {
+		Self::new(self.x.min(rhs.x), self.y.min(rhs.y))
+	}
+
+	/// Per-component maximum
+	pub fn max(self, rhs: Self) -> Self {
+		Self::new(self.x.max(rhs.x), self.y.max(rhs.y))
+	}
+
+	/// Per-component clamp between `min` and `max`
+	pub fn clamp(self, min: Self, max: Self) -> Self {
+		self.max(min).min(max)
+	}
+
+	pub fn abs(self) -> Self {
+		Self::new(self.x.abs(), self.y.abs())
+	}
+}
+
+impl fmt::Display for Vec2 {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match f.precision() {
+			Some(p) => write!(f, "[{:.*}, {:.*}]", p, self.x, p, self.y),
+			None => write!(f, "[{}, {}]", self.x, self.y),
+		}
+	}
+}
+
+impl Add for Vec2 {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self {
+		Self::new(self.x + rhs.x, self.y + rhs.y)
+	}
+}
+
+impl Add<f32> for Vec2 {
+	type Output = Self;
+
+	fn add(self, rhs: f32) -> Self {
+		self + Self::splat(rhs)
+	}
+}
+
+impl AddAssign for Vec2 {
+	fn add_assign(&mut self, rhs: Self) {
+		*self = *self + rhs;
+	}
+}
+
+impl Sub for Vec2 {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self {
+		Self::new(self.x - rhs.x, self.y - rhs.y)
+	}
+}
+
+impl Sub<f32> for Vec2 {
+	type Output = Self;
+
+	fn sub(self, rhs: f32) -> Self {
+		self - Self::splat(rhs)
+	}
+}
+
+impl SubAssign for Vec2 {
+	fn sub_assign(&mut self, rhs: Self) {
+		*self = *self - rhs;
+	}
+}
+
+impl Mul<f32> for Vec2 {
+	type Output = Self;
+
+	fn mul(self, rhs: f32) -> Self {
+		Self::new(self.x * rhs, self.y * rhs)
+	}
+}
+
+impl Mul<Vec2> for f32 {
+	type Output = Vec2;
+
+	fn mul(self, rhs: Vec2) -> Vec2 {
+		rhs * self
+	}
+}
+
+impl MulAssign<f32> for Vec2 {
+	fn mul_assign(&mut self, rhs: f32) {
+		*self = *self * rhs;
+	}
+}
+
+impl Div<f32> for Vec2 {
+	type Output = Self;
+
+	fn div(self, rhs: f32) -> Self {
+		Self::new(self.x / rhs, self.y / rhs)
+	}
+}
+
+impl Neg for Vec2 {
+	type Output = Self;
+
+	fn neg(self) -> Self {
+		Self::new(-self.x, -self.y)
+	}
+}
+
+impl From<(f32, f32)> for Vec2 {
+	fn from((x, y): (f32, f32)) -> Self {
+		Self { x, y }
+	}
+}
+
+impl From<[f32; 2]> for Vec2 {
+	fn from([x, y]: [f32; 2]) -> Self {
+		Self { x, y }
+	}
+}
+
+impl From<Vec2> for (f32, f32) {
+	fn from(v: Vec2) -> Self {
+		(v.x, v.y)
+	}
+}
+
+impl From<Vec2> for [f32; 2] {
+	fn from(v: Vec2) -> Self {
+		[v.x, v.y]
+	}
+}
+
+#[cfg(feature = "glam")]
+impl From<glam::Vec2> for Vec2 {
+	fn from(v: glam::Vec2) -> Self {
+		Self { x: v.x, y: v.y }
+	}
+}
+
+#[cfg(feature = "glam")]
+impl From<Vec2> for glam::Vec2 {
+	fn from(v: Vec2) -> Self {
+		glam::Vec2::new(v.x, v.y)
+	}
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use redox_core::{
+	config::{Config, SearchStrategy},
+	formats::level,
+	pathfinder::{Pathfinder, search::StopReason},
+};
+
+const WIDTH: usize = 64;
+
+#[test]
+fn generous_beam_solves_within_its_width() {
+	let mut config = Config::default();
+	config.search.strategy = SearchStrategy::Beam { width: WIDTH };
+	let pathfinder = Pathfinder::with_config(common::objects(common::SPIKES_AND_BLOCK), config);
+
+	let header = level::parse_header(common::SPIKES_AND_BLOCK);
+	let start = pathfinder.initial_state(common::START, &header);
+	let mut session = pathfinder.start_search_from(start, 1100.0);
+
+	while !pathfinder.step_single(&mut session, 1100.0) {
+		assert!(
+			session.open_set.len() <= WIDTH,
+			"{} open after {} expansions",
+			session.open_set.len(),
+			session.nodes_expanded
+		);
+	}
+
+	assert_eq!(session.stop_reason, Some(StopReason::Goal));
+	let end = &session.all_nodes[session.end_index()];
+	assert!(end.state.position.x >= 1100.0);
+}
+
+#[test]
+fn full_beam_is_cut_back_to_its_width_not_below() {
+	let mut config = Config::default();
+	config.search.strategy = SearchStrategy::Beam { width: WIDTH };
+	let pathfinder = Pathfinder::with_config(common::objects(common::SPIKES_AND_BLOCK), config);
+
+	let header = level::parse_header(common::SPIKES_AND_BLOCK);
+	let start = pathfinder.initial_state(common::START, &header);
+	let mut session = pathfinder.start_search_from(start, 1100.0);
+
+	let mut filled = false;
+	while !pathfinder.step_single(&mut session, 1100.0) {
+		let open = session.open_set.len();
+		filled |= open == WIDTH;
+		// An expansion pops one node, so a full beam only shrinks a step at a time
+		assert!(
+			!filled || open > WIDTH / 2,
+			"{open} open after {} expansions",
+			session.nodes_expanded
+		);
+	}
+
+	assert!(filled, "the beam never filled up");
+}
+
+#[test]
+fn beam_keeps_fewer_nodes_open_than_a_star() {
+	let open_peak = |strategy| {
+		let mut config = Config::default();
+		config.search.strategy = strategy;
+		let pathfinder = Pathfinder::with_config(common::objects(common::SPIKES_AND_BLOCK), config);
+		let header = level::parse_header(common::SPIKES_AND_BLOCK);
+		let mut session =
+			pathfinder.start_search_from(pathfinder.initial_state(common::START, &header), 1100.0);
+		pathfinder.step(&mut session, 1100.0);
+		assert_eq!(session.stop_reason, Some(StopReason::Goal));
+		session.open_set_peak
+	};
+
+	let a_star = open_peak(SearchStrategy::AStar);
+	let beam = open_peak(SearchStrategy::Beam { width: WIDTH });
+	assert!(a_star > WIDTH, "A* only peaked at {a_star}");
+	assert!(beam < a_star);
+}
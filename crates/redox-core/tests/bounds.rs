@@ -0,0 +1,30 @@
+mod common;
+
+use std::f32::consts::SQRT_2;
+
+use redox_core::Vec2;
+
+#[test]
+fn rotated_block_aabb_covers_its_corners() {
+	let objects = common::objects("kA2,0;1,1,2,300,3,15,6,45;");
+	let (min, max) = objects[0].aabb();
+
+	// A 30 unit square turned 45 degrees reaches half its diagonal either way
+	let reach = 15.0 * SQRT_2;
+	assert!((min - Vec2::new(300.0 - reach, 15.0 - reach)).length() < 1e-3);
+	assert!((max - Vec2::new(300.0 + reach, 15.0 + reach)).length() < 1e-3);
+}
+
+#[test]
+fn rotated_block_contains_only_points_inside_its_hitbox() {
+	let objects = common::objects("kA2,0;1,1,2,300,3,15,6,45;");
+	let block = &objects[0];
+
+	// Towards a corner, past the unrotated square's edge
+	assert!(block.contains_point(Vec2::new(320.0, 15.0)));
+	assert!(block.contains_point(Vec2::new(300.0, 15.0)));
+
+	// Inside the AABB but beyond a diagonal edge
+	assert!(!block.contains_point(Vec2::new(318.0, 33.0)));
+	assert!(!block.contains_point(Vec2::new(340.0, 15.0)));
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use redox_core::{
+	Vec2, config::Config, pathfinder::Pathfinder, simulation::collision::CollisionKind,
+	state::Action,
+};
+
+/// A breakable block (id 143) resting on the ground, and one floating with its top at
+/// y=105
+const LEVEL: &str = "kA2,0;1,143,2,300,3,15;1,143,2,600,3,90;";
+
+fn pathfinder(breakables_solid: bool) -> Pathfinder {
+	let mut config = Config::default();
+	config.physics.breakables_solid = breakables_solid;
+	Pathfinder::with_config(common::objects(LEVEL), config)
+}
+
+/// Drops a cube onto the floating breakable, returning where it settles
+fn drop_onto_floating(pathfinder: &Pathfinder) -> f32 {
+	let mut state = common::cube_at(Vec2::new(600.0, 125.0));
+	state.speed = 0;
+	for _ in 0..240 {
+		state = pathfinder.simulate_step(&state, Action::None);
+		assert_eq!(pathfinder.collides(&state), None);
+		if state.on_ground {
+			break;
+		}
+	}
+	assert!(state.on_ground);
+	state.position.y
+}
+
+#[test]
+fn breakables_are_passable_by_default() {
+	let pathfinder = pathfinder(false);
+
+	let running_into = common::cube_at(Vec2::new(290.0, 15.0));
+	assert_eq!(pathfinder.collides(&running_into), None);
+
+	// Falls straight through to the ground
+	assert!((drop_onto_floating(&pathfinder) - 15.0).abs() < 0.01);
+}
+
+#[test]
+fn solid_breakables_block_and_can_be_landed_on() {
+	let pathfinder = pathfinder(true);
+
+	let running_into = common::cube_at(Vec2::new(290.0, 15.0));
+	assert_eq!(
+		pathfinder.collides_detailed(&running_into),
+		Some((143, CollisionKind::SolidWall))
+	);
+
+	assert!((drop_onto_floating(&pathfinder) - 120.0).abs() < 0.01);
+}
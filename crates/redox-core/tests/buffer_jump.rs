@@ -0,0 +1,45 @@
+mod common;
+
+use redox_core::{Vec2, config::Config, pathfinder::Pathfinder, state::Action};
+
+/// Six steps four blocks wide, each a block higher than the last, so a held cube has
+/// to jump again on every landing
+fn staircase() -> String {
+	let mut level = String::from(common::FLAT);
+	for step in 0..6 {
+		for column in 0..4 {
+			for row in 0..=step {
+				let x = 315 + step * 120 + column * 30;
+				level += &format!("1,1,2,{x},3,{};", 15 + row * 30);
+			}
+		}
+	}
+	level
+}
+
+/// How far a cube gets holding the button from the start, and whether it died there
+fn hold_through(allow_buffer_jump: bool) -> (f32, bool) {
+	let mut config = Config::default();
+	config.search.allow_buffer_jump = allow_buffer_jump;
+	let pathfinder = Pathfinder::with_config(common::objects(&staircase()), config);
+
+	let mut state = common::cube_at(Vec2::new(0.0, 15.0));
+	state.on_ground = true;
+
+	let (state, collided) = pathfinder.simulate_n(&state, Action::Press, 960);
+	(state.position.x, collided)
+}
+
+#[test]
+fn held_cube_climbs_staircase_with_buffered_jumps() {
+	let (x, collided) = hold_through(true);
+	assert!(!collided, "died at x {x}");
+	assert!(x > 1100.0);
+}
+
+#[test]
+fn held_cube_without_buffering_jumps_once() {
+	let (x, collided) = hold_through(false);
+	assert!(collided);
+	assert!(x < 315.0, "got to x {x}");
+}
@@ -0,0 +1,38 @@
+mod common;
+
+use redox_core::{config::Config, formats::level, pathfinder::Pathfinder};
+
+const LEVEL: &str = "kA2,0;1,8,2,300,3,15;1,1,2,520,3,15;1,8,2,700,3,15;1,8,2,730,3,15;";
+const GOAL_X: f32 = 900.0;
+
+#[test]
+fn resumed_search_matches_uninterrupted_run() {
+	let uninterrupted = common::solve(LEVEL, GOAL_X, Config::default());
+	assert!(uninterrupted.reached_goal);
+
+	let pathfinder = Pathfinder::with_config(common::objects(LEVEL), Config::default());
+	let start = pathfinder.initial_state(common::START, &level::parse_header(LEVEL));
+
+	let mut session = pathfinder.start_search_from(start, GOAL_X);
+	for _ in 0..uninterrupted.nodes_expanded / 2 {
+		assert!(!pathfinder.step_single(&mut session, GOAL_X));
+	}
+
+	let path = std::env::temp_dir().join(format!("redox-checkpoint-{}.bin", std::process::id()));
+	session.save(&path).unwrap();
+	drop(session);
+
+	// A fresh pathfinder, as if the process had restarted
+	let pathfinder = Pathfinder::with_config(common::objects(LEVEL), Config::default());
+	let mut resumed = pathfinder.resume_search(&path).unwrap();
+	std::fs::remove_file(&path).unwrap();
+
+	pathfinder.step(&mut resumed, GOAL_X);
+	let end = resumed
+		.goal_reached_index
+		.expect("resumed search should reach the goal");
+	let resumed_path = pathfinder.reconstruct_path(&resumed.all_nodes, &resumed.all_nodes[end]);
+
+	assert_eq!(resumed.nodes_expanded, uninterrupted.nodes_expanded);
+	assert_eq!(resumed_path, uninterrupted.path);
+}
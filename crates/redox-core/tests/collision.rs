@@ -0,0 +1,396 @@
+mod common;
+
+use redox_core::{
+	Vec2,
+	simulation::collision::{capsule_rect_intersects, circle_rect_intersects},
+};
+
+const PLAYER: f32 = 30.0;
+
+#[test]
+fn capsule_reaches_past_circle_radius() {
+	let center = Vec2::ZERO;
+	let radius = 6.0;
+	let half_length = 12.0;
+
+	// Player edge sits 10 units right of the blade centre, outside the circle
+	let player = Vec2::new(10.0 + PLAYER / 2.0, 0.0);
+
+	assert!(!circle_rect_intersects(
+		center, radius, player, PLAYER, PLAYER
+	));
+	assert!(capsule_rect_intersects(
+		center - Vec2::new(half_length, 0.0),
+		center + Vec2::new(half_length, 0.0),
+		radius,
+		player,
+		PLAYER,
+		PLAYER,
+	));
+}
+
+#[test]
+fn capsule_misses_beside_its_ends() {
+	let a = Vec2::new(-12.0, 0.0);
+	let b = Vec2::new(12.0, 0.0);
+
+	// Above the segment but further than the radius
+	assert!(!capsule_rect_intersects(
+		a,
+		b,
+		6.0,
+		Vec2::new(0.0, 7.0 + PLAYER / 2.0),
+		PLAYER,
+		PLAYER,
+	));
+
+	// Past the rounded end diagonally, where a box around the capsule would still hit
+	let corner_gap = Vec2::splat(5.0);
+	assert!(!capsule_rect_intersects(
+		a,
+		b,
+		6.0,
+		b + corner_gap + Vec2::splat(PLAYER / 2.0),
+		PLAYER,
+		PLAYER,
+	));
+}
+
+#[test]
+fn rotated_capsule_crossing_player_collides() {
+	// A long thin blade passing through the player without any endpoint inside it
+	let a = Vec2::new(-40.0, -40.0);
+	let b = Vec2::new(40.0, 40.0);
+
+	assert!(capsule_rect_intersects(
+		a,
+		b,
+		0.5,
+		Vec2::ZERO,
+		PLAYER,
+		PLAYER
+	));
+}
+
+mod kinds {
+	use redox_core::{
+		Vec2,
+		config::PhysicsParams,
+		formats::level,
+		game_object::GameObject,
+		simulation::{
+			collision::{CollisionKind, collides_detailed},
+			spatial_grid::SpatialGrid,
+		},
+		state::{GameMode, State},
+	};
+
+	use super::common::cube_at;
+
+	/// Collision of a cube at `player` against the single object `id` at `object`
+	fn collide(id: i32, object: Vec2, player: Vec2) -> Option<CollisionKind> {
+		collide_with(&PhysicsParams::default(), id, object, player)
+	}
+
+	fn collide_with(
+		params: &PhysicsParams, id: i32, object: Vec2, player: Vec2,
+	) -> Option<CollisionKind> {
+		collide_state(params, id, object, &cube_at(player))
+	}
+
+	fn collide_state(
+		params: &PhysicsParams, id: i32, object: Vec2, state: &State,
+	) -> Option<CollisionKind> {
+		let level = format!("1,{},2,{},3,{};", id, object.x, object.y);
+		let objects: Vec<GameObject> = level::parse_objects(&level)
+			.iter()
+			.map(GameObject::from_raw)
+			.collect();
+		let grid = SpatialGrid::new(&objects, 128.0);
+
+		collides_detailed(state, &objects, &grid, params).map(|(_, kind)| kind)
+	}
+
+	/// Collision of a ship whose top is `overlap` units into the underside of a block,
+	/// with a hitbox `size` units square
+	fn ship_under_block(size: f32, overlap: f32) -> Option<CollisionKind> {
+		let params = PhysicsParams {
+			player_width: size,
+			player_height: size,
+			..Default::default()
+		};
+		let block = Vec2::new(300.0, 200.0);
+		let ship = State {
+			mode: GameMode::Ship,
+			..cube_at(Vec2::new(300.0, block.y - 15.0 - size * 0.5 + overlap))
+		};
+
+		collide_state(&params, 1, block, &ship)
+	}
+
+	#[test]
+	fn spike_is_hazard() {
+		let spike = Vec2::new(300.0, 15.0);
+		assert_eq!(collide(8, spike, spike), Some(CollisionKind::Hazard));
+	}
+
+	#[test]
+	fn blade_is_sawblade() {
+		let blade = Vec2::new(300.0, 45.0);
+		assert_eq!(
+			collide(88, blade, Vec2::new(290.0, 25.0)),
+			Some(CollisionKind::Sawblade)
+		);
+	}
+
+	#[test]
+	fn ramming_a_block_is_solid_wall() {
+		let block = Vec2::new(300.0, 15.0);
+		assert_eq!(
+			collide(1, block, Vec2::new(280.0, 15.0)),
+			Some(CollisionKind::SolidWall)
+		);
+	}
+
+	#[test]
+	fn hitting_a_block_from_below_is_surface() {
+		let block = Vec2::new(300.0, 75.0);
+		assert_eq!(
+			collide(1, block, Vec2::new(300.0, 48.0)),
+			Some(CollisionKind::Surface)
+		);
+	}
+
+	#[test]
+	fn grazing_a_block_top_is_not_a_collision() {
+		let block = Vec2::new(300.0, 15.0);
+		assert_eq!(collide(1, block, Vec2::new(300.0, 44.0)), None);
+	}
+
+	#[test]
+	fn surface_zone_spans_five_units_either_way_of_a_block_face() {
+		// A cube sunk 4 units into the top survives, 6 units doesn't
+		let block = Vec2::new(300.0, 15.0);
+		assert_eq!(collide(1, block, Vec2::new(300.0, 41.0)), None);
+		assert!(collide(1, block, Vec2::new(300.0, 39.0)).is_some());
+
+		// Flipped, the face is the block's underside
+		let block = Vec2::new(300.0, 75.0);
+		let flipped = |y: f32| State {
+			gravity_flipped: true,
+			..cube_at(Vec2::new(300.0, y))
+		};
+		let params = PhysicsParams::default();
+		assert_eq!(collide_state(&params, 1, block, &flipped(49.0)), None);
+		assert!(collide_state(&params, 1, block, &flipped(51.0)).is_some());
+	}
+
+	#[test]
+	fn ship_grazing_a_ceiling_survives_at_any_size() {
+		// Full size, then mini at 0.6 scale
+		for size in [30.0, 18.0] {
+			assert_eq!(ship_under_block(size, 2.0), None, "size {size}");
+			assert_eq!(ship_under_block(size, size * 0.4), None, "size {size}");
+		}
+	}
+
+	#[test]
+	fn ship_deep_in_a_ceiling_dies_at_any_size() {
+		for size in [30.0, 18.0] {
+			assert_eq!(
+				ship_under_block(size, size * 0.6),
+				Some(CollisionKind::Surface),
+				"size {size}"
+			);
+		}
+	}
+
+	#[test]
+	fn grazing_a_spike_sprite_is_not_a_collision() {
+		// The player's right edge is 2 units into the 30-wide sprite, but well short of
+		// the 6x12 hitbox in its middle
+		let spike = Vec2::new(300.0, 15.0);
+		assert_eq!(collide(8, spike, Vec2::new(272.0, 15.0)), None);
+	}
+
+	#[test]
+	fn hazard_hitbox_scale_forgives_spike_hitbox_edges() {
+		// A unit into the full 6-wide hitbox and a quarter into one scaled by 0.75, but
+		// outside the default half-size one
+		let spike = Vec2::new(300.0, 15.0);
+		let player = Vec2::new(283.0, 15.0);
+		assert_eq!(collide(8, spike, player), None);
+
+		for scale in [1.0, 0.75] {
+			let strict = PhysicsParams {
+				hazard_hitbox_scale: scale,
+				..Default::default()
+			};
+			assert_eq!(
+				collide_with(&strict, 8, spike, player),
+				Some(CollisionKind::Hazard),
+				"scale {scale}"
+			);
+		}
+	}
+
+	#[test]
+	fn hazard_hitbox_scale_leaves_sawblades_to_hazard_leniency() {
+		let blade = Vec2::new(300.0, 15.0);
+		let player = Vec2::new(271.0, 15.0);
+		let strict = PhysicsParams {
+			hazard_leniency: 1.0,
+			hazard_hitbox_scale: 0.1,
+			..Default::default()
+		};
+		assert_eq!(
+			collide_with(&strict, 88, blade, player),
+			Some(CollisionKind::Sawblade)
+		);
+	}
+
+	#[test]
+	fn sawblade_near_miss_survives_the_lenient_radius() {
+		// The player's right edge is 14 units from the blade centre, inside its full
+		// 16.15 radius but outside the default 0.75 scaled one
+		let blade = Vec2::new(300.0, 15.0);
+		let player = Vec2::new(271.0, 15.0);
+		assert_eq!(collide(88, blade, player), None);
+
+		let strict = PhysicsParams {
+			hazard_leniency: 1.0,
+			..Default::default()
+		};
+		assert_eq!(
+			collide_with(&strict, 88, blade, player),
+			Some(CollisionKind::Sawblade)
+		);
+	}
+
+	#[test]
+	fn sawblade_radius_grows_with_its_scale() {
+		// 20 units from the centre, clear of a normal blade but inside a doubled one
+		let player = cube_at(Vec2::new(265.0, 15.0));
+		let params = PhysicsParams::default();
+		for (scale, hit) in [(1.0, false), (2.0, true)] {
+			let level = format!("1,88,2,300,3,15,32,{scale};");
+			let objects: Vec<GameObject> = level::parse_objects(&level)
+				.iter()
+				.map(GameObject::from_raw)
+				.collect();
+			let grid = SpatialGrid::new(&objects, 128.0);
+
+			assert_eq!(
+				collides_detailed(&player, &objects, &grid, &params).is_some(),
+				hit,
+				"scale {scale}"
+			);
+		}
+	}
+
+	#[test]
+	fn hazard_scales_leave_blocks_full_size() {
+		let lenient = PhysicsParams {
+			hazard_leniency: 0.5,
+			hazard_hitbox_scale: 0.25,
+			..Default::default()
+		};
+		let block = Vec2::new(300.0, 15.0);
+		assert_eq!(
+			collide_with(&lenient, 1, block, Vec2::new(272.0, 15.0)),
+			Some(CollisionKind::SolidWall)
+		);
+	}
+}
+
+mod grid_query {
+	use redox_core::{
+		Vec2,
+		config::PhysicsParams,
+		formats::level,
+		game_object::GameObject,
+		simulation::{collision::colliding_object, spatial_grid::SpatialGrid},
+	};
+
+	use super::common::cube_at;
+
+	/// xorshift, enough to scatter positions without pulling in a crate
+	struct Rng(u32);
+
+	impl Rng {
+		fn next(&mut self) -> f32 {
+			self.0 ^= self.0 << 13;
+			self.0 ^= self.0 >> 17;
+			self.0 ^= self.0 << 5;
+			self.0 as f32 / u32::MAX as f32
+		}
+
+		fn range(&mut self, min: f32, max: f32) -> f32 {
+			min + self.next() * (max - min)
+		}
+	}
+
+	/// Blocks, spikes, saws and blades up to ten times their normal size, at random angles
+	fn scattered_level(rng: &mut Rng) -> String {
+		let ids = [1, 8, 88, 1619];
+		(0..60)
+			.map(|i| {
+				format!(
+					"1,{},2,{},3,{},6,{},32,{};",
+					ids[i % ids.len()],
+					rng.range(0.0, 3000.0),
+					rng.range(0.0, 600.0),
+					rng.range(0.0, 360.0),
+					rng.range(0.5, 10.0),
+				)
+			})
+			.collect()
+	}
+
+	#[test]
+	fn grid_query_finds_every_collision_a_full_scan_does() {
+		let params = PhysicsParams::default();
+		let mut rng = Rng(0x9e37_79b9);
+
+		for _ in 0..20 {
+			let objects: Vec<GameObject> = level::parse_objects(&scattered_level(&mut rng))
+				.iter()
+				.map(GameObject::from_raw)
+				.collect();
+			let grid = SpatialGrid::new(&objects, 128.0);
+
+			// Each object checked on its own, so nothing about the grid can hide it
+			let alone: Vec<(&GameObject, SpatialGrid)> = objects
+				.iter()
+				.map(|obj| (obj, SpatialGrid::new(std::slice::from_ref(obj), 128.0)))
+				.collect();
+
+			for _ in 0..500 {
+				let player = cube_at(Vec2::new(rng.range(-100.0, 3100.0), rng.range(0.0, 700.0)));
+
+				let killers: Vec<usize> = alone
+					.iter()
+					.enumerate()
+					.filter(|(_, (obj, grid))| {
+						colliding_object(&player, std::slice::from_ref(*obj), grid, &params)
+							.is_some()
+					})
+					.map(|(index, _)| index)
+					.collect();
+
+				match colliding_object(&player, &objects, &grid, &params) {
+					Some((index, _)) => assert!(
+						killers.contains(&index),
+						"object {index} isn't a collision at {:?}",
+						player.position
+					),
+					None => assert!(
+						killers.is_empty(),
+						"missed {killers:?} at {:?}",
+						player.position
+					),
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,102 @@
+//! Shared helpers for the integration tests
+
+#![allow(dead_code)]
+
+use redox_core::{
+	Vec2,
+	config::Config,
+	formats::level,
+	game_object::GameObject,
+	pathfinder::{Pathfinder, search::StopReason},
+	state::{Action, GameMode, State},
+};
+
+/// Where every test run starts, on the ground at the level's origin
+pub const START: Vec2 = Vec2::new(0.0, 15.0);
+
+/// Nothing but the ground
+pub const FLAT: &str = "kA2,0;";
+
+/// One spike at x 300, a single jump clears it
+pub const SINGLE_SPIKE: &str = "kA2,0;1,8,2,300,3,15;";
+
+/// A 45-degree, 30x30 slope (id 289) rising from y 0 at x 285 to y 30 at x 315
+pub const UP_SLOPE: &str = "kA2,0;1,289,2,300,3,15;";
+
+/// A few spikes, a double spike and a block to hop over, solvable to x 1100
+pub const SPIKES_AND_BLOCK: &str = "kA2,0;1,8,2,300,3,15;1,8,2,330,3,15;1,1,2,520,3,15;1,8,2,\
+                                    700,3,15;1,8,2,900,3,15;1,8,2,930,3,15;";
+
+/// The outcome of solving a level string
+pub struct Solution {
+	pub path: Vec<(Action, f32)>,
+	pub reached_goal: bool,
+	pub nodes_expanded: usize,
+	/// Nodes pushed over the whole search, expanded or not
+	pub nodes_generated: usize,
+	pub stop_reason: Option<StopReason>,
+	/// x of the final node, the goal node when `reached_goal`
+	pub final_x: f32,
+}
+
+impl Solution {
+	pub fn presses(&self) -> usize {
+		self.path
+			.iter()
+			.filter(|(a, _)| *a == Action::Press)
+			.count()
+	}
+}
+
+/// Decodes an uncompressed level string into game objects
+pub fn objects(level_string: &str) -> Vec<GameObject> {
+	level::parse_objects(level_string)
+		.iter()
+		.map(GameObject::from_raw)
+		.collect()
+}
+
+/// Parses `level_string`, runs the search to `goal_x` and reconstructs the path
+pub fn solve(level_string: &str, goal_x: f32, config: Config) -> Solution {
+	let header = level::parse_header(level_string);
+	let pathfinder = Pathfinder::with_config(objects(level_string), config);
+
+	let start_state = pathfinder.initial_state(START, &header);
+	let mut session = pathfinder.start_search_from(start_state, goal_x);
+	pathfinder.step(&mut session, goal_x);
+
+	let end = session.end_index();
+	let end_node = &session.all_nodes[end];
+
+	Solution {
+		path: pathfinder.reconstruct_path(&session.all_nodes, end_node),
+		reached_goal: session.stop_reason == Some(StopReason::Goal),
+		nodes_expanded: session.nodes_expanded,
+		nodes_generated: session.all_nodes.len(),
+		stop_reason: session.stop_reason,
+		final_x: end_node.state.position.x,
+	}
+}
+
+/// An airborne cube at normal speed, the usual subject for single-step checks
+pub fn cube_at(position: Vec2) -> State {
+	State {
+		position,
+		vy: 0.0,
+		on_ground: false,
+		rotation: 0.0,
+		mode: GameMode::Cube,
+		gravity_flipped: false,
+		floor: 0.0,
+		ceiling: f32::MAX,
+		pressing: false,
+		speed: 1,
+		mini: false,
+		mirrored: false,
+		jump_hold_frames: 0,
+		dashing: false,
+		last_pad: None,
+		last_orb: None,
+		dual: None,
+	}
+}
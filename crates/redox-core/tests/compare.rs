@@ -0,0 +1,53 @@
+mod common;
+
+use redox_core::{
+	config::Config,
+	pathfinder::{Pathfinder, compare::PathDiff},
+	state::Action,
+};
+
+/// Runs `before` ticks, jumps, then runs `after` ticks
+fn jump_after(before: u32, after: u32, dt: f32) -> Vec<(Action, f32)> {
+	vec![
+		(Action::None, before as f32 * dt),
+		(Action::Press, dt),
+		(Action::Release, after as f32 * dt),
+	]
+}
+
+fn compare(a: &[(Action, f32)], b: &[(Action, f32)]) -> PathDiff {
+	let pathfinder = Pathfinder::with_config(common::objects(common::FLAT), Config::default());
+	let start = pathfinder.initial_state(common::START, &Default::default());
+	pathfinder.compare_paths(start, a, b, 1.0)
+}
+
+#[test]
+fn reports_the_first_frame_the_runs_come_apart() {
+	let dt = Config::default().physics.dt();
+	let diff = compare(&jump_after(10, 100, dt), &jump_after(12, 98, dt));
+
+	// The press lands on the eleventh tick, which is the state at index 11
+	let divergence = diff
+		.divergence
+		.expect("the jumps happen at different times");
+	assert_eq!(divergence.frame, 11);
+	assert!(divergence.a.y > divergence.b.y);
+	assert_eq!(divergence.a.x, divergence.b.x);
+
+	assert_eq!(diff.frames, (111, 111));
+	assert_eq!(diff.presses, (1, 1));
+}
+
+#[test]
+fn identical_runs_never_diverge() {
+	let dt = Config::default().physics.dt();
+	let path = jump_after(10, 100, dt);
+	let mut longer = path.clone();
+	longer.push((Action::Press, dt));
+
+	let diff = compare(&path, &longer);
+	assert_eq!(diff.divergence, None);
+	assert_eq!(diff.frames, (111, 112));
+	assert_eq!(diff.presses, (1, 2));
+	assert!(diff.to_string().contains("frames:  111 vs 112 (+1)"));
+}
@@ -0,0 +1,136 @@
+use redox_core::{
+	config::{Config, ConfigError, PhysicsParams},
+	pathfinder::Pathfinder,
+};
+
+fn invalid(edit: impl FnOnce(&mut PhysicsParams)) -> ConfigError {
+	let mut physics = PhysicsParams::default();
+	edit(&mut physics);
+	physics
+		.validate()
+		.expect_err("edited physics should be rejected")
+}
+
+#[test]
+fn presets_are_valid() {
+	PhysicsParams::default().validate().unwrap();
+	PhysicsParams::gd_2_2().validate().unwrap();
+}
+
+#[test]
+fn nan_in_array_names_the_entry() {
+	let err = invalid(|p| p.jump_velocities[3] = f32::NAN);
+	assert!(
+		matches!(err, ConfigError::NotFinite { ref field, .. } if field == "jump_velocities[3]")
+	);
+	assert!(
+		err.to_string()
+			.contains("jump_velocities[3] must be finite")
+	);
+}
+
+#[test]
+fn infinite_pad_velocity_is_rejected() {
+	let err = invalid(|p| p.pad_velocities[1] = f32::INFINITY);
+	assert!(
+		matches!(err, ConfigError::NotFinite { ref field, .. } if field == "pad_velocities[1]")
+	);
+}
+
+#[test]
+fn zero_gravity_is_rejected() {
+	let err = invalid(|p| p.gravities[0] = 0.0);
+	assert_eq!(err, ConfigError::Zero {
+		field: "gravities[0]".to_string()
+	});
+}
+
+#[test]
+fn zero_ship_accel_is_rejected() {
+	let err = invalid(|p| p.ship_accels[2] = 0.0);
+	assert_eq!(err, ConfigError::Zero {
+		field: "ship_accels[2]".to_string()
+	});
+}
+
+#[test]
+fn negative_player_speed_is_rejected() {
+	let err = invalid(|p| p.player_speeds[4] = -10.0);
+	assert!(
+		matches!(err, ConfigError::NotPositive { ref field, .. } if field == "player_speeds[4]")
+	);
+}
+
+#[test]
+fn negative_ship_clamp_is_rejected() {
+	let err = invalid(|p| p.ship_vy_clamp[2] = -800.0);
+	assert!(
+		matches!(err, ConfigError::NotPositive { ref field, .. } if field == "ship_vy_clamp[2]")
+	);
+}
+
+#[test]
+fn zero_tps_is_rejected() {
+	let err = invalid(|p| p.tps = 0.0);
+	assert!(matches!(err, ConfigError::NotPositive { ref field, .. } if field == "tps"));
+}
+
+#[test]
+fn infinite_tps_leaves_no_tick_length() {
+	// 1 / inf is a zero dt, which would never move the player
+	let err = invalid(|p| p.tps = f32::INFINITY);
+	assert!(matches!(err, ConfigError::NotFinite { ref field, .. } if field == "tps"));
+}
+
+#[test]
+fn zero_quantize_step_is_rejected() {
+	let err = invalid(|p| p.vy_quantize_step = 0.0);
+	assert!(
+		matches!(err, ConfigError::NotPositive { ref field, .. } if field == "vy_quantize_step")
+	);
+	assert_eq!(err.to_string(), "vy_quantize_step must be positive, got 0");
+}
+
+#[test]
+fn zero_hitbox_is_rejected() {
+	let err = invalid(|p| p.player_height = 0.0);
+	assert!(matches!(err, ConfigError::NotPositive { ref field, .. } if field == "player_height"));
+}
+
+#[test]
+fn nan_scalar_is_rejected() {
+	let err = invalid(|p| p.hazard_leniency = f32::NAN);
+	assert!(matches!(err, ConfigError::NotFinite { ref field, .. } if field == "hazard_leniency"));
+}
+
+#[test]
+fn speed_index_past_the_arrays_is_rejected() {
+	let physics = PhysicsParams::default();
+	physics.validate_speed(4).unwrap();
+
+	let err = physics.validate_speed(5).unwrap_err();
+	assert_eq!(err, ConfigError::SpeedOutOfRange { speed: 5, count: 5 });
+	assert!(err.to_string().contains("Speed index 5 is out of range"));
+}
+
+#[test]
+fn try_with_config_returns_the_error() {
+	let mut config = Config::default();
+	config.physics.tps = 0.0;
+
+	let err = Pathfinder::try_with_config(Vec::new(), config).err();
+	assert!(matches!(err, Some(ConfigError::NotPositive { .. })));
+}
+
+#[test]
+fn infinite_ground_is_rejected() {
+	let err = invalid(|p| p.ground_y = Some(f32::NEG_INFINITY));
+	assert!(matches!(err, ConfigError::NotFinite { ref field, .. } if field == "ground_y"));
+
+	PhysicsParams {
+		ground_y: None,
+		..PhysicsParams::default()
+	}
+	.validate()
+	.unwrap();
+}
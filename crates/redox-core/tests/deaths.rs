@@ -0,0 +1,52 @@
+mod common;
+
+use redox_core::{
+	config::Config,
+	formats::level,
+	pathfinder::{Pathfinder, search::SearchSession},
+};
+
+/// Two spikes far enough apart to need separate jumps
+const TWO_SPIKES: &str = "kA2,0;1,8,2,300,3,15;1,8,2,600,3,15;";
+
+fn search(count_deaths: bool) -> (Pathfinder, SearchSession) {
+	let mut config = Config::default();
+	config.search.count_deaths = count_deaths;
+
+	let pathfinder = Pathfinder::with_config(common::objects(TWO_SPIKES), config);
+	let start = pathfinder.initial_state(common::START, &level::parse_header(TWO_SPIKES));
+	let mut session = pathfinder.start_search_from(start, 900.0);
+	pathfinder.step(&mut session, 900.0);
+
+	(pathfinder, session)
+}
+
+#[test]
+fn counts_deaths_on_each_spike() {
+	let (pathfinder, session) = search(true);
+
+	let deadliest = pathfinder.deadliest(&session, 5);
+	assert_eq!(deadliest.len(), 2);
+	for (obj, deaths) in &deadliest {
+		assert_eq!(obj.id, 8);
+		assert!(
+			*deaths > 1,
+			"spike at x={} only killed {deaths}",
+			obj.position.x
+		);
+	}
+
+	let total: u32 = session.death_counts.values().sum();
+	assert_eq!(
+		total,
+		deadliest.iter().map(|(_, deaths)| deaths).sum::<u32>()
+	);
+}
+
+#[test]
+fn off_by_default() {
+	let (pathfinder, session) = search(false);
+
+	assert!(session.death_counts.is_empty());
+	assert!(pathfinder.deadliest(&session, 5).is_empty());
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use redox_core::{
+	Vec2,
+	config::Config,
+	game_object::{GameObjectType, mapping::get_object_type_for_id},
+	pathfinder::Pathfinder,
+	state::State,
+};
+
+/// Spikes and a block to hop over, with an unclassified decoration id scattered all
+/// through and around them
+fn level() -> String {
+	let mut level = String::from(
+		"kA2,0;1,8,2,300,3,15;1,8,2,330,3,15;1,1,2,520,3,15;1,8,2,700,3,15;1,8,2,900,3,15;",
+	);
+	for i in 0..200 {
+		level += &format!("1,211,2,{},3,{};", 100 + i * 5, 15 + (i % 7) * 20);
+	}
+	level
+}
+
+fn config(drop_decoration: bool) -> Config {
+	Config {
+		drop_decoration,
+		..Config::default()
+	}
+}
+
+#[test]
+fn dropping_decoration_keeps_the_solution() {
+	assert!(get_object_type_for_id(211).is_decoration());
+
+	let level = level();
+	let kept = common::solve(&level, 1100.0, config(false));
+	let dropped = common::solve(&level, 1100.0, config(true));
+
+	assert!(kept.reached_goal);
+	assert_eq!(kept.path, dropped.path);
+	assert_eq!(kept.nodes_expanded, dropped.nodes_expanded);
+
+	let objects = common::objects(&level);
+	assert_eq!(
+		Pathfinder::with_config(objects.clone(), config(false))
+			.objects()
+			.len(),
+		205
+	);
+	assert_eq!(
+		Pathfinder::with_config(objects, config(true))
+			.objects()
+			.len(),
+		5
+	);
+}
+
+#[test]
+fn triggers_are_never_decoration() {
+	for object_type in [
+		GameObjectType::ShipPortal,
+		GameObjectType::YellowJumpPad,
+		GameObjectType::GreenRing,
+		GameObjectType::Slope,
+		GameObjectType::Breakable,
+	] {
+		assert!(!object_type.is_decoration(), "{object_type:?}");
+	}
+}
+
+#[test]
+fn layer_fields_are_parsed() {
+	let objects = common::objects("kA2,0;1,8,2,300,3,15,24,-3,25,7,20,2,61,4;1,8,2,400,3,15;");
+
+	assert_eq!(objects[0].z_layer, Some(-3));
+	assert_eq!(objects[0].z_order, Some(7));
+	assert_eq!(objects[0].editor_layers, [Some(2), Some(4)]);
+	assert_eq!(objects[1].z_layer, None);
+}
+
+#[test]
+fn background_hazard_is_dropped_below_the_layer() {
+	// A spike far back on B4, and a ship portal next to it on the same layer
+	let level = "kA2,0;1,8,2,300,3,15,24,-3;1,13,2,600,3,45,24,-3;";
+	let on_spike = State {
+		position: Vec2::new(300.0, 15.0),
+		..common::cube_at(Vec2::ZERO)
+	};
+
+	let kept = Pathfinder::with_config(common::objects(level), config(true));
+	assert!(kept.collides(&on_spike).is_some());
+
+	let background = Config {
+		background_z_layer: Some(-1),
+		..config(true)
+	};
+	let dropped = Pathfinder::with_config(common::objects(level), background);
+	assert!(dropped.collides(&on_spike).is_none());
+	assert_eq!(dropped.objects().len(), 1);
+	assert_eq!(dropped.objects()[0].object_type, GameObjectType::ShipPortal);
+}
@@ -0,0 +1,36 @@
+mod common;
+
+use redox_core::{config::Config, pathfinder::Pathfinder};
+
+/// A ledge and two spikes, the usual short hop test
+const LEVEL: &str = "kA2,0;1,8,2,300,3,15;1,1,2,520,3,15;1,1,2,550,3,15;1,8,2,800,3,15;";
+
+/// `LEVEL` with every object pasted three times over
+fn stacked() -> String {
+	let objects = LEVEL.trim_start_matches("kA2,0;");
+	format!("kA2,0;{objects}{objects}{objects}")
+}
+
+#[test]
+fn stacked_copies_are_merged() {
+	let pathfinder = Pathfinder::with_config(common::objects(&stacked()), Config::default());
+	assert_eq!(pathfinder.objects().len(), common::objects(LEVEL).len());
+}
+
+#[test]
+fn merging_copies_keeps_the_solution() {
+	let single = common::solve(LEVEL, 1000.0, Config::default());
+	let stacked = common::solve(&stacked(), 1000.0, Config::default());
+
+	assert!(single.reached_goal);
+	assert_eq!(single.path, stacked.path);
+	assert_eq!(single.nodes_expanded, stacked.nodes_expanded);
+}
+
+#[test]
+fn stacked_pads_and_copies_that_differ_are_kept() {
+	// Two yellow pads fire twice, and a rotated spike is a different spike
+	let level = "kA2,0;1,35,2,300,3,15;1,35,2,300,3,15;1,8,2,600,3,15;1,8,2,600,3,15,6,90;";
+	let pathfinder = Pathfinder::with_config(common::objects(level), Config::default());
+	assert_eq!(pathfinder.objects().len(), 4);
+}
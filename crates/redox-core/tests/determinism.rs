@@ -0,0 +1,68 @@
+mod common;
+
+use redox_core::config::{Config, TieBreak};
+
+fn solve(tie_break: TieBreak) -> Vec<u8> {
+	let mut config = Config::default();
+	config.search.tie_break = tie_break;
+
+	let solution = common::solve(common::SPIKES_AND_BLOCK, 1100.0, config);
+	assert!(solution.reached_goal, "test level should be solvable");
+
+	// Compare the exact bits of every duration, not a rounded rendering
+	solution
+		.path
+		.iter()
+		.flat_map(|(action, duration)| {
+			let mut bytes = vec![*action as u8];
+			bytes.extend(duration.to_bits().to_le_bytes());
+			bytes
+		})
+		.collect()
+}
+
+#[test]
+fn repeated_searches_produce_identical_paths() {
+	for tie_break in [
+		TieBreak::InsertionOrder,
+		TieBreak::HigherX,
+		TieBreak::LowerG,
+	] {
+		let first = solve(tie_break);
+		assert!(!first.is_empty());
+		assert_eq!(first, solve(tie_break), "{tie_break:?} diverged");
+	}
+}
+
+/// Recorded before expansion stopped copying nodes out of the arena and successors
+/// that were already expanded as cheaply stopped being pushed, so any change to the
+/// path found shows up here
+/// The config the recorded searches below ran with, from before spikes got their own
+/// hitbox scale
+fn recorded_config() -> Config {
+	let mut config = Config::default();
+	config.physics.hazard_hitbox_scale = config.physics.hazard_leniency;
+	config
+}
+
+#[test]
+fn expansion_matches_recorded_search() {
+	let solution = common::solve(common::SPIKES_AND_BLOCK, 1100.0, recorded_config());
+
+	assert!(solution.reached_goal);
+	assert_eq!(solution.path.len(), 13);
+	assert_eq!(solution.presses(), 4);
+}
+
+#[test]
+fn closed_successors_are_not_pushed() {
+	let solution = common::solve(common::SPIKES_AND_BLOCK, 1100.0, recorded_config());
+
+	// 1356 expanded out of 1796 generated when every successor was pushed
+	assert_eq!(solution.nodes_expanded, 1131);
+	assert!(
+		solution.nodes_generated < 1796,
+		"generated {} nodes",
+		solution.nodes_generated
+	);
+}
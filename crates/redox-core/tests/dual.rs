@@ -0,0 +1,80 @@
+mod common;
+
+use redox_core::{
+	Vec2,
+	config::Config,
+	formats::level,
+	pathfinder::Pathfinder,
+	state::{Action, State},
+};
+
+const GOAL_X: f32 = 900.0;
+
+/// A dual portal under a roof with its underside at y 210, then a floor spike and a
+/// hanging spike both at x 500, so one jump has to clear both at once
+fn mirrored_spikes() -> String {
+	let mut level = String::from("kA2,0;1,286,2,150,3,45;");
+	for column in 0..40 {
+		level += &format!("1,1,2,{},3,225;", 135 + 30 * column);
+	}
+	level += "1,8,2,500,3,15;1,8,2,500,3,195,6,180;";
+	level
+}
+
+fn trajectory(level: &str, path: &[(Action, f32)]) -> (Pathfinder, Vec<State>) {
+	let pathfinder = Pathfinder::with_config(common::objects(level), Config::default());
+	let start = pathfinder.initial_state(common::START, &level::parse_header(level));
+	let states = pathfinder.simulate_trajectory(start, path);
+	(pathfinder, states)
+}
+
+#[test]
+fn one_input_carries_both_players_past_mirrored_spikes() {
+	let level = mirrored_spikes();
+	let solution = common::solve(&level, GOAL_X, Config::default());
+	assert!(
+		solution.reached_goal,
+		"stopped with {:?} at x={}",
+		solution.stop_reason, solution.final_x
+	);
+
+	let (pathfinder, states) = trajectory(&level, &solution.path);
+	for state in &states {
+		assert_eq!(
+			pathfinder.collides(state),
+			None,
+			"died at x {}",
+			state.position.x
+		);
+	}
+
+	// Both are in the air over the spikes, jumping off the floor and the roof together
+	let over = states.iter().find(|s| s.position.x >= 500.0).unwrap();
+	let second = over.dual_state().expect("still in dual mode");
+	assert!(!over.on_ground && !second.on_ground);
+	assert!(second.gravity_flipped && !over.gravity_flipped);
+
+	// Without the jump the player on the roof runs into its spike too
+	let (_, states) = trajectory(&level, &[(Action::None, 4.0)]);
+	let death = states
+		.iter()
+		.find(|s| pathfinder.collides(s).is_some())
+		.unwrap();
+	assert!(pathfinder.collides(&death.dual_state().unwrap()).is_some());
+}
+
+#[test]
+fn second_player_is_ignored_without_a_dual_portal() {
+	let pathfinder =
+		Pathfinder::with_config(common::objects(common::SINGLE_SPIKE), Config::default());
+
+	// The first player is high above the spike, the second sits right on it
+	let mut state = common::cube_at(Vec2::new(300.0, 200.0));
+	state.set_dual_state(&common::cube_at(Vec2::new(300.0, 15.0)));
+	assert!(pathfinder.collides(&state.dual_state().unwrap()).is_some());
+	assert_eq!(pathfinder.collides(&state), None);
+	assert_eq!(
+		pathfinder.simulate_step(&state, Action::None).dual,
+		state.dual
+	);
+}
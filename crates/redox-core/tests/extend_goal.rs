@@ -0,0 +1,77 @@
+mod common;
+
+use redox_core::{
+	config::Config,
+	pathfinder::{Pathfinder, search::StopReason},
+	state::Action,
+};
+
+/// Spikes on both sides of the first goal, so the extended search has to jump again
+const LEVEL: &str = "kA2,0;1,8,2,300,3,15;1,8,2,1300,3,15;1,8,2,1700,3,15;";
+
+fn duration(path: &[(Action, f32)]) -> f32 {
+	path.iter().map(|(_, duration)| duration).sum()
+}
+
+#[test]
+fn extended_goal_matches_a_fresh_search() {
+	let mut config = Config::default();
+	config.search.optimal = true;
+	let pathfinder = Pathfinder::with_config(common::objects(LEVEL), config);
+
+	let mut session = pathfinder.start_search(common::START, 1000.0);
+	pathfinder.step(&mut session, 1000.0);
+	assert_eq!(session.stop_reason, Some(StopReason::Goal));
+	let first_goal = session.goal_reached_index.unwrap();
+	let generated = session.all_nodes.len();
+
+	pathfinder.extend_goal(&mut session, 2000.0);
+	assert_eq!(session.stop_reason, None);
+	assert_eq!(session.goal_reached_index, None);
+	pathfinder.step(&mut session, 2000.0);
+	assert_eq!(session.stop_reason, Some(StopReason::Goal));
+
+	// The first leg's tree is still there and the new goal grew out of it
+	let end = session.goal_reached_index.unwrap();
+	assert!(session.all_nodes.len() > generated);
+	assert!(end != first_goal);
+	let extended = pathfinder.reconstruct_path(&session.all_nodes, &session.all_nodes[end]);
+
+	let fresh = pathfinder.solve(common::START, 2000.0);
+	assert!(fresh.reached_goal);
+	assert!(
+		(duration(&extended) - duration(&fresh.actions)).abs() < 1e-3,
+		"extended {} vs fresh {}",
+		duration(&extended),
+		duration(&fresh.actions)
+	);
+}
+
+#[test]
+fn extending_after_a_budget_stop_resumes_without_a_goal() {
+	let mut config = Config::default();
+	config.search.max_nodes = Some(10);
+	let pathfinder = Pathfinder::with_config(common::objects(LEVEL), config);
+
+	let mut session = pathfinder.start_search(common::START, 1000.0);
+	pathfinder.step(&mut session, 1000.0);
+	assert_eq!(session.stop_reason, Some(StopReason::NodeBudget));
+	let open = session.open_set.len();
+
+	// The furthest node was already expanded, it must not come back as a goal
+	pathfinder.extend_goal(&mut session, 2000.0);
+	assert_eq!(session.stop_reason, None);
+	assert_eq!(session.goal_reached_index, None);
+	assert_eq!(session.open_set.len(), open);
+	assert!(
+		session
+			.open_set
+			.iter()
+			.all(|entry| entry.index != session.best_x_index)
+	);
+
+	// The budget is still spent
+	pathfinder.step(&mut session, 2000.0);
+	assert_eq!(session.stop_reason, Some(StopReason::NodeBudget));
+	assert_eq!(session.goal_reached_index, None);
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use redox_core::{Vec2, config::Config, pathfinder::Pathfinder, state::Action};
+
+/// `common::UP_SLOPE` flipped horizontally
+const FLIPPED_SLOPE: &str = "kA2,0;1,289,2,300,3,15,4,1;";
+
+#[test]
+fn flipped_up_slope_falls_to_the_right() {
+	let up = &common::objects(common::UP_SLOPE)[0];
+	let down = &common::objects(FLIPPED_SLOPE)[0];
+
+	for x in [285.0, 292.5, 300.0, 307.5, 315.0] {
+		let mirrored = 600.0 - x;
+		assert_eq!(down.slope_surface_y(x), up.slope_surface_y(mirrored));
+	}
+	assert_eq!(down.slope_gradient(), up.slope_gradient().map(|g| -g));
+}
+
+#[test]
+fn cube_runs_down_a_flipped_up_slope() {
+	let pathfinder = Pathfinder::with_config(common::objects(FLIPPED_SLOPE), Config::default());
+
+	// Standing on the high end
+	let mut state = common::cube_at(Vec2::new(285.0, 45.0));
+	state.on_ground = true;
+
+	let mut heights = Vec::new();
+	while state.position.x < 310.0 {
+		state = pathfinder.simulate_step(&state, Action::None);
+		heights.push(state.position.y);
+	}
+
+	assert!(heights.windows(2).all(|w| w[1] <= w[0] + 0.01));
+	assert!(
+		state.position.y < 40.0,
+		"still at y={} past the midpoint",
+		state.position.y
+	);
+}
+
+#[test]
+fn flipping_a_block_leaves_its_hitbox_alone() {
+	// Hitboxes are centred on the object, so only slopes change with a flip
+	let plain = &common::objects("kA2,0;1,1,2,300,3,15;")[0];
+	let flipped = &common::objects("kA2,0;1,1,2,300,3,15,4,1,5,1;")[0];
+
+	assert_eq!(plain.obb, flipped.obb);
+	assert_eq!(plain.aabb(), flipped.aabb());
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use std::collections::HashMap;
+
+use redox_core::{
+	config::Config,
+	formats::level,
+	game_object::{ForcedInput, GameObjectType, HitboxShape},
+	pathfinder::{Pathfinder, search::StopReason},
+	state::Action,
+};
+
+/// A ship level with an unmapped id standing in for the region, spanning x 300 to 600
+/// and the ship's whole range
+const LEVEL: &str = "kA2,1;1,9999,2,450,3,150;";
+const REGION_ID: i32 = 9999;
+const GOAL_X: f32 = 900.0;
+
+fn pathfinder(region: GameObjectType) -> Pathfinder {
+	let mut config = Config::default();
+	config
+		.id_overrides
+		.insert(REGION_ID, (region, HitboxShape::Rectangle, 300.0, 300.0));
+
+	let objects = level::parse_game_objects(LEVEL, &config.id_overrides);
+	Pathfinder::with_config(objects, config)
+}
+
+#[test]
+fn forced_region_leaves_one_successor() {
+	let pathfinder = pathfinder(GameObjectType::ForceRelease);
+	let start = pathfinder.initial_state(common::START, &level::parse_header(LEVEL));
+	let mut session = pathfinder.start_search_from(start, GOAL_X);
+	pathfinder.step(&mut session, GOAL_X);
+
+	let mut children: HashMap<usize, Vec<Action>> = HashMap::new();
+	for node in &session.all_nodes {
+		if let (Some(parent), Some(action)) = (node.parent_index, node.action) {
+			children.entry(parent).or_default().push(action);
+		}
+	}
+
+	let mut forced = 0;
+	let mut branching = 0;
+	for (parent, actions) in &children {
+		match pathfinder.forced_input(&session.all_nodes[*parent].state) {
+			Some(ForcedInput::Release) => {
+				forced += 1;
+				assert_eq!(actions.len(), 1, "{actions:?}");
+				assert_ne!(actions[0], Action::Press);
+			}
+			Some(ForcedInput::Hold) => unreachable!(),
+			None => branching = branching.max(actions.len()),
+		}
+	}
+
+	assert!(forced > 0, "no node was expanded inside the region");
+	assert!(branching > 1, "the ship should branch outside the region");
+	assert_eq!(session.stop_reason, Some(StopReason::Goal));
+}
+
+#[test]
+fn forced_hold_presses_on_entry_and_keeps_holding() {
+	let pathfinder = pathfinder(GameObjectType::ForceHold);
+	let start = pathfinder.initial_state(common::START, &level::parse_header(LEVEL));
+	let result = pathfinder.solve_from(start, GOAL_X);
+	assert!(result.reached_goal);
+
+	// Only the tick the player enters on is still released, the press lands on the next
+	let released_inside = pathfinder
+		.simulate_trajectory(start, &result.actions)
+		.iter()
+		.filter(|state| pathfinder.forced_input(state) == Some(ForcedInput::Hold))
+		.filter(|state| !state.pressing)
+		.count();
+	assert!(released_inside <= 1, "{released_inside} released ticks");
+}
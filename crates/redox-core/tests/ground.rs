@@ -0,0 +1,82 @@
+mod common;
+
+use redox_core::{
+	config::Config,
+	formats::level,
+	pathfinder::Pathfinder,
+	state::{Action, State},
+};
+
+const SHIP_LEVEL: &str = "kA2,1;";
+
+fn pathfinder(ground_y: Option<f32>) -> Pathfinder {
+	let mut config = Config::default();
+	config.physics.ground_y = ground_y;
+	Pathfinder::with_config(Vec::new(), config)
+}
+
+/// Steps without input until the player drops out of the level, `None` if it never does
+fn ticks_until_out(pathfinder: &Pathfinder, mut state: State, ticks: usize) -> Option<usize> {
+	(1..=ticks).find(|_| {
+		state = pathfinder.simulate_step(&state, Action::None);
+		pathfinder.out_of_bounds(&state)
+	})
+}
+
+#[test]
+fn cube_rests_on_the_default_ground() {
+	let pathfinder = pathfinder(Config::default().physics.ground_y);
+	let mut state = pathfinder.initial_state(common::START, &level::parse_header(common::FLAT));
+
+	for _ in 0..240 {
+		state = pathfinder.simulate_step(&state, Action::None);
+		assert!(state.on_ground);
+		assert_eq!(state.position.y, 15.0);
+	}
+}
+
+#[test]
+fn cube_lands_on_a_raised_ground() {
+	let pathfinder = pathfinder(Some(60.0));
+	let state = common::cube_at(redox_core::Vec2::new(0.0, 120.0));
+
+	assert_eq!(ticks_until_out(&pathfinder, state, 240), None);
+
+	let mut state = state;
+	for _ in 0..240 {
+		state = pathfinder.simulate_step(&state, Action::None);
+	}
+	assert!(state.on_ground);
+	assert_eq!(state.position.y, 75.0);
+}
+
+#[test]
+fn ship_is_held_up_by_the_ground() {
+	let pathfinder = pathfinder(Some(0.0));
+	let state = pathfinder.initial_state(common::START, &level::parse_header(SHIP_LEVEL));
+
+	assert_eq!(ticks_until_out(&pathfinder, state, 2400), None);
+}
+
+#[test]
+fn ship_falls_out_of_a_level_without_ground() {
+	let pathfinder = pathfinder(None);
+	let state = pathfinder.initial_state(common::START, &level::parse_header(SHIP_LEVEL));
+
+	assert!(ticks_until_out(&pathfinder, state, 2400).is_some());
+
+	// Holding still keeps it up
+	let mut state = state;
+	for _ in 0..240 {
+		state = pathfinder.simulate_step(&state, Action::Press);
+		assert!(!pathfinder.out_of_bounds(&state));
+	}
+}
+
+#[test]
+fn cube_falls_through_where_the_ground_would_be() {
+	let pathfinder = pathfinder(None);
+	let state = pathfinder.initial_state(common::START, &level::parse_header(common::FLAT));
+
+	assert!(ticks_until_out(&pathfinder, state, 2400).is_some());
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use redox_core::{
+	Vec2,
+	config::Config,
+	formats::level,
+	pathfinder::Pathfinder,
+	state::{Action, GameMode, State},
+};
+
+/// A mini ship at 2x speed, `kA4` numbers speeds 1x, 0.5x, 2x, ...
+const MINI_SHIP: &str = "kA2,1,kA3,1,kA4,2;";
+
+#[test]
+fn header_seeds_a_mini_ship_start() {
+	let header = level::parse_header(MINI_SHIP);
+	assert_eq!(header.game_mode, GameMode::Ship);
+	assert!(header.mini);
+	assert_eq!(header.speed, 2);
+
+	let pathfinder = Pathfinder::with_config(Vec::new(), Config::default());
+	let state = pathfinder.initial_state(common::START, &header);
+	assert_eq!(state.mode, GameMode::Ship);
+	assert!(state.mini);
+	assert_eq!(state.speed, 2);
+	assert!(!state.on_ground);
+}
+
+#[test]
+fn mini_player_fits_under_a_low_block() {
+	// Underside at y 25, below the top of a full-size cube on the ground
+	let pathfinder =
+		Pathfinder::with_config(common::objects("kA2,0;1,1,2,300,3,40;"), Config::default());
+
+	let full = common::cube_at(Vec2::new(300.0, 15.0));
+	assert!(pathfinder.collides(&full).is_some());
+
+	let mini = State {
+		mini: true,
+		..common::cube_at(Vec2::new(300.0, 9.0))
+	};
+	assert_eq!(pathfinder.collides(&mini), None);
+}
+
+#[test]
+fn mini_cube_rests_lower_on_the_ground() {
+	let pathfinder = Pathfinder::with_config(Vec::new(), Config::default());
+	let header = level::parse_header("kA2,0,kA3,1;");
+
+	let mut state = pathfinder.initial_state(common::START, &header);
+	for _ in 0..30 {
+		state = pathfinder.simulate_step(&state, Action::None);
+	}
+
+	assert!(state.on_ground);
+	assert!(
+		(state.position.y - 9.0).abs() < 0.01,
+		"resting at y={}",
+		state.position.y
+	);
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use redox_core::{
+	Vec2,
+	config::Config,
+	formats::level,
+	pathfinder::{Pathfinder, search::Corridor},
+	state::Action,
+};
+
+const MEDIUM_LEVEL: &str = include_str!("../benches/levels/medium.txt");
+
+/// Past the first few jumps, long enough for the search cost to show
+const GOAL_X: f32 = 2500.0;
+
+fn optimal() -> Config {
+	let mut config = Config::default();
+	config.search.optimal = true;
+	config
+}
+
+fn frames(path: &[(Action, f32)], tps: f32) -> u32 {
+	(path.iter().map(|(_, dur)| dur).sum::<f32>() * tps).round() as u32
+}
+
+#[test]
+fn two_passes_expand_fewer_nodes_on_medium_level() {
+	let single = common::solve(MEDIUM_LEVEL, GOAL_X, optimal());
+	assert!(single.reached_goal);
+
+	let header = level::parse_header(MEDIUM_LEVEL);
+	let pathfinder = Pathfinder::with_config(common::objects(MEDIUM_LEVEL), optimal());
+	let start = pathfinder.initial_state(common::START, &header);
+	let search = pathfinder.search_hierarchical(start, GOAL_X);
+
+	assert!(search.reached_goal());
+	assert!(search.fine.corridor.is_some(), "coarse pass fell short");
+	assert!(
+		search.nodes_expanded() < single.nodes_expanded,
+		"two passes expanded {} + {} nodes, a single pass {}",
+		search.coarse.nodes_expanded,
+		search.fine.nodes_expanded,
+		single.nodes_expanded
+	);
+
+	// The corridor is wide enough that the optimal path still fits inside it
+	let path = pathfinder.solve_hierarchical(start, GOAL_X).unwrap();
+	assert_eq!(
+		frames(&path, pathfinder.tps()),
+		frames(&single.path, pathfinder.tps())
+	);
+	assert!(pathfinder.validate_path(start, &path, GOAL_X).is_ok());
+}
+
+#[test]
+fn corridor_interpolates_between_points() {
+	let corridor = Corridor::new(
+		vec![
+			Vec2::new(100.0, 45.0),
+			Vec2::new(0.0, 15.0),
+			Vec2::new(200.0, 45.0),
+		],
+		10.0,
+	);
+
+	assert_eq!(corridor.y_at(50.0), Some(30.0));
+	assert_eq!(corridor.y_at(-20.0), Some(15.0));
+	assert_eq!(corridor.y_at(500.0), Some(45.0));
+
+	assert!(corridor.contains(Vec2::new(50.0, 39.0)));
+	assert!(!corridor.contains(Vec2::new(50.0, 41.0)));
+	assert!(Corridor::new(Vec::new(), 10.0).contains(Vec2::new(50.0, 1000.0)));
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use std::collections::HashMap;
+
+use redox_core::{
+	Vec2,
+	config::Config,
+	formats::level::{self, LevelHeader},
+	game_object::{GameObject, GameObjectType, HitboxShape},
+	pathfinder::Pathfinder,
+	state::Action,
+};
+
+/// 9999 isn't in the built-in mapping, placed where the player runs along the ground
+const LEVEL: &str = "kA2,0;1,9999,2,400,3,15;";
+const UNKNOWN_ID: i32 = 9999;
+
+#[test]
+fn overridden_unknown_id_is_avoided() {
+	let raw = level::parse_objects(LEVEL);
+	assert_eq!(
+		GameObject::from_raw(&raw[0]).object_type,
+		GameObjectType::Unknown
+	);
+
+	let mut config = Config::default();
+	config.id_overrides.insert(
+		UNKNOWN_ID,
+		(GameObjectType::Hazard, HitboxShape::Rectangle, 30.0, 30.0),
+	);
+
+	let objects: Vec<GameObject> = raw
+		.iter()
+		.map(|raw| GameObject::from_raw_with_overrides(raw, &config.id_overrides))
+		.collect();
+	assert_eq!(objects[0].object_type, GameObjectType::Hazard);
+
+	let goal_x = 700.0;
+	let start = Vec2::new(0.0, 15.0);
+	let pathfinder = Pathfinder::with_config(objects, config);
+
+	// Running straight along the ground now dies
+	let start_state = pathfinder.initial_state(start, &LevelHeader::default());
+	let idle = pathfinder.simulate_trajectory(start_state, &[(Action::None, 2.0)]);
+	assert!(
+		idle.iter()
+			.any(|state| pathfinder.collides(state).is_some())
+	);
+
+	let mut session = pathfinder.start_search(start, goal_x);
+	pathfinder.step(&mut session, goal_x);
+	let end = session
+		.goal_reached_index
+		.expect("should jump over the hazard");
+	let path = pathfinder.reconstruct_path(&session.all_nodes, &session.all_nodes[end]);
+
+	let states = pathfinder.simulate_trajectory(start_state, &path);
+	assert!(
+		states
+			.iter()
+			.all(|state| pathfinder.collides(state).is_none())
+	);
+	assert!(states.last().unwrap().position.x >= goal_x);
+}
+
+#[test]
+fn overrides_leave_other_ids_alone() {
+	let raw = level::parse_objects(common::SINGLE_SPIKE);
+	let overrides = HashMap::from([(
+		UNKNOWN_ID,
+		(GameObjectType::Solid, HitboxShape::Rectangle, 30.0, 30.0),
+	)]);
+
+	let object = GameObject::from_raw_with_overrides(&raw[0], &overrides);
+	assert_eq!(object.object_type, GameObjectType::Hazard);
+}
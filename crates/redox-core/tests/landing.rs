@@ -0,0 +1,153 @@
+mod common;
+
+use redox_core::{
+	Vec2, config::Config, formats::level::LevelHeader, pathfinder::Pathfinder, state::Action,
+};
+
+/// A 1.5-tall platform (id 468) whose top sits at y=100.75
+const PLATFORM: &str = "kA2,0;1,468,2,300,3,100;";
+const PLATFORM_TOP: f32 = 100.75;
+
+#[test]
+fn fast_fall_lands_on_thin_platform() {
+	let pathfinder = Pathfinder::with_config(common::objects(PLATFORM), Config::default());
+
+	// Feet just above the platform, falling far more than its height in one tick
+	let mut state = common::cube_at(Vec2::new(300.0, PLATFORM_TOP + 15.5));
+	state.speed = 4;
+	state.vy = -1500.0;
+
+	let next = pathfinder.simulate_step(&state, Action::None);
+
+	assert!(next.on_ground, "fell through to y={}", next.position.y);
+	assert!((next.position.y - (PLATFORM_TOP + 15.0)).abs() < 0.01);
+	assert_eq!(next.vy, 0.0);
+}
+
+#[test]
+fn rising_through_thin_platform_does_not_land() {
+	let pathfinder = Pathfinder::with_config(common::objects(PLATFORM), Config::default());
+
+	let mut state = common::cube_at(Vec2::new(300.0, PLATFORM_TOP - 16.5));
+	state.vy = 1500.0;
+
+	let next = pathfinder.simulate_step(&state, Action::None);
+
+	assert!(!next.on_ground);
+}
+
+#[test]
+fn flipped_start_rests_against_the_world_ceiling() {
+	let mut config = Config::default();
+	config.physics.world_ceiling = Some(200.0);
+	let pathfinder = Pathfinder::with_config(Vec::new(), config);
+
+	let header = LevelHeader {
+		gravity_flipped: true,
+		..Default::default()
+	};
+	let mut state = pathfinder.initial_state(common::START, &header);
+	for _ in 0..240 {
+		state = pathfinder.simulate_step(&state, Action::None);
+	}
+
+	assert!(state.on_ground, "still falling at y={}", state.position.y);
+	assert_eq!(state.position.y, 185.0);
+	assert_eq!(state.vy, 0.0);
+}
+
+#[test]
+fn flipped_ceiling_defaults_to_the_level_top() {
+	let pathfinder = Pathfinder::with_config(common::objects(PLATFORM), Config::default());
+
+	// Reaches the ceiling well before running under the platform
+	let mut state = common::cube_at(Vec2::new(0.0, 15.0));
+	state.gravity_flipped = true;
+	for _ in 0..120 {
+		state = pathfinder.simulate_step(&state, Action::None);
+	}
+
+	assert!(state.on_ground);
+	assert!((state.position.y - (PLATFORM_TOP - 15.0)).abs() < 0.01);
+}
+
+#[test]
+fn cube_lands_on_rotated_block() {
+	// A block turned 45 degrees, so its top corner sits 15 * sqrt(2) above its center
+	let level = "kA2,0;1,1,2,300,3,100,6,45;";
+	let top = 100.0 + 15.0 * 2.0f32.sqrt();
+	let pathfinder = Pathfinder::with_config(common::objects(level), Config::default());
+
+	// Dropped from just short of it, so it comes down on the corner
+	let mut state = common::cube_at(Vec2::new(275.0, 150.0));
+	state.speed = 0;
+	for _ in 0..240 {
+		state = pathfinder.simulate_step(&state, Action::None);
+		assert_eq!(
+			pathfinder.collides(&state),
+			None,
+			"died at {:?}",
+			state.position
+		);
+		if state.on_ground {
+			break;
+		}
+	}
+
+	assert!(state.on_ground);
+	assert!(
+		(state.position.y - (top + 15.0)).abs() < 0.01,
+		"rests at y={}",
+		state.position.y
+	);
+
+	// Sinking a little into the corner is inside the surface zone, not a crash
+	state.position.y -= 4.0;
+	assert_eq!(pathfinder.collides(&state), None);
+}
+
+/// Two thin platforms 10 units apart, the lower one first in x order
+const LOWER_FIRST: &str = "kA2,0;1,468,2,290,3,90;1,468,2,310,3,100;";
+
+/// The same two platforms, the higher one first in x order
+const HIGHER_FIRST: &str = "kA2,0;1,468,2,290,3,100;1,468,2,310,3,90;";
+
+#[test]
+fn fast_fall_through_two_platforms_lands_on_the_higher() {
+	let pathfinder = Pathfinder::with_config(common::objects(LOWER_FIRST), Config::default());
+
+	// Feet just above both, falling past them within one tick
+	let mut state = common::cube_at(Vec2::new(300.0, PLATFORM_TOP + 15.5));
+	state.speed = 0;
+	state.vy = -4000.0;
+
+	let next = pathfinder.simulate_step(&state, Action::None);
+
+	assert!(next.on_ground, "fell through to y={}", next.position.y);
+	assert!(
+		(next.position.y - (PLATFORM_TOP + 15.0)).abs() < 0.01,
+		"rests at y={}",
+		next.position.y
+	);
+}
+
+#[test]
+fn flipped_rise_through_two_platforms_lands_under_the_lower() {
+	let pathfinder = Pathfinder::with_config(common::objects(HIGHER_FIRST), Config::default());
+
+	// Head just under both, rising past them within one tick
+	let lower_bottom = 90.0 - (PLATFORM_TOP - 100.0);
+	let mut state = common::cube_at(Vec2::new(300.0, lower_bottom - 15.5));
+	state.gravity_flipped = true;
+	state.speed = 0;
+	state.vy = 4000.0;
+
+	let next = pathfinder.simulate_step(&state, Action::None);
+
+	assert!(next.on_ground, "rose through to y={}", next.position.y);
+	assert!(
+		(next.position.y - (lower_bottom - 15.0)).abs() < 0.01,
+		"rests at y={}",
+		next.position.y
+	);
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use redox_core::formats::level;
+
+/// A single spike, `kA2,0;1,8,2,300,3,15;` gzipped and base64-encoded
+const SPIKE_LEVEL: &str = "H4sIAAAAAAACA8t2NNIxsDbUsdAx0jE2MNAx1jE0tQYAj2HDORUAAAA=";
+
+fn xor(data: &str, key: u8) -> String {
+	data.bytes().map(|byte| (byte ^ key) as char).collect()
+}
+
+fn object_ids(level_string: &str) -> Vec<String> {
+	level::parse_objects(level_string)
+		.iter()
+		.filter_map(|raw| {
+			raw.properties
+				.iter()
+				.find(|(key, _)| key == "1")
+				.map(|(_, id)| id.clone())
+		})
+		.collect()
+}
+
+#[test]
+fn xor_wrapped_payload_decodes_like_the_plain_one() {
+	let plain = level::parse_level_data(SPIKE_LEVEL).unwrap();
+	assert_eq!(plain, common::SINGLE_SPIKE);
+
+	// Key 11 is what save files use, turning the gzip magic into `C?xB`
+	let save_file = xor(SPIKE_LEVEL, 11);
+	assert!(save_file.starts_with("C?xB"));
+
+	for wrapped in [save_file, xor(SPIKE_LEVEL, 25)] {
+		let decoded = level::parse_level_data(&wrapped).unwrap();
+		assert_eq!(decoded, plain);
+		assert_eq!(object_ids(&decoded), ["8"]);
+	}
+}
+
+#[test]
+fn payload_is_found_inside_its_wrapper() {
+	let plain = level::parse_level_data(SPIKE_LEVEL).unwrap();
+
+	let response = format!("1:128:2:Spike:3:U3Bpa2U=:4:{SPIKE_LEVEL}:5:1:13:21");
+	let plist = format!("<d><k>k2</k><s>Spike</s><k>k4</k><s>{SPIKE_LEVEL}</s></d>");
+
+	for wrapped in [
+		response.clone(),
+		plist.clone(),
+		xor(&response, 11),
+		xor(&plist, 11),
+	] {
+		assert_eq!(
+			level::parse_level_data(&wrapped).unwrap(),
+			plain,
+			"{wrapped}"
+		);
+	}
+}
+
+#[test]
+fn gmd_export_decodes_its_level() {
+	let gmd = format!(
+		"<?xml version=\"1.0\"?><plist version=\"1.0\" gjver=\"2.0\"><dict><k>kCEK</k><i>4</i>\
+		 <k>k2</k><s>Spike</s><k>k4</k><s>{SPIKE_LEVEL}</s><k>k13</k><t /></dict></plist>"
+	);
+	assert_eq!(level::parse_gmd(&gmd).unwrap(), common::SINGLE_SPIKE);
+
+	// Some tools write the level string without compressing it
+	let raw = gmd.replace(SPIKE_LEVEL, common::SINGLE_SPIKE);
+	assert_eq!(level::parse_gmd(&raw).unwrap(), common::SINGLE_SPIKE);
+
+	assert!(matches!(
+		level::parse_gmd("<plist><dict><k>k2</k><s>Spike</s></dict></plist>"),
+		Err(level::ParserError::MissingGmdLevel)
+	));
+}
+
+#[test]
+fn standard_and_url_safe_base64_decode_alike() {
+	// Five spikes and a block, a payload whose encoding has both `+` and `/` in it
+	let standard =
+		"H4sIAAAAAAACA8t2NNIxsDbUsdAx0jE2MNAx1jE0hXGNUblmqFxLFK6JEZxrCORaghWbmFoDAMb/+qZgAAAA";
+	let url_safe = standard.replace('+', "-").replace('/', "_");
+	assert_ne!(standard, url_safe);
+
+	let from_standard = level::parse_level_data(standard).unwrap();
+	let from_url_safe = level::parse_level_data(&url_safe).unwrap();
+	assert_eq!(from_standard, from_url_safe);
+	assert_eq!(object_ids(&from_standard), ["8", "8", "8", "8", "8", "1"]);
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use redox_core::{formats::level, game_object::GameObjectType};
+
+/// Two blocks, a spike, a ship section bounded by ship and cube portals, both gravity
+/// portals, two speed portals and a dual portal
+const MIXED: &str = "kA2,0;1,1,2,300,3,15;1,1,2,330,3,15;1,8,2,400,3,15;1,13,2,600,3,45;\
+                     1,12,2,900,3,45;1,11,2,1000,3,45;1,10,2,1100,3,45;1,201,2,1200,3,45;\
+                     1,203,2,1300,3,45;1,286,2,1500,3,45;";
+
+#[test]
+fn counts_a_mixed_level() {
+	let stats = level::summarize(&common::objects(MIXED));
+
+	assert_eq!(stats.object_count, 10);
+	assert_eq!(stats.count(GameObjectType::Solid), 2);
+	assert_eq!(stats.count(GameObjectType::Hazard), 1);
+	assert_eq!(stats.count(GameObjectType::CubePortal), 1);
+	assert_eq!(stats.count(GameObjectType::Sawblade), 0);
+	assert_eq!(stats.ship_portals, 1);
+	assert_eq!(stats.gravity_portals, 2);
+	assert_eq!(stats.speed_portals, 2);
+	assert!(!stats.single_player);
+
+	// Bounded by the first block's left edge and the ground it sits on
+	let (min_x, max_x) = stats.x_range.unwrap();
+	assert_eq!(min_x, 285.0);
+	assert!(max_x > 1500.0);
+	assert_eq!(stats.y_range.unwrap().0, 0.0);
+}
+
+#[test]
+fn empty_level_has_no_bounds() {
+	let stats = level::summarize(&[]);
+
+	assert_eq!(stats.object_count, 0);
+	assert_eq!(stats.x_range, None);
+	assert!(stats.single_player);
+	assert_eq!(stats.max_x(), 0.0);
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use redox_core::config::Config;
+
+const GOAL_X: f32 = 3000.0;
+
+/// A ship portal, then a two block stub on the floor every 300 units, leaving a long
+/// open corridor between the ship's bounds to fly down
+fn ship_corridor() -> String {
+	let mut level = String::from("kA2,0;1,13,2,100,3,45;");
+	for x in (500..2900).step_by(300) {
+		level += &format!("1,1,2,{x},3,15;1,1,2,{x},3,45;");
+	}
+	level
+}
+
+fn holding_for(macro_hold_frames: Vec<u32>) -> common::Solution {
+	let mut config = Config::default();
+	config.search.macro_hold_frames = macro_hold_frames;
+	common::solve(&ship_corridor(), GOAL_X, config)
+}
+
+#[test]
+fn macro_edges_solve_a_ship_corridor_in_far_fewer_expansions() {
+	let per_frame = holding_for(Vec::new());
+	let macro_edges = holding_for(vec![2, 4, 8]);
+
+	assert!(
+		per_frame.reached_goal,
+		"stopped with {:?}",
+		per_frame.stop_reason
+	);
+	assert!(
+		macro_edges.reached_goal,
+		"stopped with {:?}",
+		macro_edges.stop_reason
+	);
+	assert!(
+		macro_edges.nodes_expanded * 2 < per_frame.nodes_expanded,
+		"{} expansions with macro edges, {} without",
+		macro_edges.nodes_expanded,
+		per_frame.nodes_expanded
+	);
+
+	// Expanded back into ticks, both take the same time up to the overshoot of one
+	// 8 tick edge past the goal
+	let seconds = |path: &[(_, f32)]| path.iter().map(|(_, d)| d).sum::<f32>();
+	let dt = Config::default().physics.dt();
+	assert!((seconds(&macro_edges.path) - seconds(&per_frame.path)).abs() < 8.0 * dt);
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use redox_core::{Vec2, config::Config, formats::level, pathfinder::Pathfinder};
+
+fn pathfinder() -> Pathfinder {
+	Pathfinder::with_config(common::objects(common::SPIKES_AND_BLOCK), Config::default())
+}
+
+#[test]
+fn solves_from_a_start_in_the_middle_of_the_level() {
+	let pathfinder = pathfinder();
+	let header = level::parse_header(common::SPIKES_AND_BLOCK);
+
+	// Past the first spikes and the block, with the last three spikes still ahead, and a
+	// goal out in the open field after the level's last object
+	let start = pathfinder.initial_state(Vec2::new(600.0, 15.0), &header);
+	assert_eq!(pathfinder.collides(&start), None);
+	let result = pathfinder.solve_from(start, 1500.0);
+	assert!(result.reached_goal, "stopped with {:?}", result.stop_reason);
+
+	let states = pathfinder.simulate_trajectory(start, &result.actions);
+	assert_eq!(states[0].position, Vec2::new(600.0, 15.0));
+	assert!(states.last().unwrap().position.x >= 1500.0);
+	assert!(
+		states
+			.iter()
+			.all(|state| pathfinder.collides(state).is_none())
+	);
+
+	// Starting later skips the work of the first half
+	let from_origin = common::solve(common::SPIKES_AND_BLOCK, 1500.0, Config::default());
+	assert!(result.nodes_expanded < from_origin.nodes_expanded);
+}
+
+#[test]
+fn start_inside_a_block_is_caught() {
+	let pathfinder = pathfinder();
+	let header = level::parse_header(common::SPIKES_AND_BLOCK);
+
+	let start = pathfinder.initial_state(Vec2::new(520.0, 15.0), &header);
+	assert_eq!(pathfinder.collides(&start), Some(1));
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use redox_core::{
+	Vec2,
+	config::Config,
+	pathfinder::{Pathfinder, search},
+	state::{Action, State},
+};
+
+/// A mirror portal at x 300 turning the player around, and a normal one at x 150 turning
+/// it back
+const MIRROR_SEGMENT: &str = "kA2,0;1,45,2,300,3,45;1,46,2,150,3,45;";
+
+#[test]
+fn x_decreases_while_mirrored() {
+	let pathfinder = Pathfinder::with_config(common::objects(MIRROR_SEGMENT), Config::default());
+
+	let mut state = State {
+		on_ground: true,
+		..common::cube_at(Vec2::new(200.0, 15.0))
+	};
+	let mut turns = 0;
+	let mut mirrored_ticks = 0;
+	for _ in 0..600 {
+		let next = pathfinder.simulate_step(&state, Action::None);
+		let dx = next.position.x - state.position.x;
+		if state.mirrored {
+			assert!(
+				dx < 0.0,
+				"moved {dx} while mirrored at x {}",
+				state.position.x
+			);
+			mirrored_ticks += 1;
+		} else {
+			assert!(dx > 0.0, "moved {dx} at x {}", state.position.x);
+		}
+
+		turns += usize::from(next.mirrored != state.mirrored);
+		state = next;
+
+		// Turned around by each portal once, the normal one leaves it heading right
+		if turns == 2 {
+			break;
+		}
+	}
+
+	assert_eq!(turns, 2);
+	assert!(!state.mirrored);
+	assert!(mirrored_ticks > 50);
+	assert!(state.position.x < 200.0);
+}
+
+#[test]
+fn heuristic_is_zero_anywhere_past_the_goal() {
+	let speeds = Config::default().physics.player_speeds;
+	let at = |x: f32| search::heuristic(&common::cube_at(Vec2::new(x, 15.0)), 600.0, &speeds, 1.0);
+
+	assert!(at(300.0) > 0.0);
+	assert_eq!(at(600.0), 0.0);
+	assert_eq!(at(900.0), 0.0);
+
+	// Mirrored short of the goal it still has the whole distance left
+	let mirrored = State {
+		mirrored: true,
+		..common::cube_at(Vec2::new(300.0, 15.0))
+	};
+	assert_eq!(search::heuristic(&mirrored, 600.0, &speeds, 1.0), at(300.0));
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use redox_core::{config::Config, pathfinder::Pathfinder};
+
+/// Blocks every 100 units, plus one stretched 10x wide whose left edge is far behind
+/// its neighbours
+const LEVEL: &str = "kA2,0;1,1,2,100,3,15;1,1,2,200,3,15;1,1,2,300,3,15;1,1,2,400,3,15;1,1,2,\
+                     500,3,15;1,1,2,250,3,105,128,10;";
+
+#[test]
+fn range_query_matches_a_linear_scan() {
+	let pathfinder = Pathfinder::with_config(common::objects(LEVEL), Config::default());
+
+	for (min_x, max_x) in [
+		(0.0, 50.0),
+		(90.0, 110.0),
+		(115.0, 184.0),
+		(214.0, 286.0),
+		(390.0, 390.0),
+		(420.0, 2000.0),
+		(600.0, 700.0),
+	] {
+		let expected: Vec<_> = pathfinder
+			.objects()
+			.iter()
+			.filter(|obj| {
+				let (min, max) = obj.aabb();
+				max.x >= min_x && min.x <= max_x
+			})
+			.map(|obj| obj.position)
+			.collect();
+
+		let actual: Vec<_> = pathfinder
+			.objects_in_range(min_x, max_x)
+			.map(|obj| obj.position)
+			.collect();
+
+		assert_eq!(actual, expected, "window {min_x}..={max_x}");
+	}
+}
+
+#[test]
+fn wide_object_is_found_from_its_far_end() {
+	let pathfinder = Pathfinder::with_config(common::objects(LEVEL), Config::default());
+
+	// Between two blocks, where only the stretched one reaches from its left edge at x=100
+	let found: Vec<_> = pathfinder
+		.objects_in_range(360.0, 360.0)
+		.map(|obj| (obj.position.x, obj.position.y))
+		.collect();
+
+	assert_eq!(found, vec![(250.0, 105.0)]);
+}
+
+#[test]
+fn objects_are_sorted_by_left_edge() {
+	let pathfinder = Pathfinder::with_config(common::objects(LEVEL), Config::default());
+
+	let left_edges: Vec<f32> = pathfinder
+		.objects()
+		.iter()
+		.map(|obj| obj.aabb().0.x)
+		.collect();
+
+	assert_eq!(pathfinder.objects().len(), 6);
+	assert!(left_edges.is_sorted());
+}
@@ -0,0 +1,139 @@
+use redox_core::game_object::GameObjectType::{self, *};
+
+const ALL: &[GameObjectType] = &[
+	Solid,
+	Hazard,
+	Sawblade,
+	InverseGravityPortal,
+	NormalGravityPortal,
+	ShipPortal,
+	CubePortal,
+	Decoration,
+	YellowJumpPad,
+	PinkJumpPad,
+	GravityPad,
+	YellowJumpRing,
+	PinkJumpRing,
+	GravityRing,
+	InverseMirrorPortal,
+	NormalMirrorPortal,
+	BallPortal,
+	RegularSizePortal,
+	MiniSizePortal,
+	UfoPortal,
+	Modifier,
+	Breakable,
+	SecretCoin,
+	DualPortal,
+	SoloPortal,
+	Slope,
+	WavePortal,
+	RobotPortal,
+	TeleportPortal,
+	GreenRing,
+	Collectible,
+	UserCoin,
+	DropRing,
+	SpiderPortal,
+	RedJumpPad,
+	RedJumpRing,
+	CustomRing,
+	DashRing,
+	GravityDashRing,
+	CollisionObject,
+	Special,
+	SwingPortal,
+	GravityTogglePortal,
+	SpiderOrb,
+	SpiderPad,
+	TeleportOrb,
+	AnimatedHazard,
+	Unknown,
+];
+
+fn matching(predicate: fn(GameObjectType) -> bool) -> Vec<GameObjectType> {
+	ALL.iter().copied().filter(|t| predicate(*t)).collect()
+}
+
+#[test]
+fn solids() {
+	assert_eq!(matching(GameObjectType::is_solid), [Solid]);
+}
+
+#[test]
+fn hazards() {
+	assert_eq!(matching(GameObjectType::is_hazard), [
+		Hazard,
+		Sawblade,
+		AnimatedHazard
+	]);
+}
+
+#[test]
+fn portals() {
+	assert_eq!(matching(GameObjectType::is_portal), [
+		InverseGravityPortal,
+		NormalGravityPortal,
+		ShipPortal,
+		CubePortal,
+		InverseMirrorPortal,
+		NormalMirrorPortal,
+		BallPortal,
+		RegularSizePortal,
+		MiniSizePortal,
+		UfoPortal,
+		DualPortal,
+		SoloPortal,
+		WavePortal,
+		RobotPortal,
+		TeleportPortal,
+		SpiderPortal,
+		SwingPortal,
+		GravityTogglePortal,
+	]);
+}
+
+#[test]
+fn pads() {
+	assert_eq!(matching(GameObjectType::is_pad), [
+		YellowJumpPad,
+		PinkJumpPad,
+		GravityPad,
+		RedJumpPad,
+		SpiderPad
+	]);
+}
+
+#[test]
+fn orbs() {
+	assert_eq!(matching(GameObjectType::is_orb), [
+		YellowJumpRing,
+		PinkJumpRing,
+		GravityRing,
+		GreenRing,
+		DropRing,
+		RedJumpRing,
+		CustomRing,
+		DashRing,
+		GravityDashRing,
+		SpiderOrb,
+		TeleportOrb,
+	]);
+}
+
+#[test]
+fn classes_do_not_overlap() {
+	for &t in ALL {
+		let classes = [
+			t.is_solid(),
+			t.is_hazard(),
+			t.is_portal(),
+			t.is_pad(),
+			t.is_orb(),
+		];
+		assert!(
+			classes.iter().filter(|c| **c).count() <= 1,
+			"{t:?} is in more than one class"
+		);
+	}
+}
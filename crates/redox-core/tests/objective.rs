@@ -0,0 +1,48 @@
+mod common;
+
+use redox_core::config::{Config, Objective};
+
+/// Four spikes four blocks apart. Every path jumps each of them, but with presses free
+/// nothing stops the search from jumping more often than it has to
+const SPIKE_ROW: &str = "kA2,0;1,8,2,300,3,15;1,8,2,420,3,15;1,8,2,540,3,15;1,8,2,660,3,15;";
+const GOAL_X: f32 = 900.0;
+
+fn solve(objective: Objective) -> common::Solution {
+	let mut config = Config::default();
+	config.search.objective = objective;
+	let solution = common::solve(SPIKE_ROW, GOAL_X, config);
+	assert!(solution.reached_goal, "{objective:?} didn't reach the goal");
+	solution
+}
+
+#[test]
+fn min_presses_taps_less_than_min_time() {
+	let min_time = solve(Objective::MinTime);
+	let balanced = solve(Objective::default());
+	let min_presses = solve(Objective::MinPresses);
+
+	assert!(
+		min_presses.presses() < min_time.presses(),
+		"{} presses against {}",
+		min_presses.presses(),
+		min_time.presses()
+	);
+	assert!(min_presses.presses() <= balanced.presses());
+}
+
+#[test]
+fn optimal_ignores_the_objective() {
+	let mut config = Config::default();
+	config.search.optimal = true;
+	config.search.objective = Objective::MinPresses;
+
+	assert_eq!(config.search.press_ticks(false), 0.0);
+	assert_eq!(config.search.press_ticks(true), 0.0);
+}
+
+#[test]
+fn balanced_presses_cost_less_when_flying() {
+	let objective = Objective::Balanced { press_weight: 15.0 };
+	assert_eq!(objective.press_ticks(false), 15.0);
+	assert_eq!(objective.press_ticks(true), 0.5);
+}
@@ -0,0 +1,26 @@
+mod common;
+
+use redox_core::config::Config;
+
+/// Frames needed to run `goal_x` units at normal speed, with nothing to slow the cube
+fn minimum_frames(goal_x: f32, config: &Config) -> usize {
+	let per_frame = config.physics.player_speeds[1] * config.physics.dt();
+	(goal_x / per_frame).ceil() as usize
+}
+
+fn frames(solution: &common::Solution, config: &Config) -> usize {
+	let total: f32 = solution.path.iter().map(|(_, duration)| duration).sum();
+	(total / config.physics.dt()).round() as usize
+}
+
+#[test]
+fn optimal_run_matches_hand_computed_minimum() {
+	let mut config = Config::default();
+	config.search.optimal = true;
+	let expected = minimum_frames(600.0, &config);
+
+	let solution = common::solve(common::SINGLE_SPIKE, 600.0, config);
+
+	assert!(solution.reached_goal);
+	assert_eq!(frames(&solution, &Config::default()), expected);
+}
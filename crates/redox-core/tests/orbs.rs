@@ -0,0 +1,154 @@
+mod common;
+
+use redox_core::{
+	Vec2,
+	config::Config,
+	pathfinder::Pathfinder,
+	state::{Action, State},
+};
+
+fn pathfinder(level: &str) -> Pathfinder {
+	Pathfinder::with_config(common::objects(level), Config::default())
+}
+
+#[test]
+fn green_ring_flips_gravity_mid_air() {
+	let pathfinder = pathfinder("kA2,0;1,1022,2,300,3,100;");
+
+	let mut state = common::cube_at(Vec2::new(300.0, 100.0));
+	state.vy = -200.0;
+
+	let next = pathfinder.simulate_step(&state, Action::Press);
+
+	assert!(next.gravity_flipped);
+	// Starting from rest, so one tick of flipped gravity has it rising slowly
+	assert!(next.vy > 0.0 && next.vy < 50.0, "vy={}", next.vy);
+
+	let ignored = pathfinder.simulate_step(&state, Action::None);
+	assert!(!ignored.gravity_flipped);
+	assert!(ignored.vy < -200.0);
+}
+
+#[test]
+fn dash_ring_carries_the_player_along_its_angle() {
+	// Rotated 45 degrees counter-clockwise, so the dash heads up and to the right
+	let pathfinder = pathfinder("kA2,0;1,1704,2,300,3,100,6,-45;");
+
+	let start = common::cube_at(Vec2::new(300.0, 100.0));
+	let mut state = pathfinder.simulate_step(&start, Action::Press);
+	assert!(state.dashing);
+
+	for _ in 0..60 {
+		state = pathfinder.simulate_step(&state, Action::None);
+	}
+
+	assert!(state.dashing);
+	let travelled = state.position - start.position;
+	assert!(
+		(travelled.x - travelled.y).abs() < 1.0,
+		"moved {travelled:?} instead of along the diagonal"
+	);
+
+	let released = pathfinder.simulate_step(&state, Action::Release);
+	assert!(!released.dashing);
+	assert!(released.vy < state.vy, "gravity should take over again");
+}
+
+/// The search offers a press whenever this finds a ring, even in mid-air
+#[test]
+fn touching_orb_needs_overlap() {
+	let pathfinder = pathfinder("kA2,0;1,1022,2,300,3,100;");
+
+	let state = common::cube_at(Vec2::new(300.0, 100.0));
+	assert!(pathfinder.touching_orb(&state).is_some());
+	assert!(
+		pathfinder
+			.touching_orb(&common::cube_at(Vec2::new(300.0, 200.0)))
+			.is_none()
+	);
+}
+
+#[test]
+fn yellow_ring_launches_mid_air() {
+	let config = Config::default();
+	let jump = config.physics.jump_velocities[1];
+	let pathfinder = pathfinder("kA2,0;1,36,2,300,3,100;");
+
+	let mut state = common::cube_at(Vec2::new(300.0, 100.0));
+	state.vy = -200.0;
+
+	let next = pathfinder.simulate_step(&state, Action::Press);
+	assert!(next.vy > jump * 0.9 && next.vy < jump, "vy={}", next.vy);
+
+	state.gravity_flipped = true;
+	let flipped = pathfinder.simulate_step(&state, Action::Press);
+	assert!(flipped.vy < -jump * 0.9, "vy={}", flipped.vy);
+}
+
+#[test]
+fn ring_clicks_once_per_pass() {
+	let pathfinder = pathfinder("kA2,0;1,1022,2,300,3,100;");
+
+	let mut state = common::cube_at(Vec2::new(290.0, 100.0));
+	state = pathfinder.simulate_step(&state, Action::Press);
+	assert!(state.gravity_flipped);
+
+	// Still on the ring, a second click doesn't flip back
+	state = pathfinder.simulate_step(&state, Action::Release);
+	assert!(pathfinder.touching_orb(&state).is_none());
+	state = pathfinder.simulate_step(&state, Action::Press);
+	assert!(state.gravity_flipped);
+
+	// Re-armed once the player is clear of it
+	while state.position.x < 360.0 {
+		state = pathfinder.simulate_step(&state, Action::None);
+	}
+	assert_eq!(state.last_orb, None);
+}
+
+/// Spikes under a yellow ring with a block past it. Clicking as soon as the ring is
+/// reached flies into the block, clicking late enough clears it and the spikes
+fn late_ring_level() -> String {
+	let spikes: String = (0..7)
+		.map(|i| format!("1,8,2,{},3,15;", 330 + i * 30))
+		.collect();
+	format!("kA2,0;1,36,2,400,3,90;1,1,2,460,3,165;{spikes}")
+}
+
+/// Mid-air at the apex of a jump, just short of the ring
+fn before_ring() -> State {
+	common::cube_at(Vec2::new(360.0, 90.0))
+}
+
+#[test]
+fn search_can_click_a_ring_late_in_the_overlap() {
+	let goal_x = 650.0;
+	let pathfinder = pathfinder(&late_ring_level());
+
+	// Clicking on the first tick of the overlap dies
+	let mut state = before_ring();
+	while pathfinder.touching_orb(&state).is_none() {
+		state = pathfinder.simulate_step(&state, Action::None);
+	}
+	let first_overlap_x = state.position.x;
+	state = pathfinder.simulate_step(&state, Action::Press);
+	while state.position.x < goal_x && !pathfinder.out_of_bounds(&state) {
+		state = pathfinder.simulate_step(&state, Action::None);
+		if pathfinder.colliding_object(&state).is_some() {
+			break;
+		}
+	}
+	assert!(state.position.x < goal_x, "the earliest click should die");
+
+	let result = pathfinder.solve_from(before_ring(), goal_x);
+	assert!(result.reached_goal);
+
+	let trajectory = pathfinder.simulate_trajectory(before_ring(), &result.actions);
+	// Only the ring makes the player rise faster from one tick to the next
+	let click = trajectory
+		.windows(2)
+		.find(|pair| pair[1].vy > pair[0].vy)
+		.map(|pair| pair[0].position.x)
+		.unwrap();
+	assert!(click > first_overlap_x + 20.0, "clicked at x={click}");
+}
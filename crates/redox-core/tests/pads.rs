@@ -0,0 +1,163 @@
+mod common;
+
+use redox_core::{
+	Vec2,
+	config::Config,
+	pathfinder::Pathfinder,
+	state::{Action, GameMode, State, StateKey},
+};
+
+/// Two yellow pads on the ground, far enough apart to land between them. The second is
+/// twice the size so the player spends longer on it
+const LEVEL: &str = "kA2,0;1,35,2,300,3,2;1,35,2,900,3,4,32,2;";
+
+/// Ticks that ended with the player launched at exactly a yellow pad's velocity
+fn launches(pathfinder: &Pathfinder, mut state: State, ticks: usize) -> usize {
+	let launch = Config::default().physics.pad_velocities[0];
+
+	let mut launches = 0;
+	for _ in 0..ticks {
+		state = pathfinder.simulate_step(&state, Action::None);
+		if state.vy == launch {
+			launches += 1;
+		}
+	}
+	launches
+}
+
+fn on_ground_at(x: f32) -> State {
+	State {
+		on_ground: true,
+		..common::cube_at(Vec2::new(x, 15.0))
+	}
+}
+
+#[test]
+fn holding_through_a_pad_launches_once() {
+	let pathfinder = Pathfinder::with_config(common::objects(LEVEL), Config::default());
+
+	for x in [270.0, 870.0] {
+		let held = State {
+			pressing: true,
+			..on_ground_at(x)
+		};
+		assert_eq!(launches(&pathfinder, held, 40), 1, "pad at {}", x + 30.0);
+	}
+}
+
+#[test]
+fn pad_rearms_after_leaving_it() {
+	let pathfinder = Pathfinder::with_config(common::objects(LEVEL), Config::default());
+
+	let mut state = on_ground_at(270.0);
+	assert_eq!(launches(&pathfinder, state, 600), 2);
+
+	for _ in 0..600 {
+		state = pathfinder.simulate_step(&state, Action::None);
+	}
+	assert!(state.position.x > 900.0);
+	assert_eq!(state.last_pad, None);
+}
+
+#[test]
+fn cooldown_is_part_of_the_key() {
+	let state = on_ground_at(300.0);
+	let cooling = State {
+		last_pad: Some(0),
+		..state
+	};
+
+	assert_ne!(
+		StateKey::from_state(&state, 1.0, 1.0, 10.0),
+		StateKey::from_state(&cooling, 1.0, 1.0, 10.0)
+	);
+}
+
+/// A pad of type `id` at x 300, then a ledge `height` units tall from x 360 on
+fn ledge_after(id: u32, height: u32) -> String {
+	let mut level = format!("kA2,0;1,{id},2,300,3,2;");
+	for column in 0..10 {
+		for row in 0..height / 30 {
+			level += &format!("1,1,2,{},3,{};", 375 + 30 * column, 15 + 30 * row);
+		}
+	}
+	level
+}
+
+/// Whether running straight over the pad lands the player on the ledge alive
+fn clears(level: &str) -> bool {
+	let pathfinder = Pathfinder::with_config(common::objects(level), Config::default());
+
+	let mut state = on_ground_at(200.0);
+	for _ in 0..300 {
+		state = pathfinder.simulate_step(&state, Action::None);
+		if pathfinder.collides(&state).is_some() {
+			return false;
+		}
+	}
+	state.position.x > 550.0
+}
+
+#[test]
+fn each_pad_clears_a_ledge_the_next_weaker_one_cannot() {
+	// Pink, then yellow, then red, each with the tallest ledge it still reaches
+	let ladder = [(140, 30), (35, 120), (1332, 150)];
+
+	assert!(
+		!clears(&ledge_after(1, 30)),
+		"a plain block launches nothing"
+	);
+	for (i, &(id, height)) in ladder.iter().enumerate() {
+		assert!(clears(&ledge_after(id, height)), "pad {id} over {height}");
+		if let Some(&(weaker, _)) = i.checked_sub(1).and_then(|i| ladder.get(i)) {
+			assert!(
+				!clears(&ledge_after(weaker, height)),
+				"pad {weaker} over {height}"
+			);
+		}
+	}
+}
+
+#[test]
+fn gravity_pad_lands_the_player_on_the_ceiling() {
+	// A roof with its underside at y 120 over the pad and beyond
+	let mut level = String::from("kA2,0;1,67,2,300,3,2;");
+	for column in 0..15 {
+		level += &format!("1,1,2,{},3,135;", 255 + 30 * column);
+	}
+	let pathfinder = Pathfinder::with_config(common::objects(&level), Config::default());
+
+	let mut state = on_ground_at(200.0);
+	for _ in 0..120 {
+		state = pathfinder.simulate_step(&state, Action::None);
+		assert!(
+			pathfinder.collides(&state).is_none(),
+			"died at {:?}",
+			state.position
+		);
+	}
+	assert!(state.gravity_flipped && state.on_ground);
+	assert!(
+		(state.position.y - 105.0).abs() < 0.01,
+		"at y {}",
+		state.position.y
+	);
+}
+
+#[test]
+fn gravity_pad_moves_a_ship_s_bounds() {
+	let pathfinder = Pathfinder::with_config(
+		common::objects("kA2,0;1,67,2,300,3,255;"),
+		Config::default(),
+	);
+	let ship = State {
+		mode: GameMode::Ship,
+		floor: 0.0,
+		ceiling: 300.0,
+		..common::cube_at(Vec2::new(300.0, 255.0))
+	};
+
+	let state = pathfinder.check_portal_collisions(ship);
+	assert!(state.gravity_flipped);
+	assert_eq!((state.floor, state.ceiling), (90.0, 390.0));
+}
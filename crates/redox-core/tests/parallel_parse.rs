@@ -0,0 +1,42 @@
+#![cfg(feature = "rayon")]
+
+use std::collections::HashMap;
+
+use redox_core::{
+	formats::level,
+	game_object::{GameObject, GameObjectType, HitboxShape},
+};
+
+const MEDIUM_LEVEL: &str = include_str!("../benches/levels/medium.txt");
+
+#[test]
+fn parallel_parse_matches_sequential() {
+	let overrides = HashMap::from([(
+		8,
+		(GameObjectType::Solid, HitboxShape::Rectangle, 30.0, 30.0),
+	)]);
+
+	// Repeated so there are enough segments to be split across threads
+	let level_string = MEDIUM_LEVEL.trim().repeat(20);
+
+	let sequential = level::parse_game_objects(&level_string, &overrides);
+	let parallel = level::par_parse_game_objects(&level_string, &overrides);
+
+	assert!(sequential.len() > 1000);
+	assert_eq!(parallel, sequential);
+
+	let from_raw: Vec<GameObject> = level::parse_objects(&level_string)
+		.iter()
+		.map(|raw| GameObject::from_raw_with_overrides(raw, &overrides))
+		.collect();
+	assert_eq!(sequential, from_raw);
+}
+
+#[test]
+fn header_and_empty_segments_are_skipped() {
+	let level_string = "kA2,1,kA4,2;;1,8,2,300,3,15;;1,1,2,330,3,15;";
+	let objects = level::par_parse_game_objects(level_string, &HashMap::new());
+
+	let ids: Vec<i32> = objects.iter().map(|obj| obj.id).collect();
+	assert_eq!(ids, [8, 1]);
+}
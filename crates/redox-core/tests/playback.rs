@@ -0,0 +1,36 @@
+use redox_core::pathfinder::playback::Playhead;
+
+#[test]
+fn stepping_forward_always_moves_forward() {
+	// Frames like 15 and 240 used to read back one short when stored as seconds
+	let mut playhead = Playhead::new(2000);
+	for expected in 1..2000 {
+		playhead.seek(playhead.frame() + 1);
+		assert_eq!(playhead.frame(), expected);
+	}
+
+	playhead.seek(playhead.frame() + 1);
+	assert_eq!(playhead.frame(), 1999);
+	assert!(playhead.at_end());
+
+	for expected in (0..1999).rev() {
+		playhead.seek(playhead.frame().saturating_sub(1));
+		assert_eq!(playhead.frame(), expected);
+	}
+}
+
+#[test]
+fn playing_stops_at_the_last_frame() {
+	let mut playhead = Playhead::new(11);
+	playhead.advance(4.5);
+	assert_eq!(playhead.frame(), 4);
+	assert_eq!(playhead.ticks(), 4.5);
+
+	playhead.advance(100.0);
+	assert!(playhead.at_end());
+	assert_eq!(playhead.frame(), 10);
+	assert_eq!(playhead.progress(), 1.0);
+
+	playhead.seek_progress(0.5);
+	assert_eq!(playhead.frame(), 5);
+}
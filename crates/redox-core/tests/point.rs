@@ -0,0 +1,44 @@
+mod common;
+
+use redox_core::{Point, config::Config, pathfinder::Pathfinder};
+
+#[test]
+fn search_starts_from_a_tuple() {
+	let pathfinder =
+		Pathfinder::with_config(common::objects(common::SINGLE_SPIKE), Config::default());
+	let session = pathfinder.start_search((0.0, 15.0), 600.0);
+
+	let start = session.all_nodes[0].state.position;
+	assert_eq!((start.x, start.y), (0.0, 15.0));
+	assert!(pathfinder.solve((0.0, 15.0), 600.0).reached_goal);
+}
+
+#[test]
+fn conversions_agree() {
+	let point = Point::new(1.5, -2.0);
+	assert_eq!(Point::from((1.5, -2.0)), point);
+	assert_eq!(Point::from([1.5, -2.0]), point);
+	assert_eq!(<(f32, f32)>::from(point), (1.5, -2.0));
+	assert_eq!(<[f32; 2]>::from(point), [1.5, -2.0]);
+}
+
+#[cfg(feature = "glam")]
+#[test]
+fn glam_conversions_agree() {
+	let point = Point::new(1.5, -2.0);
+	assert_eq!(Point::from(glam::Vec2::new(1.5, -2.0)), point);
+	assert_eq!(glam::Vec2::from(point), glam::Vec2::new(1.5, -2.0));
+
+	let pathfinder =
+		Pathfinder::with_config(common::objects(common::SINGLE_SPIKE), Config::default());
+	let session = pathfinder.start_search(glam::Vec2::new(0.0, 15.0), 600.0);
+	assert_eq!(session.all_nodes[0].state.position, Point::new(0.0, 15.0));
+}
+
+#[test]
+fn serializes_as_a_pair_like_glam() {
+	let point = Point::new(1.5, -2.0);
+	let json = serde_json::to_string(&point).unwrap();
+	assert_eq!(json, "[1.5,-2.0]");
+	assert_eq!(serde_json::from_str::<Point>(&json).unwrap(), point);
+}
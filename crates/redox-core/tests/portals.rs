@@ -0,0 +1,175 @@
+mod common;
+
+use redox_core::{
+	Vec2,
+	config::Config,
+	pathfinder::Pathfinder,
+	state::{Action, GameMode, State},
+};
+
+/// Runs an airborne cube from just before x=300 to well past it
+fn run_through(level_string: &str, mut state: State) -> State {
+	let pathfinder = Pathfinder::with_config(common::objects(level_string), Config::default());
+	while state.position.x < 340.0 {
+		state = pathfinder.simulate_step(&state, Action::None);
+	}
+	state
+}
+
+fn cube_before_portals() -> State {
+	common::cube_at(Vec2::new(270.0, 315.0))
+}
+
+#[test]
+fn adjacent_ship_and_gravity_portals() {
+	// Ship then inverse gravity, close enough to overlap the player together
+	for level in [
+		"kA2,0;1,13,2,300,3,315;1,11,2,310,3,315;",
+		"kA2,0;1,11,2,310,3,315;1,13,2,300,3,315;",
+	] {
+		let state = run_through(level, cube_before_portals());
+
+		assert_eq!(state.mode, GameMode::Ship, "{level}");
+		assert!(state.gravity_flipped, "{level}");
+		assert_eq!((state.floor, state.ceiling), (150.0, 450.0), "{level}");
+	}
+}
+
+#[test]
+fn size_portal_after_ship_keeps_its_bounds() {
+	let plain = run_through("kA2,0;1,13,2,300,3,315;", cube_before_portals());
+	let mini = run_through(
+		"kA2,0;1,13,2,300,3,315;1,99,2,310,3,315;",
+		cube_before_portals(),
+	);
+
+	assert!(mini.mini);
+	assert_eq!(
+		(mini.mode, mini.floor, mini.ceiling),
+		(plain.mode, plain.floor, plain.ceiling)
+	);
+}
+
+#[test]
+fn stacked_mode_portals_apply_left_to_right() {
+	// The cube portal is scaled up so its left edge comes first, but its center is
+	// further right, so it was passed last
+	let level = "kA2,0;1,13,2,300,3,315;1,12,2,310,3,315,32,2;";
+	let pathfinder = Pathfinder::with_config(common::objects(level), Config::default());
+
+	let mut ship = common::cube_at(Vec2::new(310.0, 315.0));
+	ship.mode = GameMode::Ship;
+	let state = pathfinder.check_portal_collisions(ship);
+
+	assert_eq!(state.mode, GameMode::Cube);
+	assert_eq!((state.floor, state.ceiling), (0.0, f32::MAX));
+}
+
+#[test]
+fn flipped_ship_exit_resets_bounds() {
+	let mut ship = cube_before_portals();
+	ship.mode = GameMode::Ship;
+	ship.gravity_flipped = true;
+	ship.floor = 150.0;
+	ship.ceiling = 450.0;
+
+	let state = run_through("kA2,0;1,12,2,300,3,315;", ship);
+
+	assert_eq!(state.mode, GameMode::Cube);
+	assert!(state.gravity_flipped);
+	assert_eq!((state.floor, state.ceiling), (0.0, f32::MAX));
+}
+
+#[test]
+fn gravity_toggle_portal_only_flips_while_inside() {
+	// 25 wide and 75 tall around (300, 45), overlapping the grounded player for
+	// 272.5 < x < 327.5
+	let level = "kA2,0;1,2926,2,300,3,45;";
+	let pathfinder = Pathfinder::with_config(common::objects(level), Config::default());
+
+	let mut state = common::cube_at(Vec2::new(200.0, 15.0));
+	state.on_ground = true;
+
+	let mut flipped_ticks = 0;
+	while state.position.x < 400.0 {
+		state = pathfinder.simulate_step(&state, Action::None);
+
+		let x = state.position.x;
+		// A unit of slack either side of the edges, where rounding decides overlap
+		if (273.5..=326.5).contains(&x) {
+			assert!(state.gravity_flipped, "not flipped inside at x={x}");
+			flipped_ticks += 1;
+		} else if !(271.5..=328.5).contains(&x) {
+			assert!(!state.gravity_flipped, "flipped outside at x={x}");
+		}
+	}
+
+	assert!(flipped_ticks > 0);
+}
+
+#[test]
+fn ship_entered_below_its_floor_starts_inside_the_bounds() {
+	// Scaled up so the player overlaps it well below the floor it sets
+	let level = "kA2,0;1,13,2,300,3,315,32,4;";
+	let pathfinder = Pathfinder::with_config(common::objects(level), Config::default());
+
+	let mut cube = common::cube_at(Vec2::new(300.0, 160.0));
+	cube.vy = -200.0;
+	let ship = pathfinder.check_portal_collisions(cube);
+
+	assert_eq!(ship.mode, GameMode::Ship);
+	assert_eq!((ship.floor, ship.ceiling), (150.0, 450.0));
+	assert_eq!(ship.position.y, 165.0);
+	assert_eq!(ship.vy, cube.vy);
+
+	// Not clipped into the floor, so a press climbs straight away
+	let next = pathfinder.simulate_step(&ship, Action::Press);
+	assert!(next.vy > ship.vy);
+	assert!(next.position.y > ship.position.y - 1.0);
+}
+
+#[test]
+fn cube_portal_out_of_ship_falls_normally() {
+	let level = "kA2,0;1,12,2,300,3,315;";
+	let pathfinder = Pathfinder::with_config(common::objects(level), Config::default());
+
+	let mut ship = common::cube_at(Vec2::new(300.0, 315.0));
+	ship.mode = GameMode::Ship;
+	ship.floor = 150.0;
+	ship.ceiling = 450.0;
+
+	let mut state = pathfinder.check_portal_collisions(ship);
+	assert_eq!(state.mode, GameMode::Cube);
+	for _ in 0..10 {
+		state = pathfinder.simulate_step(&state, Action::None);
+	}
+
+	assert!(!state.on_ground);
+	assert!(state.vy < 0.0);
+	assert!(state.position.y < 315.0);
+}
+
+#[test]
+fn cube_portal_out_of_a_climbing_ship_caps_its_velocity() {
+	let level = "kA2,0;1,12,2,300,3,315;";
+	let config = Config::default();
+	let jump = config.physics.jump_velocities[1];
+	let pathfinder = Pathfinder::with_config(common::objects(level), config);
+
+	let mut ship = common::cube_at(Vec2::new(300.0, 315.0));
+	ship.mode = GameMode::Ship;
+	ship.floor = 150.0;
+	ship.ceiling = 450.0;
+	ship.pressing = true;
+	ship.vy = 800.0;
+
+	let cube = pathfinder.check_portal_collisions(ship);
+	assert_eq!(cube.mode, GameMode::Cube);
+	assert!(!cube.on_ground);
+	assert!(cube.vy > 0.0 && cube.vy <= jump, "vy {}", cube.vy);
+	assert!(cube.pressing);
+
+	// A slow climb keeps half of it
+	ship.vy = 200.0;
+	assert_eq!(pathfinder.check_portal_collisions(ship).vy, 100.0);
+}
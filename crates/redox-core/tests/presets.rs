@@ -0,0 +1,48 @@
+mod common;
+
+use redox_core::config::{Config, PhysicsParams};
+
+fn assert_close(actual: f32, expected: f32) {
+	assert!(
+		(actual - expected).abs() < 0.01,
+		"{actual} is not {expected}"
+	);
+}
+
+#[test]
+fn gd_2_2_matches_game_constants() {
+	let params = PhysicsParams::gd_2_2();
+
+	for speed in 0..5 {
+		assert_close(params.gravities[speed], -2794.108);
+		assert_close(params.jump_velocities[speed], 603.722);
+		assert_close(params.ship_velocities[speed], 103.485);
+	}
+
+	let defaults = PhysicsParams::default();
+	for (gd, default) in params.player_speeds.iter().zip(defaults.player_speeds) {
+		assert_close(*gd, default);
+	}
+
+	for (accel, expected) in params
+		.ship_accels
+		.into_iter()
+		.zip([1397.054, 1117.643, 1341.172, 894.115])
+	{
+		assert_close(accel, expected);
+	}
+
+	// Everything the preset doesn't pin down stays at the default
+	assert_eq!(params.tps, defaults.tps);
+	assert_eq!(params.ship_bounds, defaults.ship_bounds);
+	assert_eq!(params.hazard_leniency, defaults.hazard_leniency);
+	assert_eq!(params.hazard_hitbox_scale, defaults.hazard_hitbox_scale);
+}
+
+#[test]
+fn short_level_solves_under_gd_accurate() {
+	let solution = common::solve(common::SPIKES_AND_BLOCK, 1100.0, Config::gd_accurate());
+
+	assert!(solution.reached_goal, "stopped at x {}", solution.final_x);
+	assert!(solution.presses() > 0);
+}
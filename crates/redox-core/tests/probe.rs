@@ -0,0 +1,38 @@
+mod common;
+
+use redox_core::{Vec2, config::Config, formats::level, pathfinder::Pathfinder};
+
+fn reachable_x(level_string: &str, max_nodes: usize) -> f32 {
+	let pathfinder = Pathfinder::with_config(common::objects(level_string), Config::default());
+	let start = pathfinder.initial_state(common::START, &level::parse_header(level_string));
+	pathfinder.reachable_x(start, max_nodes)
+}
+
+#[test]
+fn blocked_level_stalls_before_the_wall() {
+	// A column of blocks far too tall to jump
+	let mut level = String::from(common::FLAT);
+	for row in 0..12 {
+		level += &format!("1,1,2,600,3,{};", 15 + row * 30);
+	}
+
+	let reached = reachable_x(&level, 5_000);
+
+	assert!(reached < 600.0, "got past the wall to x={reached}");
+	assert!(reached > 500.0, "stopped early at x={reached}");
+}
+
+#[test]
+fn open_level_runs_on() {
+	let reached = reachable_x(common::SINGLE_SPIKE, 1_000);
+
+	assert!(reached > 900.0, "stopped at x={reached}");
+}
+
+#[test]
+fn zero_budget_stays_at_start() {
+	let pathfinder = Pathfinder::with_config(Vec::new(), Config::default());
+	let start = common::cube_at(Vec2::new(42.0, 15.0));
+
+	assert_eq!(pathfinder.reachable_x(start, 0), 42.0);
+}
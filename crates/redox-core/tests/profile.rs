@@ -0,0 +1,37 @@
+mod common;
+
+use redox_core::{
+	config::Config,
+	formats::level,
+	pathfinder::{
+		Pathfinder,
+		search::{PhaseTimings, SearchSession},
+	},
+};
+
+const LEVEL: &str = "kA2,0;1,8,2,300,3,15;1,1,2,520,3,15;";
+
+fn search(profile: bool) -> SearchSession {
+	let mut config = Config::default();
+	config.search.profile = profile;
+	let pathfinder = Pathfinder::with_config(common::objects(LEVEL), config);
+
+	let start = pathfinder.initial_state(common::START, &level::parse_header(LEVEL));
+	let mut session = pathfinder.start_search_from(start, 800.0);
+	pathfinder.step(&mut session, 800.0);
+	session
+}
+
+#[test]
+fn profiling_times_every_phase_without_changing_the_search() {
+	let profiled = search(true);
+	let plain = search(false);
+
+	assert_eq!(profiled.nodes_expanded, plain.nodes_expanded);
+	assert_eq!(profiled.goal_reached_index, plain.goal_reached_index);
+
+	for (name, time) in profiled.timings.phases() {
+		assert!(!time.is_zero(), "{name} was never timed");
+	}
+	assert_eq!(plain.timings, PhaseTimings::default());
+}
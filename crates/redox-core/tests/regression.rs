@@ -0,0 +1,71 @@
+//! Known-good outcomes for tiny hand-built levels, so physics or search changes that
+//! alter them show up as failures
+
+mod common;
+
+use redox_core::config::Config;
+
+struct Case {
+	name: &'static str,
+	level: &'static str,
+	goal_x: f32,
+	presses: usize,
+	/// Generous bound on expansions, about twice what the search needed when recorded
+	max_nodes: usize,
+}
+
+const CASES: &[Case] = &[
+	Case {
+		name: "flat run",
+		level: common::FLAT,
+		goal_x: 600.0,
+		presses: 0,
+		max_nodes: 1_000,
+	},
+	Case {
+		name: "single spike",
+		level: common::SINGLE_SPIKE,
+		goal_x: 600.0,
+		presses: 1,
+		max_nodes: 1_200,
+	},
+	// Up onto a ledge, then across a two-spike gap to the next one
+	Case {
+		name: "single gap",
+		level: "kA2,0;1,1,2,300,3,15;1,1,2,330,3,15;1,1,2,360,3,15;1,1,2,390,3,15;1,1,2,420,3,\
+		        15;1,1,2,450,3,15;1,1,2,480,3,15;1,8,2,525,3,15;1,8,2,555,3,15;1,1,2,600,3,15;1,1,\
+		        2,630,3,15;1,1,2,660,3,15;1,1,2,690,3,15;1,1,2,720,3,15;1,1,2,750,3,15;1,1,2,780,\
+		        3,15;",
+		goal_x: 900.0,
+		presses: 2,
+		max_nodes: 2_000,
+	},
+];
+
+#[test]
+fn bundled_levels_match_recorded_solutions() {
+	for case in CASES {
+		let solution = common::solve(case.level, case.goal_x, Config::default());
+
+		assert!(solution.reached_goal, "{}: goal not reached", case.name);
+		assert!(
+			solution.final_x >= case.goal_x,
+			"{}: ended at x={} before the goal",
+			case.name,
+			solution.final_x
+		);
+		assert_eq!(
+			solution.presses(),
+			case.presses,
+			"{}: press count",
+			case.name
+		);
+		assert!(
+			solution.nodes_expanded <= case.max_nodes,
+			"{}: expanded {} nodes, ceiling is {}",
+			case.name,
+			solution.nodes_expanded,
+			case.max_nodes
+		);
+	}
+}
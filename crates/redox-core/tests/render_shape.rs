@@ -0,0 +1,62 @@
+mod common;
+
+use redox_core::{Vec2, game_object::RenderShape};
+
+fn close(a: Vec2, b: Vec2) -> bool {
+	a.distance(b) < 0.01
+}
+
+#[test]
+fn rotated_block_is_a_rect_with_rotated_corners() {
+	// A 30x30 block turned 45 degrees, so its corners sit straight out from the center
+	let block = &common::objects("1,1,2,100,3,100,6,45;")[0];
+
+	let RenderShape::Rect { corners } = block.render_shape() else {
+		panic!("block drew as {:?}", block.render_shape());
+	};
+
+	let reach = 15.0 * 2.0_f32.sqrt();
+	let center = Vec2::new(100.0, 100.0);
+	for expected in [
+		center - Vec2::new(0.0, reach),
+		center + Vec2::new(reach, 0.0),
+		center + Vec2::new(0.0, reach),
+		center - Vec2::new(reach, 0.0),
+	] {
+		assert!(
+			corners.iter().any(|&corner| close(corner, expected)),
+			"no corner at {expected:?} in {corners:?}"
+		);
+	}
+}
+
+#[test]
+fn sawblade_is_a_circle_at_its_position() {
+	// Large sawblade, a 32.3 wide circle scaled up by half
+	let saw = &common::objects("1,88,2,200,3,60,32,1.5;")[0];
+
+	let RenderShape::Circle { center, radius } = saw.render_shape() else {
+		panic!("sawblade drew as {:?}", saw.render_shape());
+	};
+
+	assert!(close(center, Vec2::new(200.0, 60.0)));
+	assert!((radius - 32.3 * 1.5 * 0.5).abs() < 0.01);
+}
+
+#[test]
+fn capsule_blade_takes_its_segment_from_the_scaled_size() {
+	for (scale, radius, half_length) in [(1.0, 6.0, 6.5), (2.0, 12.0, 13.0)] {
+		let blade = &common::objects(&format!("1,1619,2,100,3,100,32,{scale};"))[0];
+
+		let (a, b, r) = blade.capsule_segment().expect("blade should be a capsule");
+		assert_eq!(r, radius, "scale {scale}");
+		assert!(
+			close(a, Vec2::new(100.0 - half_length, 100.0)),
+			"scale {scale}: {a:?}"
+		);
+		assert!(
+			close(b, Vec2::new(100.0 + half_length, 100.0)),
+			"scale {scale}: {b:?}"
+		);
+	}
+}
@@ -0,0 +1,129 @@
+mod common;
+
+use redox_core::{
+	config::Config,
+	pathfinder::{
+		Pathfinder,
+		search::{NodeIndexWrapper, SearchSession},
+	},
+	state::StateKey,
+};
+
+const GOAL_X: f32 = 600.0;
+
+/// A session whose start node was already expanded once, at a cost of `closed_g`,
+/// with the start node back as the only open entry
+fn reached_again(pathfinder: &Pathfinder, config: &Config, closed_g: f32) -> SearchSession {
+	let mut session = pathfinder.start_search(common::START, GOAL_X);
+	pathfinder.step_single(&mut session, GOAL_X);
+	assert_eq!(session.closed_set.len(), 1);
+
+	for best_g in session.closed_set.values_mut() {
+		*best_g = closed_g;
+	}
+	session.open_set.clear();
+	session.open_set.push(NodeIndexWrapper::new(
+		&session.all_nodes[0],
+		0,
+		&config.search,
+	));
+	session
+}
+
+/// Whether stepping the session expanded its node, rather than skipping it as closed
+fn expands(pathfinder: &Pathfinder, session: &mut SearchSession) -> bool {
+	let generated = session.all_nodes.len();
+	pathfinder.step_single(session, GOAL_X);
+	session.all_nodes.len() > generated
+}
+
+#[test]
+fn cheaper_path_found_later_is_expanded() {
+	let config = Config::default();
+	let pathfinder = Pathfinder::with_config(common::objects(common::FLAT), config.clone());
+
+	// The start node costs nothing, far below the earlier expansion
+	let mut session = reached_again(&pathfinder, &config, 1.0);
+	assert!(expands(&pathfinder, &mut session));
+	assert_eq!(session.closed_set.values().copied().collect::<Vec<_>>(), [
+		0.0
+	]);
+}
+
+#[test]
+fn costlier_path_is_skipped() {
+	let config = Config::default();
+	let pathfinder = Pathfinder::with_config(common::objects(common::FLAT), config.clone());
+
+	let mut session = reached_again(&pathfinder, &config, -1.0);
+	assert!(!expands(&pathfinder, &mut session));
+
+	// A reopen never raises the cost recorded for the key
+	assert_eq!(session.closed_set.values().copied().collect::<Vec<_>>(), [
+		-1.0
+	]);
+}
+
+#[test]
+fn equally_cheap_path_is_skipped() {
+	let config = Config::default();
+	let pathfinder = Pathfinder::with_config(common::objects(common::FLAT), config.clone());
+
+	let mut session = reached_again(&pathfinder, &config, 0.0);
+	assert!(!expands(&pathfinder, &mut session));
+}
+
+#[test]
+fn slack_skips_barely_cheaper_paths() {
+	let mut config = Config::default();
+	let just_over = 0.4 * config.physics.dt();
+
+	let pathfinder = Pathfinder::with_config(common::objects(common::FLAT), config.clone());
+	let mut session = reached_again(&pathfinder, &config, just_over);
+	assert!(!expands(&pathfinder, &mut session));
+
+	config.search.reopen_slack = 0.0;
+	let pathfinder = Pathfinder::with_config(common::objects(common::FLAT), config.clone());
+	let mut session = reached_again(&pathfinder, &config, just_over);
+	assert!(expands(&pathfinder, &mut session));
+	assert_eq!(session.closed_set.values().copied().collect::<Vec<_>>(), [
+		0.0
+	]);
+}
+
+/// Successors pushed by expanding the start node, after one of them was already
+/// expanded at `extra_ticks` more than it costs from the start
+fn pushed_after_closing_a_successor(config: &Config, extra_ticks: f32) -> usize {
+	let pathfinder = Pathfinder::with_config(common::objects(common::FLAT), config.clone());
+
+	let mut first = pathfinder.start_search(common::START, GOAL_X);
+	pathfinder.step_single(&mut first, GOAL_X);
+	let successor = &first.all_nodes[1];
+	let search = &config.search;
+	let key = StateKey::with_layout(
+		&successor.state,
+		search.x_quant,
+		search.y_quant,
+		search.vy_quant,
+		search.key_layout,
+	);
+
+	let mut session = pathfinder.start_search(common::START, GOAL_X);
+	session
+		.closed_set
+		.insert(key, successor.g + extra_ticks * config.physics.dt());
+	pathfinder.step_single(&mut session, GOAL_X);
+	session.all_nodes.len() - 1
+}
+
+#[test]
+fn slack_applies_before_pushing_too() {
+	let mut config = Config::default();
+	let all = pushed_after_closing_a_successor(&config, 10.0);
+
+	// Only 0.4 ticks cheaper, so it would be skipped on pop and isn't pushed
+	assert_eq!(pushed_after_closing_a_successor(&config, 0.4), all - 1);
+
+	config.search.reopen_slack = 0.0;
+	assert_eq!(pushed_after_closing_a_successor(&config, 0.4), all);
+}
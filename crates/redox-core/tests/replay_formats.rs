@@ -0,0 +1,126 @@
+mod common;
+
+use redox_core::{
+	config::Config,
+	formats::level,
+	pathfinder::Pathfinder,
+	replay::{self, Clicks, Gdr, Json, ReplayFormat},
+	state::Action,
+};
+
+const TPS: f32 = 240.0;
+
+/// A jump, a held jump and a long run out, in the merged form the solver produces
+fn path() -> Vec<(Action, f32)> {
+	let frames = |n: u32| n as f32 / TPS;
+	vec![
+		(Action::None, frames(30)),
+		(Action::Press, frames(1)),
+		(Action::Release, frames(1)),
+		(Action::None, frames(100)),
+		(Action::Press, frames(12)),
+		(Action::Release, frames(1)),
+		(Action::None, frames(500)),
+	]
+}
+
+#[test]
+fn built_in_formats_write_something() {
+	let formats: [&dyn ReplayFormat; 3] = [&Gdr, &Clicks, &Json];
+	for format in formats {
+		assert!(!format.encode(&path(), TPS).unwrap().is_empty());
+	}
+
+	assert!(Gdr.is_binary());
+	assert!(!Json.is_binary() && !Clicks.is_binary());
+}
+
+#[test]
+fn json_round_trips() {
+	let encoded = Json.encode(&path(), TPS).unwrap();
+	let decoded = replay::decode_json(&encoded).unwrap();
+
+	assert_eq!(
+		replay::to_click_frames(&decoded, TPS),
+		replay::to_click_frames(&path(), TPS)
+	);
+	assert_eq!(Json.encode(&decoded, TPS).unwrap(), encoded);
+
+	// Decodes the same way the equivalent GDR replay does
+	let replay = replay::Replay::deserialize(&Gdr.encode(&path(), TPS).unwrap()).unwrap();
+	assert_eq!(decoded, replay.to_actions());
+}
+
+#[test]
+fn solved_gdr_replays_to_the_same_x() {
+	let config = Config::default();
+	let solution = common::solve(common::SPIKES_AND_BLOCK, 1100.0, config.clone());
+	assert!(solution.reached_goal);
+
+	let out = std::env::temp_dir().join(format!("redox-round-trip-{}.gdr", std::process::id()));
+	replay::save_gdr(&solution.path, out.to_str().unwrap(), config.physics.tps).unwrap();
+	let loaded = replay::load_gdr(&out).unwrap();
+	std::fs::remove_file(&out).unwrap();
+
+	let pathfinder = Pathfinder::with_config(common::objects(common::SPIKES_AND_BLOCK), config);
+	let header = level::parse_header(common::SPIKES_AND_BLOCK);
+	let start = pathfinder.initial_state(common::START, &header);
+	let end = *pathfinder
+		.simulate_trajectory(start, &loaded)
+		.last()
+		.unwrap();
+
+	assert!(
+		(end.position.x - solution.final_x).abs() < 1e-3,
+		"replayed to x {}, solved to x {}",
+		end.position.x,
+		solution.final_x
+	);
+}
+
+#[test]
+fn overflowing_death_frames_are_rejected() {
+	let mut replay = replay::Replay::deserialize(&Gdr.encode(&path(), TPS).unwrap()).unwrap();
+	replay.deaths = vec![u64::MAX, u64::MAX];
+	let mut bytes = replay.serialize().unwrap();
+
+	// Deaths are stored as deltas, bump the second from 0 to 1 so the sum wraps
+	let max = [[0xFF; 9].as_slice(), &[0x01, 0x00]].concat();
+	let at = bytes.windows(max.len()).position(|w| w == max).unwrap();
+	bytes[at + max.len() - 1] = 0x01;
+
+	assert!(replay::Replay::deserialize(&bytes).is_err());
+}
+
+#[test]
+fn click_frames_line_up_with_simulated_steps() {
+	let config = Config::default();
+	let tps = config.physics.tps;
+	let solution = common::solve(common::SPIKES_AND_BLOCK, 1100.0, config.clone());
+
+	let pathfinder = Pathfinder::with_config(common::objects(common::SPIKES_AND_BLOCK), config);
+	let header = level::parse_header(common::SPIKES_AND_BLOCK);
+	let start = pathfinder.initial_state(common::START, &header);
+	let states = pathfinder.simulate_trajectory(start, &solution.path);
+
+	// Every press state change lands on the step the simulator applied it
+	let clicks = replay::to_click_frames(&solution.path, tps);
+	assert!(!clicks.is_empty());
+	for &(frame, down) in &clicks {
+		let frame = frame as usize;
+		assert_eq!(states[frame].pressing, !down, "before frame {frame}");
+		assert_eq!(states[frame + 1].pressing, down, "after frame {frame}");
+	}
+
+	// Flipping the press state after the path lands exactly on the step count
+	let steps = states.len() - 1;
+	let flip = if states[steps].pressing {
+		Action::Release
+	} else {
+		Action::Press
+	};
+	let mut marked = solution.path.clone();
+	marked.push((flip, 1.0 / tps));
+	let (last_frame, _) = *replay::to_click_frames(&marked, tps).last().unwrap();
+	assert_eq!(last_frame as usize, steps);
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use redox_core::{
+	Vec2,
+	config::Config,
+	pathfinder::Pathfinder,
+	state::{Action, GameMode, State, StateKey},
+};
+
+fn robot_on_ground() -> State {
+	State {
+		mode: GameMode::Robot,
+		on_ground: true,
+		..common::cube_at(Vec2::new(0.0, 15.0))
+	}
+}
+
+/// Highest y a robot reaches when the jump is held for `hold` ticks and then released
+fn apex(hold: usize) -> f32 {
+	let pathfinder = Pathfinder::with_config(common::objects(common::FLAT), Config::default());
+
+	let mut state = pathfinder.simulate_step(&robot_on_ground(), Action::Press);
+	for _ in 1..hold {
+		state = pathfinder.simulate_step(&state, Action::None);
+	}
+	state = pathfinder.simulate_step(&state, Action::Release);
+
+	let mut highest = state.position.y;
+	while !state.on_ground {
+		state = pathfinder.simulate_step(&state, Action::None);
+		highest = highest.max(state.position.y);
+	}
+	highest
+}
+
+#[test]
+fn longer_holds_jump_higher_up_to_a_cap() {
+	let tap = apex(1);
+	let medium = apex(10);
+	let long = apex(40);
+
+	assert!(tap > 15.0, "a tap still jumps");
+	assert!(medium > tap + 5.0, "tap {tap}, medium {medium}");
+	assert!(long > medium + 5.0, "medium {medium}, long {long}");
+
+	// Past the hold window, holding longer changes nothing
+	assert_eq!(apex(200), apex(400));
+}
+
+#[test]
+fn hold_frames_are_part_of_the_key() {
+	let rising = State {
+		on_ground: false,
+		pressing: true,
+		jump_hold_frames: 3,
+		..robot_on_ground()
+	};
+	let later = State {
+		jump_hold_frames: 4,
+		..rising
+	};
+
+	assert_ne!(
+		StateKey::from_state(&rising, 1.0, 1.0, 10.0),
+		StateKey::from_state(&later, 1.0, 1.0, 10.0)
+	);
+}
@@ -0,0 +1,41 @@
+mod common;
+
+use redox_core::{
+	config::Config,
+	formats::level,
+	pathfinder::{Pathfinder, search::SearchProgress},
+};
+
+const GOAL_X: f32 = 1100.0;
+
+fn drain(config: Config) -> Vec<SearchProgress> {
+	let pathfinder = Pathfinder::with_config(common::objects(common::SPIKES_AND_BLOCK), config);
+	let header = level::parse_header(common::SPIKES_AND_BLOCK);
+	let start = pathfinder.initial_state(common::START, &header);
+
+	let mut session = pathfinder.start_search_from(start, GOAL_X);
+	pathfinder.search_iter(&mut session, GOAL_X).collect()
+}
+
+#[test]
+fn draining_ends_with_a_solved_item_whose_path_reaches_the_goal() {
+	let mut config = Config::default();
+	config.search.progress_interval = 100;
+	let items = drain(config);
+
+	let (last, running) = items.split_last().unwrap();
+	assert!(running.len() > 1);
+	assert!(running.iter().all(|item| !item.is_finished()));
+	assert!(
+		running
+			.windows(2)
+			.all(|w| w[1].nodes_expanded == w[0].nodes_expanded + 100)
+	);
+
+	assert!(last.reached_goal());
+	assert!(last.best_path.last().unwrap().x >= GOAL_X);
+	assert_eq!(
+		last.nodes_expanded,
+		common::solve(common::SPIKES_AND_BLOCK, GOAL_X, Config::default()).nodes_expanded
+	);
+}
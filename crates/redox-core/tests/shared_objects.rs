@@ -0,0 +1,42 @@
+mod common;
+
+use std::sync::Arc;
+
+use redox_core::{config::Config, game_object::GameObject, pathfinder::Pathfinder};
+
+const MEDIUM_LEVEL: &str = include_str!("../benches/levels/medium.txt");
+const GOAL_X: f32 = 1500.0;
+
+#[test]
+fn pathfinders_sharing_objects_agree() {
+	let first = Pathfinder::with_config(common::objects(MEDIUM_LEVEL), Config::default());
+	let second = Pathfinder::with_config(first.shared_objects(), Config::default());
+
+	// Built straight on the first one's allocation, nothing was copied
+	assert!(Arc::ptr_eq(
+		&first.shared_objects(),
+		&second.shared_objects()
+	));
+
+	let a = first.solve(common::START, GOAL_X);
+	let b = second.solve(common::START, GOAL_X);
+	assert!(a.reached_goal);
+	assert_eq!(a.actions, b.actions);
+	assert_eq!(a.nodes_expanded, b.nodes_expanded);
+}
+
+#[test]
+fn objects_are_copied_only_when_something_changes() {
+	// Out of order and with a duplicate spike, so the builder has to make its own copy
+	let objects: Arc<[GameObject]> =
+		common::objects("kA2,0;1,8,2,600,3,15;1,8,2,300,3,15;1,8,2,300,3,15;").into();
+	let pathfinder = Pathfinder::with_config(Arc::clone(&objects), Config::default());
+
+	assert!(!Arc::ptr_eq(&objects, &pathfinder.shared_objects()));
+	assert_eq!(pathfinder.objects().len(), 2);
+	assert!(pathfinder.objects()[0].position.x < pathfinder.objects()[1].position.x);
+
+	let sorted = pathfinder.shared_objects();
+	let rebuilt = Pathfinder::with_config(Arc::clone(&sorted), Config::default());
+	assert!(Arc::ptr_eq(&sorted, &rebuilt.shared_objects()));
+}
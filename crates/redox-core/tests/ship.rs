@@ -0,0 +1,88 @@
+mod common;
+
+use redox_core::{
+	config::Config,
+	pathfinder::Pathfinder,
+	state::{Action, GameMode, State},
+};
+
+/// A ship with nothing above it, so only the velocity clamp caps the climb
+fn ship() -> State {
+	State {
+		mode: GameMode::Ship,
+		..common::cube_at(common::START)
+	}
+}
+
+/// Highest the ship gets after holding for a second and then letting go
+fn apex(config: Config) -> f32 {
+	let pathfinder = Pathfinder::with_config(Vec::new(), config);
+
+	let mut state = pathfinder.simulate_step(&ship(), Action::Press);
+	for _ in 0..pathfinder.tps() as usize {
+		state = pathfinder.simulate_step(&state, Action::None);
+	}
+
+	state = pathfinder.simulate_step(&state, Action::Release);
+	let mut apex = state.position.y;
+	while state.vy > 0.0 {
+		state = pathfinder.simulate_step(&state, Action::None);
+		apex = apex.max(state.position.y);
+	}
+
+	apex
+}
+
+#[test]
+fn velocity_clamp_caps_the_climb() {
+	let default = apex(Config::default());
+
+	let mut slow = Config::default();
+	slow.physics.ship_vy_clamp[ship().speed] = 400.0;
+	let slow = apex(slow);
+
+	assert!(
+		slow < default,
+		"apex {slow} with the lower clamp, {default} without"
+	);
+
+	// Other speeds keep their own clamp
+	let mut other_speed = Config::default();
+	other_speed.physics.ship_vy_clamp[ship().speed + 1] = 400.0;
+	assert_eq!(apex(other_speed), default);
+}
+
+/// Vertical speed on the tick a ship holding up first reaches its ceiling
+fn vy_at_ceiling(restitution: f32) -> f32 {
+	let mut config = Config::default();
+	config.physics.ship_boundary_restitution = restitution;
+	let bounds = config.physics.ship_bounds;
+	let half_height = config.physics.player_height / 2.0;
+	let pathfinder = Pathfinder::with_config(Vec::new(), config);
+
+	let ship = State {
+		floor: 0.0,
+		ceiling: bounds,
+		..ship()
+	};
+	let mut state = pathfinder.simulate_step(&ship, Action::Press);
+	while state.position.y < bounds - half_height {
+		assert!(
+			state.vy > 0.0,
+			"ship stopped climbing at {}",
+			state.position.y
+		);
+		state = pathfinder.simulate_step(&state, Action::None);
+	}
+
+	assert_eq!(state.position.y, bounds - half_height);
+	state.vy
+}
+
+#[test]
+fn boundary_restitution_bounces_the_ship_off_its_ceiling() {
+	assert_eq!(vy_at_ceiling(0.0), 0.0);
+
+	let bounced = vy_at_ceiling(0.5);
+	assert!(bounced < 0.0, "vy {bounced} after hitting the ceiling");
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use redox_core::{Vec2, config::Config, pathfinder::Pathfinder, state::Action};
+
+#[test]
+fn matches_individual_steps() {
+	let pathfinder = Pathfinder::with_config(Vec::new(), Config::default());
+
+	let mut start = common::cube_at(Vec2::new(0.0, 15.0));
+	start.on_ground = true;
+
+	let (state, collided) = pathfinder.simulate_n(&start, Action::Press, 10);
+	assert!(!collided);
+
+	let mut stepped = pathfinder.simulate_step(&start, Action::Press);
+	for _ in 1..10 {
+		stepped = pathfinder.simulate_step(&stepped, Action::None);
+	}
+
+	assert_eq!(state, stepped);
+	assert!(state.pressing && state.position.y > 15.0);
+}
+
+#[test]
+fn stops_before_a_collision() {
+	// A spike a few ticks ahead of the player
+	let pathfinder =
+		Pathfinder::with_config(common::objects("kA2,0;1,8,2,100,3,15;"), Config::default());
+
+	let mut start = common::cube_at(Vec2::new(0.0, 15.0));
+	start.on_ground = true;
+
+	let (state, collided) = pathfinder.simulate_n(&start, Action::None, 240);
+	assert!(collided);
+	assert!(state.position.x > start.position.x && state.position.x < 100.0);
+	assert_eq!(pathfinder.collides(&state), None);
+	assert!(
+		pathfinder
+			.collides(&pathfinder.simulate_step(&state, Action::None))
+			.is_some()
+	);
+
+	assert_eq!(
+		pathfinder.simulate_n(&start, Action::None, 0),
+		(start, false)
+	);
+}
+
+#[test]
+fn stops_before_falling_out_of_the_level() {
+	// No ground, only a block to stand on at the start
+	let mut config = Config::default();
+	config.physics.ground_y = None;
+	let pathfinder = Pathfinder::with_config(common::objects("kA2,0;1,1,2,0,3,-15;"), config);
+
+	let mut start = common::cube_at(Vec2::new(0.0, 15.0));
+	start.on_ground = true;
+
+	let (state, collided) = pathfinder.simulate_n(&start, Action::None, 2_000);
+	assert!(collided);
+	assert!(!pathfinder.out_of_bounds(&state));
+	assert!(pathfinder.out_of_bounds(&pathfinder.simulate_step(&state, Action::None)));
+}
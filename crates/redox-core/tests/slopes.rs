@@ -0,0 +1,31 @@
+mod common;
+
+use redox_core::{Vec2, config::Config, pathfinder::Pathfinder, state::Action};
+
+#[test]
+fn cube_climbs_an_up_slope() {
+	let pathfinder = Pathfinder::with_config(common::objects(common::UP_SLOPE), Config::default());
+
+	let mut state = common::cube_at(Vec2::new(240.0, 15.0));
+	state.on_ground = true;
+
+	let mut heights = Vec::new();
+	while state.position.x < 315.0 {
+		state = pathfinder.simulate_step(&state, Action::None);
+		assert_eq!(
+			pathfinder.collides(&state),
+			None,
+			"died at x={}",
+			state.position.x
+		);
+		heights.push(state.position.y);
+	}
+
+	// Rising with x instead of stopping against the slope's box
+	assert!(heights.windows(2).all(|w| w[1] >= w[0] - 0.01));
+	assert!(
+		state.position.y > 35.0,
+		"only at y={} by the top of the slope",
+		state.position.y
+	);
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use redox_core::{
+	config::Config,
+	formats::level,
+	pathfinder::Pathfinder,
+	state::{Action, State},
+};
+
+fn setup(level_string: &str) -> (Pathfinder, State) {
+	let pathfinder = Pathfinder::with_config(common::objects(level_string), Config::default());
+	let start = pathfinder.initial_state(common::START, &level::parse_header(level_string));
+	(pathfinder, start)
+}
+
+fn jittery(dt: f32) -> Vec<(Action, f32)> {
+	(0..200)
+		.map(|i| {
+			let action = if i % 2 == 0 {
+				Action::Press
+			} else {
+				Action::Release
+			};
+			(action, dt)
+		})
+		.collect()
+}
+
+#[test]
+fn jittery_ship_path_gets_fewer_actions() {
+	// Ship start with nothing in the way, so a steady hold would do just as well
+	let (pathfinder, start) = setup("kA2,1;");
+	let jittery = jittery(pathfinder.dt());
+	assert!(pathfinder.validate_path(start, &jittery, 250.0).is_ok());
+
+	let smoothed = pathfinder.smooth_path(start, &jittery, 250.0, 8, 15.0);
+
+	assert!(
+		smoothed.len() < jittery.len() / 4,
+		"still {} actions",
+		smoothed.len()
+	);
+	assert!(pathfinder.validate_path(start, &smoothed, 250.0).is_ok());
+}
+
+#[test]
+fn keeps_short_presses_the_path_needs() {
+	let (pathfinder, start) = setup(common::SINGLE_SPIKE);
+	let solution = common::solve(common::SINGLE_SPIKE, 600.0, Config::default());
+
+	let smoothed = pathfinder.smooth_path(start, &solution.path, 600.0, 1_000, 15.0);
+
+	assert!(pathfinder.validate_path(start, &smoothed, 600.0).is_ok());
+	assert!(smoothed.iter().any(|(action, _)| *action == Action::Press));
+}
+
+#[test]
+fn smoothed_ship_stays_within_the_tolerance() {
+	let (pathfinder, start) = setup("kA2,1;");
+	let jittery = jittery(pathfinder.dt());
+
+	let smoothed = pathfinder.smooth_path(start, &jittery, 250.0, 8, 5.0);
+	assert!(smoothed.len() < jittery.len());
+
+	let original = pathfinder.simulate_trajectory(start, &jittery);
+	let replayed = pathfinder.simulate_trajectory(start, &smoothed);
+	for (before, after) in original.iter().zip(&replayed) {
+		if before.position.x >= 250.0 {
+			break;
+		}
+		let drift = (before.position - after.position).length();
+		assert!(drift <= 5.0, "drifted {drift} at x={}", before.position.x);
+	}
+}
+
+#[test]
+fn zero_tolerance_keeps_every_flicker() {
+	let (pathfinder, start) = setup("kA2,1;");
+	let jittery = jittery(pathfinder.dt());
+
+	let smoothed = pathfinder.smooth_path(start, &jittery, 250.0, 8, 0.0);
+
+	assert_eq!(smoothed.len(), jittery.len());
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use std::time::Duration;
+
+use redox_core::{
+	config::Config,
+	pathfinder::{Pathfinder, search::StopReason},
+};
+
+#[test]
+fn solve_jumps_the_spike() {
+	let pathfinder =
+		Pathfinder::with_config(common::objects(common::SINGLE_SPIKE), Config::default());
+	let result = pathfinder.solve(common::START, 600.0);
+
+	assert!(result.reached_goal);
+	assert_eq!(result.stop_reason, StopReason::Goal);
+	assert!(result.best_x >= 600.0);
+	assert!(result.nodes_expanded > 0);
+
+	let start = pathfinder.initial_state(common::START, &Default::default());
+	assert!(
+		pathfinder
+			.validate_path(start, &result.actions, 600.0)
+			.is_ok()
+	);
+}
+
+/// A wall of spikes too tall to clear
+fn spike_wall() -> String {
+	let wall: String = (0..6)
+		.map(|i| format!("1,8,2,300,3,{};", 15 + i * 30))
+		.collect();
+	format!("kA2,0;{wall}")
+}
+
+/// Stop reason and nodes expanded searching `spike_wall` under `config`
+fn stop_at_wall(config: Config) -> (StopReason, usize) {
+	let pathfinder = Pathfinder::with_config(common::objects(&spike_wall()), config);
+	let mut session = pathfinder.start_search(common::START, 600.0);
+	pathfinder.step(&mut session, 600.0);
+
+	(session.stop_reason.unwrap(), session.nodes_expanded)
+}
+
+#[test]
+fn solve_returns_the_furthest_run_when_stuck() {
+	let level = spike_wall();
+
+	let mut config = Config::default();
+	config.search.max_nodes = Some(5_000);
+	let pathfinder = Pathfinder::with_config(common::objects(&level), config);
+	let result = pathfinder.solve(common::START, 600.0);
+
+	assert!(!result.reached_goal);
+	assert_ne!(result.stop_reason, StopReason::Goal);
+	assert!(result.best_x < 300.0);
+	assert!(!result.actions.is_empty());
+}
+
+#[test]
+fn a_budget_stop_is_not_a_goal() {
+	let mut config = Config::default();
+	config.search.max_nodes = Some(50);
+	let pathfinder = Pathfinder::with_config(common::objects(common::SINGLE_SPIKE), config);
+
+	let mut session = pathfinder.start_search(common::START, 600.0);
+	pathfinder.step(&mut session, 600.0);
+
+	assert_eq!(session.stop_reason, Some(StopReason::NodeBudget));
+	assert_eq!(session.goal_reached_index, None);
+	assert_eq!(session.end_index(), session.best_x_index);
+	let best = session.all_nodes[session.best_x_index].state.position;
+	assert_eq!(session.progress().best_path.last(), Some(&best));
+}
+
+#[test]
+fn node_budget_stops_an_unsolvable_search() {
+	let mut config = Config::default();
+	config.search.max_nodes = Some(2_000);
+
+	assert_eq!(stop_at_wall(config), (StopReason::NodeBudget, 2_000));
+}
+
+#[test]
+fn time_budget_stops_an_unsolvable_search() {
+	let mut config = Config::default();
+	config.search.time_budget = Some(Duration::ZERO);
+
+	// The clock is checked before the first expansion
+	assert_eq!(stop_at_wall(config), (StopReason::Timeout, 0));
+}
@@ -0,0 +1,96 @@
+use redox_core::{Vec2, simulation::spatial_grid::SpatialGrid};
+
+fn square(center: Vec2, size: f32) -> (Vec2, Vec2) {
+	let half = Vec2::splat(size * 0.5);
+	(center - half, center + half)
+}
+
+fn query(grid: &SpatialGrid, position: Vec2) -> Vec<usize> {
+	let mut hits: Vec<usize> = grid.query(position, 10.0, 10.0).collect();
+	hits.sort_unstable();
+	hits
+}
+
+#[test]
+fn insert_is_found_by_query() {
+	let mut grid = SpatialGrid::new(&[], 128.0);
+	grid.insert(3, square(Vec2::new(50.0, 50.0), 30.0));
+
+	assert_eq!(query(&grid, Vec2::new(50.0, 50.0)), vec![3]);
+	assert!(query(&grid, Vec2::new(500.0, 50.0)).is_empty());
+}
+
+#[test]
+fn moved_object_is_only_in_new_cells() {
+	let mut grid = SpatialGrid::new(&[], 128.0);
+	let old = square(Vec2::new(50.0, 50.0), 30.0);
+	let new = square(Vec2::new(600.0, 50.0), 30.0);
+
+	grid.insert(0, old);
+	grid.insert(1, old);
+	grid.update_object(0, old, new);
+
+	assert_eq!(query(&grid, Vec2::new(50.0, 50.0)), vec![1]);
+	assert_eq!(query(&grid, Vec2::new(600.0, 50.0)), vec![0]);
+}
+
+#[test]
+fn move_within_same_cells_keeps_object() {
+	let mut grid = SpatialGrid::new(&[], 128.0);
+	let old = square(Vec2::new(50.0, 50.0), 30.0);
+
+	grid.insert(0, old);
+	grid.update_object(0, old, square(Vec2::new(60.0, 55.0), 30.0));
+
+	assert_eq!(query(&grid, Vec2::new(60.0, 55.0)), vec![0]);
+}
+
+#[test]
+fn clear_and_rebuild_drops_stale_entries() {
+	let mut grid = SpatialGrid::new(&[], 128.0);
+	grid.insert(7, square(Vec2::new(50.0, 50.0), 30.0));
+	grid.clear_and_rebuild(&[]);
+
+	assert!(query(&grid, Vec2::new(50.0, 50.0)).is_empty());
+}
+
+#[test]
+fn query_returns_each_overlapping_object_once() {
+	let mut grid = SpatialGrid::new(&[], 32.0);
+	let mut boxes = Vec::new();
+
+	// Objects wide enough to span several cells so duplicates would show up
+	for i in 0..200 {
+		let center = Vec2::new((i * 37 % 1000) as f32, (i * 53 % 400) as f32);
+		let aabb = square(center, 20.0 + (i % 7) as f32 * 15.0);
+		grid.insert(i, aabb);
+		boxes.push(aabb);
+	}
+
+	for probe in [
+		Vec2::new(100.0, 100.0),
+		Vec2::new(500.0, 250.0),
+		Vec2::new(-50.0, 0.0),
+	] {
+		let mut hits: Vec<usize> = grid.query(probe, 90.0, 90.0).collect();
+		let count = hits.len();
+		hits.sort_unstable();
+		hits.dedup();
+		assert_eq!(hits.len(), count, "duplicate index returned near {probe}");
+
+		// Everything sharing a cell with the probe, whether or not it overlaps exactly
+		let cell = |v: f32| (v / 32.0).floor() as i32;
+		let expected: Vec<usize> = boxes
+			.iter()
+			.enumerate()
+			.filter(|(_, (min, max))| {
+				cell(min.x) <= cell(probe.x + 45.0)
+					&& cell(max.x) >= cell(probe.x - 45.0)
+					&& cell(min.y) <= cell(probe.y + 45.0)
+					&& cell(max.y) >= cell(probe.y - 45.0)
+			})
+			.map(|(i, _)| i)
+			.collect();
+		assert_eq!(hits, expected);
+	}
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use redox_core::{config::Config, formats::level, pathfinder::Pathfinder, state::GameMode};
+
+/// A spider portal into a corridor with its roof's underside at y 120, floor spikes
+/// at x 420 and 1020 and a hanging spike at x 720 between them, so the spider has to
+/// run on the roof, then the floor, then the roof again
+fn corridor() -> String {
+	let mut level = String::from("kA2,0;1,1331,2,150,3,45;");
+	for column in 0..50 {
+		level += &format!("1,1,2,{},3,135;", 165 + 30 * column);
+	}
+	for x in [390, 420, 450, 990, 1020, 1050] {
+		level += &format!("1,8,2,{x},3,15;");
+	}
+	for x in [690, 720, 750] {
+		level += &format!("1,8,2,{x},3,105,6,180;");
+	}
+	level
+}
+
+#[test]
+fn spider_crosses_a_corridor_by_alternating_taps() {
+	let level = corridor();
+	let solution = common::solve(&level, 1300.0, Config::default());
+	assert!(
+		solution.reached_goal,
+		"stopped with {:?} at x={}",
+		solution.stop_reason, solution.final_x
+	);
+
+	let pathfinder = Pathfinder::with_config(common::objects(&level), Config::default());
+	let start = pathfinder.initial_state(common::START, &level::parse_header(&level));
+	let states = pathfinder.simulate_trajectory(start, &solution.path);
+
+	let surface_at = |x: f32| {
+		let state = states.iter().find(|s| s.position.x >= x).unwrap();
+		assert_eq!(state.mode, GameMode::Spider);
+		assert!(state.on_ground, "airborne at x {x}");
+		state.gravity_flipped
+	};
+	assert!(surface_at(420.0), "on the floor over the first spikes");
+	assert!(!surface_at(720.0), "on the roof under the hanging spike");
+	assert!(surface_at(1020.0), "on the floor over the last spikes");
+
+	// Each tap teleports straight across, there is never a frame in between
+	for pair in states.windows(2) {
+		if pair[0].gravity_flipped != pair[1].gravity_flipped {
+			assert!((pair[1].position.y - pair[0].position.y).abs() > 60.0);
+		}
+	}
+}
@@ -0,0 +1,154 @@
+mod common;
+
+use redox_core::{
+	config::{Config, ProgressWindow},
+	formats::level,
+	pathfinder::{Pathfinder, search::StopReason},
+};
+
+/// A column of blocks at x=600 too tall to jump, leaving the cube to shuffle through
+/// jump timings in front of it forever
+fn walled_level() -> String {
+	let mut level = String::from(common::FLAT);
+	for row in 0..12 {
+		level += &format!("1,1,2,600,3,{};", 15 + row * 30);
+	}
+	level
+}
+
+fn windowed(max_nodes: usize) -> Config {
+	let mut config = Config::default();
+	config.search.max_nodes = Some(max_nodes);
+	config.search.progress_window = Some(ProgressWindow {
+		nodes: 20_000,
+		samples: 10,
+		min_rate: 1.0,
+		patience: 3,
+	});
+	config
+}
+
+#[test]
+fn stuck_search_stops_early() {
+	let solution = common::solve(&walled_level(), 900.0, windowed(500_000));
+
+	assert_eq!(solution.stop_reason, Some(StopReason::Stagnation));
+	assert!(
+		solution.nodes_expanded < 50_000,
+		"took {} expansions to notice",
+		solution.nodes_expanded
+	);
+	assert!(
+		solution.final_x > 550.0,
+		"stopped at x={}",
+		solution.final_x
+	);
+}
+
+#[test]
+fn steady_progress_is_left_alone() {
+	let mut level = String::from(common::FLAT);
+	for i in 0..20 {
+		level += &format!("1,8,2,{},3,15;", 300 + i * 240);
+	}
+
+	// A window short enough to fill several times over on the way
+	let mut config = windowed(2_000_000);
+	config.search.progress_window.as_mut().unwrap().nodes = 1_000;
+
+	let solution = common::solve(&level, 5_200.0, config);
+
+	assert!(
+		solution.reached_goal,
+		"stopped with {:?}",
+		solution.stop_reason
+	);
+}
+
+/// Three spikes and then a block overhead at x 510 that a high jump hits, so the greedy
+/// line has to be given up for a lower one
+const DIP: &str = "kA2,0;1,8,2,300,3,15;1,8,2,360,3,15;1,8,2,450,3,15;1,1,2,510,3,105;";
+
+fn checked_every(nodes: usize, adaptive_weight: bool) -> Config {
+	let mut config = Config::default();
+	config.search.stagnation_check_interval = nodes;
+	config.search.adaptive_weight = adaptive_weight;
+	config.search.max_nodes = Some(200_000);
+	config
+}
+
+#[test]
+fn adaptive_weight_clears_a_dip_the_static_weight_stalls_on() {
+	let fixed = common::solve(DIP, 780.0, checked_every(1_000, false));
+	assert_eq!(fixed.stop_reason, Some(StopReason::Stagnation));
+
+	let adaptive = common::solve(DIP, 780.0, checked_every(1_000, true));
+	assert!(
+		adaptive.reached_goal,
+		"stopped with {:?} at x={}",
+		adaptive.stop_reason, adaptive.final_x
+	);
+}
+
+#[test]
+fn healthy_window_leaves_a_weight_it_did_not_relax() {
+	let mut level = String::from(common::FLAT);
+	for i in 0..20 {
+		level += &format!("1,8,2,{},3,15;", 300 + i * 240);
+	}
+	let mut config = windowed(2_000_000);
+	config.search.adaptive_weight = true;
+	config.search.progress_window.as_mut().unwrap().nodes = 100;
+
+	let pathfinder = Pathfinder::with_config(common::objects(&level), config);
+	let start = pathfinder.initial_state(common::START, &level::parse_header(&level));
+	let mut session = pathfinder.start_search_from(start, 5_200.0);
+
+	// As the stagnation check leaves it after relaxing once
+	session.heuristic_weight = 1.4;
+	for _ in 0..2_000 {
+		pathfinder.step_single(&mut session, 5_200.0);
+	}
+
+	assert!(session.best_x > 600.0, "only got to x={}", session.best_x);
+	assert_eq!(session.heuristic_weight, 1.4);
+}
+
+#[test]
+fn weight_stays_relaxed_until_both_detectors_recover() {
+	let mut level = String::from(common::FLAT);
+	for i in 0..20 {
+		level += &format!("1,8,2,{},3,15;", 300 + i * 240);
+	}
+	let mut config = windowed(2_000_000);
+	config.search.adaptive_weight = true;
+	config.search.stagnation_check_interval = 100;
+	config.search.min_progress_per_interval = 0.0;
+	config.search.progress_window.as_mut().unwrap().nodes = 1_000;
+	let base = config.search.base_weight();
+
+	let pathfinder = Pathfinder::with_config(common::objects(&level), config);
+	let start = pathfinder.initial_state(common::START, &level::parse_header(&level));
+	let mut session = pathfinder.start_search_from(start, 5_200.0);
+
+	// As the interval check and then the window leave it after relaxing once each
+	let once = (base + 1.0) * 0.5;
+	let twice = (once + 1.0) * 0.5;
+	session.heuristic_weight = twice;
+	session.interval_relaxed_from = Some(base);
+	session.window_relaxed_from = Some(once);
+
+	// The interval check recovers first, the window hasn't taken enough samples yet
+	while session.nodes_expanded < 150 {
+		pathfinder.step_single(&mut session, 5_200.0);
+	}
+	assert_eq!(session.heuristic_weight, twice);
+	assert_eq!(session.interval_relaxed_from, None);
+	assert_eq!(session.window_relaxed_from, Some(base));
+
+	while session.nodes_expanded < 2_000 && session.stop_reason.is_none() {
+		pathfinder.step_single(&mut session, 5_200.0);
+	}
+	assert_eq!(session.window_relaxed_from, None);
+	assert_eq!(session.heuristic_weight, base);
+}
@@ -0,0 +1,136 @@
+mod common;
+
+use redox_core::{
+	Vec2,
+	state::{KeyField, KeyLayout, State, StateKey},
+};
+
+/// Long-level layout giving x most of the y and velocity bits
+const WIDE_X: KeyLayout = KeyLayout {
+	x_bits: 48,
+	y_bits: 20,
+	vy_bits: 20,
+	ceiling_bits: 16,
+};
+
+fn at(x: f32, y: f32) -> State {
+	common::cube_at(Vec2::new(x, y))
+}
+
+fn key(state: &State, layout: KeyLayout) -> StateKey {
+	StateKey::with_layout(state, 1.0, 1.0, 10.0, layout)
+}
+
+#[test]
+fn default_layout_matches_from_state() {
+	let mut state = at(1234.5, 67.0);
+	state.vy = -345.0;
+	state.pressing = true;
+	state.jump_hold_frames = 7;
+
+	assert_eq!(
+		StateKey::from_state(&state, 1.0, 1.0, 10.0),
+		key(&state, KeyLayout::default())
+	);
+	assert!(KeyLayout::default().is_valid());
+	assert!(WIDE_X.is_valid());
+	assert!(
+		!KeyLayout {
+			x_bits: 64,
+			..WIDE_X
+		}
+		.is_valid()
+	);
+}
+
+#[test]
+fn values_at_field_boundaries_stay_distinct() {
+	let layout = KeyLayout {
+		x_bits: 12,
+		y_bits: 10,
+		vy_bits: 8,
+		ceiling_bits: 8,
+	};
+	// Largest and smallest values each field holds, then their neighbours inside it
+	let x_max = 2047.0;
+	let y_max = 511.0;
+
+	let states = [
+		at(x_max, 15.0),
+		at(x_max - 1.0, 15.0),
+		at(-x_max - 1.0, 15.0),
+		at(-x_max, 15.0),
+		at(0.0, y_max),
+		at(0.0, y_max - 1.0),
+		at(0.0, -y_max - 1.0),
+		State {
+			vy: 1270.0,
+			..at(0.0, 15.0)
+		},
+		State {
+			vy: -1280.0,
+			..at(0.0, 15.0)
+		},
+	];
+
+	for (i, a) in states.iter().enumerate() {
+		assert_eq!(layout.overflow(a, 1.0, 1.0, 10.0), None, "state {i}");
+		for (j, b) in states.iter().enumerate().skip(i + 1) {
+			assert_ne!(key(a, layout), key(b, layout), "states {i} and {j}");
+		}
+	}
+
+	// One past the end wraps onto the other end, and is reported
+	let past = at(x_max + 1.0, 15.0);
+	assert_eq!(
+		layout.overflow(&past, 1.0, 1.0, 10.0),
+		Some((KeyField::X, 2048))
+	);
+	assert_eq!(key(&past, layout), key(&at(-x_max - 1.0, 15.0), layout));
+
+	let sinking = State {
+		vy: -1290.0,
+		..at(0.0, 15.0)
+	};
+	assert_eq!(
+		layout.overflow(&sinking, 1.0, 1.0, 10.0),
+		Some((KeyField::Vy, -129))
+	);
+}
+
+#[test]
+fn wider_x_field_separates_far_positions() {
+	// 2^31 and 2^31 + 2^32 units apart in x, the same low 32 bits
+	let near = at(2_147_483_648.0, 15.0);
+	let far = at(6_442_450_944.0, 15.0);
+
+	assert!(
+		KeyLayout::default()
+			.overflow(&near, 1.0, 1.0, 10.0)
+			.is_some()
+	);
+	assert_eq!(
+		key(&near, KeyLayout::default()),
+		key(&far, KeyLayout::default())
+	);
+
+	assert_eq!(WIDE_X.overflow(&far, 1.0, 1.0, 10.0), None);
+	assert_ne!(key(&near, WIDE_X), key(&far, WIDE_X));
+}
+
+#[test]
+fn flags_follow_the_quantized_fields() {
+	let state = at(100.0, 15.0);
+	let flipped = State {
+		gravity_flipped: true,
+		..state
+	};
+
+	for layout in [KeyLayout::default(), WIDE_X] {
+		let offset = layout.x_bits + layout.y_bits + layout.vy_bits + layout.ceiling_bits;
+		assert_eq!(
+			key(&state, layout).0 ^ key(&flipped, layout).0,
+			1 << (offset + 1)
+		);
+	}
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use redox_core::{
+	config::Config,
+	formats::level,
+	pathfinder::Pathfinder,
+	state::{Action, GameMode},
+};
+
+// Swing start, then spike pairs on the floor and hanging from the ceiling in turn, so
+// the player can't stay pinned to either side
+const CORRIDOR: &str = "kA2,7;\
+                        1,8,2,300,3,15;1,8,2,300,3,45;1,8,2,600,3,285;1,8,2,600,3,255;\
+                        1,8,2,900,3,15;1,8,2,900,3,45;1,8,2,1200,3,285;1,8,2,1200,3,255;\
+                        1,8,2,1500,3,15;1,8,2,1500,3,45;";
+
+#[test]
+fn press_switches_which_way_is_down() {
+	let pathfinder = Pathfinder::with_config(Vec::new(), Config::default());
+	let start = pathfinder.initial_state(common::START, &level::parse_header("kA2,7;"));
+	assert_eq!(start.mode, GameMode::Swing);
+	assert!(!start.on_ground);
+
+	let pressed = pathfinder.simulate_step(&start, Action::Press);
+	assert!(pressed.gravity_flipped);
+	assert!(pressed.vy > 0.0);
+
+	// Holding does nothing more, only the next press flips back
+	let mut state = pressed;
+	for _ in 0..30 {
+		state = pathfinder.simulate_step(&state, Action::None);
+	}
+	assert!(state.gravity_flipped);
+	assert!(state.position.y > pressed.position.y);
+	assert!(state.rotation > 0.0);
+
+	let released = pathfinder.simulate_step(&state, Action::Release);
+	let flipped_back = pathfinder.simulate_step(&released, Action::Press);
+	assert!(!flipped_back.gravity_flipped);
+}
+
+#[test]
+fn swings_through_alternating_hazard_corridor() {
+	let solution = common::solve(CORRIDOR, 1900.0, Config::default());
+	assert!(solution.reached_goal, "stopped at x {}", solution.final_x);
+	// Pinned to the floor at the start, so it has to flip at least once to get off it
+	assert!(solution.presses() >= 1, "{} presses", solution.presses());
+
+	let pathfinder = Pathfinder::with_config(common::objects(CORRIDOR), Config::default());
+	let start = pathfinder.initial_state(common::START, &level::parse_header(CORRIDOR));
+	assert!(
+		pathfinder
+			.validate_path(start, &solution.path, 1900.0)
+			.is_ok()
+	);
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use redox_core::{
+	Vec2,
+	config::Config,
+	pathfinder::Pathfinder,
+	state::{Action, State},
+};
+
+/// A teleport portal at x 300 with its exit `dy` units above the player
+fn teleport(dy: f32) -> Pathfinder {
+	let level = format!("kA2,0;1,747,2,300,3,45,54,{dy};1,1,2,900,3,15;");
+	Pathfinder::with_config(common::objects(&level), Config::default())
+}
+
+fn on_ground_at(x: f32) -> State {
+	State {
+		on_ground: true,
+		..common::cube_at(Vec2::new(x, 15.0))
+	}
+}
+
+/// Steps from the ground until the player's y jumps by more than a tick of falling
+/// could, returning the state before and after
+fn first_jump(pathfinder: &Pathfinder) -> (State, State) {
+	let mut state = on_ground_at(250.0);
+	for _ in 0..200 {
+		let next = pathfinder.simulate_step(&state, Action::None);
+		if (next.position.y - state.position.y).abs() > 10.0 {
+			return (state, next);
+		}
+		state = next;
+	}
+	panic!("never teleported");
+}
+
+#[test]
+fn teleport_moves_the_player_up_by_its_offset() {
+	let pathfinder = teleport(120.0);
+	let (before, after) = first_jump(&pathfinder);
+
+	assert!((after.position.y - (before.position.y + 120.0)).abs() < 0.5);
+	assert!(!after.on_ground);
+}
+
+#[test]
+fn short_teleport_fires_once_per_pass() {
+	// 40 units up still overlaps the 75 tall portal, it must not keep lifting the player
+	let pathfinder = teleport(40.0);
+
+	let mut state = on_ground_at(250.0);
+	let mut jumps = 0;
+	let mut highest = 0.0f32;
+	for _ in 0..120 {
+		let next = pathfinder.simulate_step(&state, Action::None);
+		if next.position.y - state.position.y > 10.0 {
+			jumps += 1;
+		}
+		highest = highest.max(next.position.y);
+		state = next;
+	}
+
+	assert_eq!(jumps, 1);
+	assert!(highest < 15.0 + 40.0 + 1.0, "rose to {highest}");
+}
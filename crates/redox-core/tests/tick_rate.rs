@@ -0,0 +1,39 @@
+mod common;
+
+use redox_core::{config::Config, replay};
+
+fn config_at(tps: f32) -> Config {
+	let mut config = Config::default();
+	config.physics.tps = tps;
+	config
+}
+
+#[test]
+fn sixty_tps_still_solves_a_simple_level() {
+	let solution = common::solve(common::SPIKES_AND_BLOCK, 1100.0, config_at(60.0));
+	assert!(solution.reached_goal);
+	assert!(solution.final_x >= 1100.0);
+}
+
+#[test]
+fn sixty_tps_covers_the_same_ground_in_a_quarter_of_the_ticks() {
+	let fast = common::solve(common::FLAT, 600.0, config_at(240.0));
+	let slow = common::solve(common::FLAT, 600.0, config_at(60.0));
+
+	let seconds = |path: &[(_, f32)]| path.iter().map(|(_, d)| d).sum::<f32>();
+	let (fast, slow) = (seconds(&fast.path), seconds(&slow.path));
+	assert!(
+		(fast - slow).abs() < 1.0 / 60.0,
+		"{fast}s at 240 TPS, {slow}s at 60"
+	);
+}
+
+#[test]
+fn replay_header_carries_the_configured_tps() {
+	let config = config_at(60.0);
+	let solution = common::solve(common::SINGLE_SPIKE, 600.0, config.clone());
+
+	let bytes = replay::encode_gdr(&solution.path, config.physics.tps).unwrap();
+	let replay = replay::Replay::deserialize(&bytes).unwrap();
+	assert_eq!(replay.framerate, 60.0);
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use std::{
+	collections::HashSet,
+	sync::{Arc, Mutex},
+};
+
+use redox_core::{config::Config, pathfinder::Pathfinder};
+use tracing::{Subscriber, span};
+use tracing_subscriber::{Layer, layer::Context, prelude::*, registry::LookupSpan};
+
+/// Remembers the name of every span opened under it
+#[derive(Clone, Default)]
+struct SpanNames(Arc<Mutex<HashSet<&'static str>>>);
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for SpanNames {
+	fn on_new_span(&self, attrs: &span::Attributes<'_>, _: &span::Id, _: Context<'_, S>) {
+		self.0.lock().unwrap().insert(attrs.metadata().name());
+	}
+}
+
+#[test]
+fn a_short_solve_records_its_phases() {
+	let names = SpanNames::default();
+	let subscriber = tracing_subscriber::registry().with(names.clone());
+
+	tracing::subscriber::with_default(subscriber, || {
+		let pathfinder =
+			Pathfinder::with_config(common::objects(common::SINGLE_SPIKE), Config::default());
+		let result = pathfinder.solve(common::START, 400.0);
+		assert!(result.reached_goal);
+
+		let mut session = pathfinder.start_search(common::START, 400.0);
+		assert!(pathfinder.search_iter(&mut session, 400.0).last().is_some());
+	});
+
+	let names = names.0.lock().unwrap();
+	for name in [
+		"start_search",
+		"step",
+		"step_batch",
+		"simulate_step",
+		"colliding_object",
+	] {
+		assert!(names.contains(name), "no {name} span in {names:?}");
+	}
+}
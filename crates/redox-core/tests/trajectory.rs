@@ -0,0 +1,25 @@
+mod common;
+
+use redox_core::{config::Config, formats::level, pathfinder::Pathfinder};
+
+#[test]
+fn trajectory_has_a_state_per_frame_plus_the_start() {
+	let config = Config::default();
+	let dt = config.physics.dt();
+	let solution = common::solve(common::SPIKES_AND_BLOCK, 1100.0, config.clone());
+
+	let pathfinder = Pathfinder::with_config(common::objects(common::SPIKES_AND_BLOCK), config);
+	let header = level::parse_header(common::SPIKES_AND_BLOCK);
+	let start = pathfinder.initial_state(common::START, &header);
+	let states = pathfinder.simulate_trajectory(start, &solution.path);
+
+	let frames: usize = solution
+		.path
+		.iter()
+		.map(|&(_, duration)| ((duration / dt).round() as usize).max(1))
+		.sum();
+
+	assert_eq!(states.len(), frames + 1);
+	assert_eq!(states[0], start);
+	assert!((states.last().unwrap().position.x - solution.final_x).abs() < 1e-3);
+}
@@ -0,0 +1,144 @@
+mod common;
+
+use redox_core::{
+	Vec2,
+	config::Config,
+	formats::level,
+	pathfinder::{
+		Pathfinder,
+		validate::{FailureReason, PathValidation},
+	},
+	simulation::collision::CollisionKind,
+	state::{Action, GameMode, State},
+};
+
+const GOAL_X: f32 = 600.0;
+
+fn pathfinder() -> Pathfinder {
+	Pathfinder::with_config(common::objects(common::SINGLE_SPIKE), Config::default())
+}
+
+#[test]
+fn solved_path_validates() {
+	let solution = common::solve(common::SINGLE_SPIKE, GOAL_X, Config::default());
+	let pathfinder = pathfinder();
+	let start = pathfinder.initial_state(common::START, &level::parse_header(common::SINGLE_SPIKE));
+
+	match pathfinder.validate_path(start, &solution.path, GOAL_X) {
+		PathValidation::Ok { final_x, .. } => assert!(final_x >= GOAL_X),
+		failed => panic!("expected the solver's own path to validate, got {failed:?}"),
+	}
+}
+
+#[test]
+fn path_without_its_jump_fails_on_the_spike() {
+	let solution = common::solve(common::SINGLE_SPIKE, GOAL_X, Config::default());
+	let pathfinder = pathfinder();
+	let start = pathfinder.initial_state(common::START, &level::parse_header(common::SINGLE_SPIKE));
+
+	// Same timings, but the jump never happens
+	let corrupted: Vec<_> = solution
+		.path
+		.iter()
+		.map(|&(_, duration)| (Action::None, duration))
+		.collect();
+
+	// The first tick of an idle run that overlaps the spike, found independently
+	let idle = pathfinder.simulate_trajectory(start, &corrupted);
+	let expected_frame = idle
+		.iter()
+		.position(|state| pathfinder.collides(state).is_some())
+		.expect("running straight should hit the spike");
+
+	match pathfinder.validate_path(start, &corrupted, GOAL_X) {
+		PathValidation::Failed { frame, reason, .. } => {
+			assert_eq!(frame, expected_frame);
+			assert_eq!(reason, FailureReason::Collision {
+				object_id: 8,
+				kind: CollisionKind::Hazard
+			});
+		}
+		ok => panic!("expected a failure, got {ok:?}"),
+	}
+}
+
+#[test]
+fn ship_below_the_origin_is_not_out_of_bounds() {
+	// A floor of blocks 300 units under the ground, with the ship flying down onto it
+	let level = "kA2,0;1,1,2,15,3,-315;1,1,2,45,3,-315;1,1,2,75,3,-315;";
+	let pathfinder = Pathfinder::with_config(common::objects(level), Config::default());
+
+	let start = State {
+		mode: GameMode::Ship,
+		floor: -300.0,
+		ceiling: 0.0,
+		..common::cube_at(Vec2::new(0.0, -150.0))
+	};
+	let path = [(Action::None, 1.0)];
+
+	match pathfinder.validate_path(start, &path, 200.0) {
+		PathValidation::Ok { .. } => {}
+		failed => panic!("expected the dive to validate, got {failed:?}"),
+	}
+	let end = *pathfinder.simulate_trajectory(start, &path).last().unwrap();
+	assert!(end.position.y < -250.0, "ended at y {}", end.position.y);
+}
+
+#[test]
+fn flipped_player_falls_out_through_the_ceiling() {
+	let mut config = Config::default();
+	config.physics.world_ceiling = Some(10_000.0);
+	config.physics.world_ceiling_y = Some(500.0);
+	let pathfinder = Pathfinder::with_config(common::objects(common::SINGLE_SPIKE), config);
+
+	let start = State {
+		gravity_flipped: true,
+		..common::cube_at(Vec2::new(0.0, 100.0))
+	};
+
+	match pathfinder.validate_path(start, &[(Action::None, 2.0)], GOAL_X) {
+		PathValidation::Failed { reason, state, .. } => {
+			assert_eq!(reason, FailureReason::FellOut);
+			assert!(state.position.y > 500.0);
+		}
+		ok => panic!("expected a failure, got {ok:?}"),
+	}
+
+	// Unflipped, the same bound doesn't stop a player going up
+	assert!(!pathfinder.out_of_bounds(&common::cube_at(Vec2::new(0.0, 600.0))));
+}
+
+#[test]
+fn furthest_run_of_unsolvable_level_reports_its_death() {
+	// A spike to jump, then a wall twelve blocks tall
+	let mut level = String::from(common::SINGLE_SPIKE);
+	for row in 0..12 {
+		level += &format!("1,1,2,600,3,{};", 15 + row * 30);
+	}
+
+	let mut config = Config::default();
+	config.search.max_nodes = Some(20_000);
+	let solution = common::solve(&level, 900.0, config);
+	assert!(!solution.reached_goal);
+
+	let pathfinder = Pathfinder::with_config(common::objects(&level), Config::default());
+	let start = pathfinder.initial_state(common::START, &level::parse_header(&level));
+	let validation = pathfinder.validate_path(start, &solution.path, 900.0);
+
+	match &validation {
+		PathValidation::Failed { reason, state, .. } => {
+			assert_eq!(*reason, FailureReason::Collision {
+				object_id: 1,
+				kind: CollisionKind::SolidWall
+			});
+			assert!(state.position.x > 550.0);
+		}
+		ok => panic!("expected a failure, got {ok:?}"),
+	}
+
+	let report = validation.to_string();
+	assert!(
+		report.starts_with("dies on object 1 (solid wall) on frame"),
+		"{report}"
+	);
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use redox_core::{
+	config::Config,
+	pathfinder::{
+		Pathfinder,
+		search::{SearchSession, StopReason},
+	},
+};
+
+/// A mirror portal that turns the player around right at the start, so the goal can
+/// never be reached
+const MIRRORED: &str = "kA2,0;1,45,2,30,3,15;";
+/// Close to the start, so there is little room to explore before the wall
+const START_WALL: f32 = -20.0;
+const GOAL_X: f32 = 600.0;
+
+fn search(config: Config) -> SearchSession {
+	let pathfinder = Pathfinder::with_config(common::objects(MIRRORED), config);
+	let mut session = pathfinder.start_search(common::START, GOAL_X);
+	pathfinder.step(&mut session, GOAL_X);
+	session
+}
+
+#[test]
+fn start_wall_stops_backward_exploration() {
+	let mut config = Config::default();
+	config.search.max_nodes = Some(20_000);
+	config.physics.world_start_x = Some(START_WALL);
+	let session = search(config);
+
+	assert_eq!(session.stop_reason, Some(StopReason::Exhausted));
+	assert!(session.nodes_expanded < 5_000, "{}", session.nodes_expanded);
+	assert!(
+		session
+			.all_nodes
+			.iter()
+			.all(|node| node.state.position.x >= START_WALL)
+	);
+}
+
+#[test]
+fn without_the_wall_the_search_runs_off_to_the_left() {
+	let mut config = Config::default();
+	config.search.max_nodes = Some(20_000);
+	config.physics.world_start_x = Some(f32::MIN);
+	let session = search(config);
+
+	assert_eq!(session.stop_reason, Some(StopReason::NodeBudget));
+	assert!(
+		session
+			.all_nodes
+			.iter()
+			.any(|node| node.state.position.x < START_WALL)
+	);
+}
+
+#[test]
+fn walls_are_out_of_bounds() {
+	let mut config = Config::default();
+	config.physics.world_end_x = Some(400.0);
+	let pathfinder = Pathfinder::with_config(common::objects(common::FLAT), config);
+
+	assert!(!pathfinder.out_of_bounds(&common::cube_at(redox_core::Vec2::new(399.0, 15.0))));
+	assert!(pathfinder.out_of_bounds(&common::cube_at(redox_core::Vec2::new(401.0, 15.0))));
+
+	// The start wall defaults to 100 units before x = 0
+	assert!(!pathfinder.out_of_bounds(&common::cube_at(redox_core::Vec2::new(-99.0, 15.0))));
+	assert!(pathfinder.out_of_bounds(&common::cube_at(redox_core::Vec2::new(-101.0, 15.0))));
+}